@@ -1,55 +1,79 @@
-use rkyv::{rancor::Error, with::AsBox, Archive, Deserialize, Serialize};
+use rkyv::rancor::Error;
 
-// This is the version used by the older client, which can read newer versions
-// from senders.
-#[derive(Archive, Deserialize, Serialize)]
-#[archive(check_bytes)]
-struct ExampleV1 {
-    a: i32,
-    b: u32,
-}
+mod v1 {
+    use rkyv::{Archive, Deserialize, Serialize};
 
-// This is the version used by the newer client, which can send newer versions
-// to receivers.
-#[derive(Archive, Deserialize, Serialize)]
-#[archive(check_bytes)]
-struct ExampleV2 {
-    a: i32,
-    b: i32,
-    c: String,
+    // This is the version used by the older client, which can read newer
+    // versions from senders.
+    //
+    // Extensible structs store their fields out-of-line along with their length
+    // and version, so that newer versions can be viewed as the older version.
+    #[derive(Archive, Deserialize, Serialize)]
+    #[archive(extensible, check_bytes)]
+    pub struct Example {
+        pub a: i32,
+        pub b: u32,
+    }
 }
 
-// This wrapper type serializes the contained value out-of-line so that newer
-// versions can be viewed as the older version.
-//
-// In a complete message format, sending a version number along with the buffer
-// would allow clients to reject incompatible messages before validating the
-// buffer.
-#[derive(Archive, Deserialize, Serialize)]
-#[repr(transparent)]
-#[archive(check_bytes)]
-struct Versioned<T>(#[with(AsBox)] pub T);
+mod v2 {
+    use rkyv::{Archive, Deserialize, Serialize};
+
+    // This is the version used by the newer client, which can send newer
+    // versions to receivers.
+    //
+    // New fields are added to the end of the struct and marked with the version
+    // they were added in. Older versions don't have them, so they must have a
+    // default value.
+    #[derive(Archive, Deserialize, Serialize)]
+    #[archive(extensible, check_bytes)]
+    pub struct Example {
+        pub a: i32,
+        pub b: u32,
+        #[archive(since = 1)]
+        pub c: String,
+    }
+}
 
 // This is some code running on the older client. It accepts the older version
 // of the struct and prints out the `a` and `b` fields.
-fn print_v1(value: &ArchivedExampleV1) {
-    println!("v1: a = {}, b = {}", value.a, value.b);
+fn print_v1(value: &v1::ArchivedExample) {
+    println!(
+        "v1 (archived as version {}): a = {}, b = {}",
+        value.version(),
+        value.a(),
+        value.b(),
+    );
 }
 
 // This is some code running on the newer client. It can also print out the `c`
-// field for newer versions.
-fn print_v2(value: &ArchivedExampleV2) {
-    println!("v2: a = {}, b = {}, c = {}", value.a, value.b, value.c);
+// field when it's present.
+fn print_v2(value: &v2::ArchivedExample) {
+    match value.c() {
+        Some(c) => println!(
+            "v2 (archived as version {}): a = {}, b = {}, c = {}",
+            value.version(),
+            value.a(),
+            value.b(),
+            c,
+        ),
+        None => println!(
+            "v2 (archived as version {}): a = {}, b = {}, c is missing",
+            value.version(),
+            value.a(),
+            value.b(),
+        ),
+    }
 }
 
 fn main() {
     // These two different versions of the type will be serialized and accessed.
-    let v1 = Versioned(ExampleV1 { a: 10, b: 20 });
-    let v2 = Versioned(ExampleV2 {
+    let v1 = v1::Example { a: 10, b: 20 };
+    let v2 = v2::Example {
         a: 30,
         b: 50,
         c: "hello world".to_string(),
-    });
+    };
 
     // v1 is serialized into v1_bytes
     let v1_bytes =
@@ -60,29 +84,28 @@ fn main() {
 
     // We can view a v1 as a v1
     let v1_as_v1 =
-        rkyv::access::<rkyv::Archived<Versioned<ExampleV1>>, Error>(&v1_bytes)
-            .unwrap();
-    print_v1(&v1_as_v1.0);
+        rkyv::access::<v1::ArchivedExample, Error>(&v1_bytes).unwrap();
+    print_v1(v1_as_v1);
 
     // We can view a v2 as a v1
     let v2_as_v1 =
-        rkyv::access::<rkyv::Archived<Versioned<ExampleV1>>, Error>(&v2_bytes)
-            .unwrap();
-    print_v1(&v2_as_v1.0);
+        rkyv::access::<v1::ArchivedExample, Error>(&v2_bytes).unwrap();
+    print_v1(v2_as_v1);
 
-    // And we can view a v2 as a v2
+    // We can view a v2 as a v2
     let v2_as_v2 =
-        rkyv::access::<rkyv::Archived<Versioned<ExampleV2>>, Error>(&v2_bytes)
-            .unwrap();
-    print_v2(&v2_as_v2.0);
+        rkyv::access::<v2::ArchivedExample, Error>(&v2_bytes).unwrap();
+    print_v2(v2_as_v2);
 
-    // But we can't view a v1 as a v2 because v1 is not forward-compatible with
-    // v2
-    if rkyv::access::<rkyv::Archived<Versioned<ExampleV2>>, Error>(&v1_bytes)
-        .is_ok()
-    {
-        panic!("v1 bytes should not validate as v2");
-    } else {
-        println!("verified that v1 cannot be viewed as v2");
-    }
+    // And we can view a v1 as a v2, which is missing the fields added in v2
+    let v1_as_v2 =
+        rkyv::access::<v2::ArchivedExample, Error>(&v1_bytes).unwrap();
+    print_v2(v1_as_v2);
+
+    // Deserializing a v1 as a v2 fills in the missing fields with their
+    // default values
+    let deserialized =
+        rkyv::from_bytes::<v2::Example, Error>(&v1_bytes).unwrap();
+    assert_eq!(deserialized.c, "");
+    println!("verified that v1 can be deserialized as v2");
 }
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+use benchlib::{bench_dataset, divan, Rng};
+use rkyv::{rancor::Panic, Archive, Archived, Deserialize, Serialize};
+
+#[derive(Archive, Serialize, Deserialize, Clone, PartialEq)]
+#[archive(check_bytes)]
+pub struct Index {
+    pub entries: HashMap<u32, u32>,
+}
+
+pub fn generate_index() -> Index {
+    let mut rng = benchlib::rng();
+
+    const ENTRIES: usize = 100_000;
+    Index {
+        entries: (0..ENTRIES).map(|_| (rng.gen(), rng.gen())).collect(),
+    }
+}
+
+bench_dataset!(Index = generate_index());
+
+#[divan::bench(min_time = std::time::Duration::from_secs(3))]
+pub fn lookup(bencher: divan::Bencher) {
+    let index = generate_index();
+    let bytes = rkyv::to_bytes::<Panic>(&index).unwrap();
+    let archived = rkyv::access::<Archived<Index>, Panic>(&bytes).unwrap();
+
+    // Half of the keys are present in the map and half are (most likely)
+    // missing, so both successful and failed probes are measured.
+    let mut rng = benchlib::rng();
+    let keys = index
+        .entries
+        .keys()
+        .copied()
+        .take(1000)
+        .chain((0..1000).map(|_| rng.gen()))
+        .map(Archived::<u32>::from_native)
+        .collect::<Vec<_>>();
+
+    bencher.bench_local(|| {
+        keys.iter()
+            .filter(|key| {
+                archived.entries.get(divan::black_box(*key)).is_some()
+            })
+            .count()
+    });
+}
@@ -0,0 +1,627 @@
+//! Append-only archives with structural sharing between versions.
+//!
+//! An append-only archive contains a sequence of versions of a root value. Each
+//! version is written after the previous ones, and can reuse any subtrees that
+//! were already written by pointing backwards to them. This makes writing a new
+//! version of a large, incrementally-changing value proportional to the size of
+//! the change instead of the size of the whole value.
+//!
+//! Every version is followed by an [`ArchivedVersion`] trailer that records the
+//! position of its root and the end of the previous version. A version is
+//! identified by its end position: the bytes of the archive up to that position
+//! form a complete archive which can be accessed and validated on its own with
+//! [`access_version`].
+//!
+//! Subtrees are reused through shared pointers. Within a version, an `Rc` or
+//! `Arc` is shared as usual. Across versions, shared pointers are only reused
+//! if they were kept alive with [`AppendSerializer::retain`], since an address
+//! may otherwise be reused by an unrelated value after the original is
+//! dropped. Values that were written by a different serializer (for example,
+//! in a previous run of the program) can be registered for reuse with
+//! [`AppendSerializer::reuse`].
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    alloc::Layout,
+    any::Any,
+    fmt,
+    mem::{size_of, size_of_val},
+    ops::Deref,
+    ptr::NonNull,
+    slice,
+};
+#[cfg(feature = "std")]
+use std::collections::hash_map;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::hash_map;
+
+#[cfg(feature = "bytecheck")]
+use bytecheck::CheckBytes;
+use rancor::{fail, Source, Strategy};
+
+#[cfg(feature = "bytecheck")]
+use crate::validation::validators::DefaultValidator;
+use crate::{
+    access_unchecked,
+    primitive::{ArchivedUsize, FixedUsize},
+    ser::{
+        Allocator, Positional, SeedProvider, Sharing, Writer, WriterExt as _,
+    },
+    util::access_pos_unchecked,
+    ArchiveUnsized, Portable, SerializeUnsized,
+};
+
+/// The trailer written after each version of an append-only archive.
+#[derive(Debug, Portable)]
+#[archive(crate)]
+#[repr(C)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+pub struct ArchivedVersion {
+    root: ArchivedUsize,
+    previous: ArchivedUsize,
+}
+
+impl ArchivedVersion {
+    /// Returns the position of the root of this version.
+    pub fn root(&self) -> usize {
+        self.root.to_native() as usize
+    }
+
+    /// Returns the end position of the previous version, or `None` if this is
+    /// the first version.
+    pub fn previous(&self) -> Option<usize> {
+        match self.previous.to_native() as usize {
+            0 => None,
+            previous => Some(previous),
+        }
+    }
+}
+
+/// A serializer adapter that appends new versions of a root to an archive.
+///
+/// The writer of the wrapped serializer may already contain an append-only
+/// archive, in which case new versions are appended after it. For example, an
+/// [`AlignedVec`](crate::util::AlignedVec) can be seeded with the bytes of an
+/// existing archive, or an [`IoWriter`](crate::ser::writer::IoWriter) can be
+/// created with [`with_pos`](crate::ser::writer::IoWriter::with_pos) for a file
+/// opened in append mode.
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+///
+/// use rkyv::{
+///     append::{access_version, AppendSerializer},
+///     rancor::Error,
+///     ser::{sharing::Share, Serializer},
+///     util::{with_arena, AlignedVec},
+///     Archived,
+/// };
+///
+/// let big = Rc::new("a large unchanged value".to_string());
+///
+/// let (bytes, v1, v2) = with_arena(|arena| {
+///     let mut serializer = AppendSerializer::new(Serializer::new(
+///         AlignedVec::<16>::new(),
+///         arena.acquire(),
+///         Share::new(),
+///     ));
+///     // Keep `big` alive so that later versions can point back to it
+///     serializer.retain(big.clone());
+///     let v1 = serializer.append::<_, Error>(&vec![big.clone()])?;
+///     let v2 = serializer
+///         .append::<_, Error>(&vec![big.clone(), Rc::new("new".into())])?;
+///     Ok::<_, Error>((serializer.into_inner().into_writer(), v1, v2))
+/// })
+/// .unwrap();
+///
+/// let first =
+///     access_version::<Archived<Vec<Rc<String>>>, Error>(&bytes, v1).unwrap();
+/// let second =
+///     access_version::<Archived<Vec<Rc<String>>>, Error>(&bytes, v2).unwrap();
+/// assert_eq!(first.len(), 1);
+/// assert_eq!(second.len(), 2);
+/// // The second version points back to the string written by the first
+/// assert!(core::ptr::eq(first[0].get(), second[0].get()));
+/// ```
+#[derive(Debug)]
+pub struct AppendSerializer<S> {
+    serializer: S,
+    latest: Option<usize>,
+    shared: hash_map::HashMap<usize, usize>,
+    retained: hash_map::HashMap<usize, Retained>,
+}
+
+#[derive(Debug)]
+struct Retained {
+    // Never read, only kept so that the address of the value stays reserved.
+    _handle: Box<dyn Any>,
+    pos: Option<usize>,
+}
+
+impl<S: Positional> AppendSerializer<S> {
+    /// Creates a new append serializer from a serializer.
+    ///
+    /// If the writer of the serializer is not at position 0, then the bytes
+    /// before its current position must be an append-only archive.
+    pub fn new(serializer: S) -> Self {
+        let pos = serializer.pos();
+        Self {
+            serializer,
+            latest: (pos != 0).then_some(pos),
+            shared: hash_map::HashMap::new(),
+            retained: hash_map::HashMap::new(),
+        }
+    }
+}
+
+impl<S> AppendSerializer<S> {
+    /// Returns the end position of the latest version, or `None` if no
+    /// versions have been written.
+    pub fn latest(&self) -> Option<usize> {
+        self.latest
+    }
+
+    /// Returns a reference to the underlying serializer.
+    pub fn serializer(&self) -> &S {
+        &self.serializer
+    }
+
+    /// Returns a mutable reference to the underlying serializer.
+    pub fn serializer_mut(&mut self) -> &mut S {
+        &mut self.serializer
+    }
+
+    /// Consumes the append serializer and returns the underlying serializer.
+    pub fn into_inner(self) -> S {
+        self.serializer
+    }
+
+    /// Appends a new version of the root and returns its end position.
+    ///
+    /// The returned position identifies the version, and can be passed to
+    /// [`access_version`] to access it.
+    ///
+    /// Shared pointers are shared within the version. Only shared pointers
+    /// that were registered with [`retain`](Self::retain) or
+    /// [`reuse`](Self::reuse) are shared with other versions. The pointer
+    /// sharing of the underlying serializer is not used.
+    pub fn append<T, E>(&mut self, value: &T) -> Result<usize, E>
+    where
+        T: for<'a> SerializeUnsized<Strategy<VersionSerializer<'a, S>, E>>
+            + ?Sized,
+        S: Writer<E>,
+    {
+        self.shared.clear();
+        let mut adapter = VersionSerializer {
+            serializer: &mut self.serializer,
+            shared: &mut self.shared,
+            retained: &mut self.retained,
+        };
+        let serializer = Strategy::<_, E>::wrap(&mut adapter);
+        let root = value.serialize_unsized(serializer)?;
+        serializer.align_for::<ArchivedVersion>()?;
+
+        let version = ArchivedVersion {
+            root: ArchivedUsize::from_native(root as FixedUsize),
+            previous: ArchivedUsize::from_native(
+                self.latest.unwrap_or(0) as FixedUsize
+            ),
+        };
+        // SAFETY: `ArchivedVersion` is `repr(C)` and consists of two fields of
+        // the same type, so it has no padding bytes and all of its bytes are
+        // initialized.
+        let bytes = unsafe {
+            slice::from_raw_parts(
+                (&version as *const ArchivedVersion).cast::<u8>(),
+                size_of::<ArchivedVersion>(),
+            )
+        };
+        let serializer = Strategy::<S, E>::wrap(&mut self.serializer);
+        serializer.write(bytes)?;
+
+        let end = serializer.pos();
+        self.latest = Some(end);
+        Ok(end)
+    }
+
+    /// Keeps the value behind `handle` alive so that it can be shared between
+    /// versions.
+    ///
+    /// The first version that serializes the value as a shared pointer writes
+    /// it, and all later versions point back to it. The handle is dropped
+    /// along with the append serializer.
+    pub fn retain<P>(&mut self, handle: P)
+    where
+        P: Deref + 'static,
+    {
+        let address = &*handle as *const P::Target as *const () as usize;
+        self.retained.entry(address).or_insert_with(|| Retained {
+            _handle: Box::new(handle),
+            pos: None,
+        });
+    }
+
+    /// Keeps the value behind `handle` alive and registers it as already
+    /// serialized at the location of `archived` in `bytes`.
+    ///
+    /// Later versions that serialize the value as a shared pointer will point
+    /// to the existing archived value instead of serializing it again. `bytes`
+    /// must be the bytes of the archive that is being appended to, and all of
+    /// `archived` must be located in them.
+    ///
+    /// Only the root of `archived` is checked against `bytes`. Any out-of-line
+    /// data it points to, like the contents of an archived string, is not
+    /// checked. Values accessed from `bytes` always satisfy this, but values
+    /// from other archives may point outside of `bytes` and will leave later
+    /// versions pointing to data that is not in the archive.
+    pub fn reuse<P, E>(
+        &mut self,
+        handle: P,
+        archived: &<P::Target as ArchiveUnsized>::Archived,
+        bytes: &[u8],
+    ) -> Result<(), E>
+    where
+        P: Deref + 'static,
+        P::Target: ArchiveUnsized,
+        E: Source,
+    {
+        let address = archived as *const _ as *const u8 as usize;
+        let size = size_of_val(archived);
+        let start = bytes.as_ptr() as usize;
+        let end = start + bytes.len();
+        if address < start || address > end || end - address < size {
+            fail!(NotInArchive {
+                address,
+                range: start..end,
+            });
+        }
+        self.retained.insert(
+            &*handle as *const P::Target as *const () as usize,
+            Retained {
+                _handle: Box::new(handle),
+                pos: Some(address - start),
+            },
+        );
+        Ok(())
+    }
+}
+
+/// The serializer used to write a single version of an append-only archive.
+///
+/// It forwards to the underlying serializer, but only shares pointers within
+/// the version and with values retained by the [`AppendSerializer`].
+#[derive(Debug)]
+pub struct VersionSerializer<'a, S> {
+    serializer: &'a mut S,
+    shared: &'a mut hash_map::HashMap<usize, usize>,
+    retained: &'a mut hash_map::HashMap<usize, Retained>,
+}
+
+impl<S: Positional> Positional for VersionSerializer<'_, S> {
+    fn pos(&self) -> usize {
+        self.serializer.pos()
+    }
+}
+
+impl<S: Writer<E>, E> Writer<E> for VersionSerializer<'_, S> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.serializer.write(bytes)
+    }
+}
+
+unsafe impl<S: Allocator<E>, E> Allocator<E> for VersionSerializer<'_, S> {
+    unsafe fn push_alloc(
+        &mut self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, E> {
+        // SAFETY: The safety requirements for `S::push_alloc()` are the same as
+        // the safety requirements for `push_alloc()`.
+        unsafe { self.serializer.push_alloc(layout) }
+    }
+
+    unsafe fn pop_alloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `S::pop_alloc()` are the same as
+        // the safety requirements for `pop_alloc()`.
+        unsafe { self.serializer.pop_alloc(ptr, layout) }
+    }
+}
+
+impl<S: SeedProvider> SeedProvider for VersionSerializer<'_, S> {
+    fn hasher_key(&self) -> [u64; 2] {
+        self.serializer.hasher_key()
+    }
+}
+
+impl<S, E> Sharing<E> for VersionSerializer<'_, S> {
+    fn get_shared_ptr(&self, address: usize) -> Option<usize> {
+        match self.retained.get(&address) {
+            Some(retained) => retained.pos,
+            None => self.shared.get(&address).copied(),
+        }
+    }
+
+    fn add_shared_ptr(&mut self, address: usize, pos: usize) -> Result<(), E> {
+        match self.retained.get_mut(&address) {
+            Some(retained) => retained.pos = Some(pos),
+            None => {
+                self.shared.insert(address, pos);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct NotInArchive {
+    address: usize,
+    range: core::ops::Range<usize>,
+}
+
+impl fmt::Display for NotInArchive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "archived value at {} is not located in the archive bytes {}..{}",
+            crate::fmt::Pointer(self.address),
+            crate::fmt::Pointer(self.range.start),
+            crate::fmt::Pointer(self.range.end),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotInArchive {}
+
+#[derive(Debug)]
+struct InvalidVersion {
+    end: usize,
+    len: usize,
+}
+
+impl fmt::Display for InvalidVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid version end position {} for archive of length {}",
+            self.end, self.len,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidVersion {}
+
+fn version_bytes<E: Source>(bytes: &[u8], end: usize) -> Result<&[u8], E> {
+    match bytes.get(..end) {
+        Some(prefix) => Ok(prefix),
+        None => fail!(InvalidVersion {
+            end,
+            len: bytes.len(),
+        }),
+    }
+}
+
+/// Accesses the version of an append-only archive that ends at the given
+/// position after checking its validity.
+///
+/// Only the bytes of the archive before `end` are considered, so the version is
+/// validated independently of any later versions.
+#[cfg(feature = "bytecheck")]
+pub fn access_version<T, E>(bytes: &[u8], end: usize) -> Result<&T, E>
+where
+    T: Portable + for<'a> CheckBytes<Strategy<DefaultValidator<'a>, E>>,
+    E: Source,
+{
+    let bytes = version_bytes::<E>(bytes, end)?;
+    let version = crate::access::<ArchivedVersion, E>(bytes)?;
+    crate::validation::util::access_pos::<T, E>(bytes, version.root())
+}
+
+/// Accesses the latest version of an append-only archive after checking its
+/// validity.
+#[cfg(feature = "bytecheck")]
+pub fn access_latest<T, E>(bytes: &[u8]) -> Result<&T, E>
+where
+    T: Portable + for<'a> CheckBytes<Strategy<DefaultValidator<'a>, E>>,
+    E: Source,
+{
+    access_version::<T, E>(bytes, bytes.len())
+}
+
+/// Returns the end positions of all of the versions in an append-only archive,
+/// from oldest to newest.
+#[cfg(feature = "bytecheck")]
+pub fn versions<E: Source>(bytes: &[u8]) -> Result<Vec<usize>, E> {
+    let mut result = Vec::new();
+    let mut end = bytes.len();
+    while end != 0 {
+        result.push(end);
+        let version = crate::access::<ArchivedVersion, E>(version_bytes::<E>(
+            bytes, end,
+        )?)?;
+        match version.previous() {
+            // Versions must strictly precede each other, otherwise a malicious
+            // archive could make us loop forever.
+            Some(previous) if previous < end => end = previous,
+            Some(_) => fail!(InvalidVersion {
+                end,
+                len: bytes.len(),
+            }),
+            None => end = 0,
+        }
+    }
+    result.reverse();
+    Ok(result)
+}
+
+/// Accesses the version of an append-only archive that ends at the given
+/// position without checking its validity.
+///
+/// # Safety
+///
+/// The bytes before `end` must be a version of an append-only archive whose
+/// root is a valid `T`.
+pub unsafe fn access_version_unchecked<T: Portable>(
+    bytes: &[u8],
+    end: usize,
+) -> &T {
+    let bytes = &bytes[..end];
+    // SAFETY: The caller has guaranteed that `bytes` ends with a version
+    // trailer.
+    let version = unsafe { access_unchecked::<ArchivedVersion>(bytes) };
+    // SAFETY: The caller has guaranteed that the root of the version is a
+    // valid `T`.
+    unsafe { access_pos_unchecked::<T>(bytes, version.root()) }
+}
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        rc::Rc,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    #[cfg(feature = "std")]
+    use std::rc::Rc;
+
+    use rancor::{Failure, Panic};
+
+    use super::{access_latest, access_version, versions, AppendSerializer};
+    use crate::{
+        access,
+        ser::{sharing::Share, Serializer},
+        to_bytes,
+        util::{with_arena, AlignedVec},
+        Archived,
+    };
+
+    type ArchivedList = Archived<Vec<Rc<String>>>;
+
+    #[test]
+    fn reuse_across_runs() {
+        let a = Rc::new("a".to_string());
+        let b = Rc::new("b".to_string());
+
+        let (bytes, first) = with_arena(|arena| {
+            let mut serializer = AppendSerializer::new(Serializer::new(
+                AlignedVec::<16>::new(),
+                arena.acquire(),
+                Share::new(),
+            ));
+            let first =
+                serializer.append::<_, Panic>(&vec![a.clone()]).unwrap();
+            (serializer.into_inner().into_writer(), first)
+        });
+
+        // Resume appending with a fresh serializer seeded with the bytes of the
+        // existing archive.
+        let (bytes, second) = with_arena(|arena| {
+            let mut writer = AlignedVec::<16>::new();
+            writer.extend_from_slice(&bytes);
+            let mut serializer = AppendSerializer::new(Serializer::new(
+                writer,
+                arena.acquire(),
+                Share::new(),
+            ));
+            assert_eq!(serializer.latest(), Some(first));
+
+            let archived =
+                access_latest::<ArchivedList, Panic>(&bytes).unwrap();
+            serializer
+                .reuse::<_, Panic>(a.clone(), archived[0].get(), &bytes)
+                .unwrap();
+
+            let second = serializer
+                .append::<_, Panic>(&vec![a.clone(), b.clone()])
+                .unwrap();
+            (serializer.into_inner().into_writer(), second)
+        });
+
+        assert_eq!(versions::<Panic>(&bytes).unwrap(), vec![first, second]);
+
+        let v1 = access_version::<ArchivedList, Panic>(&bytes, first).unwrap();
+        let v2 = access_version::<ArchivedList, Panic>(&bytes, second).unwrap();
+        assert_eq!(v1.len(), 1);
+        assert_eq!(v2.len(), 2);
+        assert_eq!(v2[1].as_str(), "b");
+        assert!(core::ptr::eq(v1[0].get(), v2[0].get()));
+    }
+
+    #[test]
+    fn reuse_out_of_bounds() {
+        let a = Rc::new("a".to_string());
+
+        with_arena(|arena| {
+            let mut serializer = AppendSerializer::new(Serializer::new(
+                AlignedVec::<16>::new(),
+                arena.acquire(),
+                Share::new(),
+            ));
+            let bytes = to_bytes::<Panic>(&vec![a.clone()]).unwrap();
+            let archived = access::<ArchivedList, Panic>(&bytes).unwrap();
+            let string = archived[0].get();
+            let offset = string as *const _ as *const u8 as usize
+                - bytes.as_ptr() as usize;
+
+            // The archived string must be entirely inside of the bytes.
+            assert!(serializer
+                .reuse::<_, Failure>(a.clone(), string, &bytes[..offset + 1])
+                .is_err());
+            assert!(serializer
+                .reuse::<_, Failure>(a.clone(), string, &bytes[offset + 1..])
+                .is_err());
+            assert!(serializer
+                .reuse::<_, Failure>(a.clone(), string, &bytes)
+                .is_ok());
+        });
+    }
+
+    #[test]
+    fn share_only_retained() {
+        let retained = Rc::new("retained".to_string());
+
+        let (bytes, first, second) = with_arena(|arena| {
+            let mut serializer = AppendSerializer::new(Serializer::new(
+                AlignedVec::<16>::new(),
+                arena.acquire(),
+                Share::new(),
+            ));
+            serializer.retain(retained.clone());
+
+            let dropped = Rc::new("dropped".to_string());
+            let first = serializer
+                .append::<_, Panic>(&vec![
+                    retained.clone(),
+                    dropped.clone(),
+                    dropped.clone(),
+                ])
+                .unwrap();
+            drop(dropped);
+
+            // This value may be allocated at the address of the dropped one,
+            // but must not be mistaken for it.
+            let replacement = Rc::new("replacement".to_string());
+            let second = serializer
+                .append::<_, Panic>(&vec![retained.clone(), replacement])
+                .unwrap();
+            (serializer.into_inner().into_writer(), first, second)
+        });
+
+        let v1 = access_version::<ArchivedList, Panic>(&bytes, first).unwrap();
+        let v2 = access_version::<ArchivedList, Panic>(&bytes, second).unwrap();
+        // Shared pointers are still shared within a version
+        assert!(core::ptr::eq(v1[1].get(), v1[2].get()));
+        assert!(core::ptr::eq(v1[0].get(), v2[0].get()));
+        assert_eq!(v2[1].as_str(), "replacement");
+        assert!(!core::ptr::eq(v1[1].get(), v2[1].get()));
+    }
+}
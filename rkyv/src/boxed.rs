@@ -6,7 +6,8 @@ use munge::munge;
 use rancor::Fallible;
 
 use crate::{
-    ArchivePointee, ArchiveUnsized, Place, Portable, RelPtr, SerializeUnsized,
+    ArchivePointee, ArchiveUnsized, Extent, Place, Portable, RelPtr, Relocate,
+    SerializeUnsized,
 };
 
 /// An archived [`Box`].
@@ -72,6 +73,30 @@ impl<T: ArchivePointee + ?Sized> ArchivedBox<T> {
     }
 }
 
+// SAFETY: An archived box owns its value and the out-of-line data of its value,
+// and only its relative pointer needs to be relocated.
+unsafe impl<T> Relocate for ArchivedBox<T>
+where
+    T: ArchivePointee + Relocate + ?Sized,
+    Self: Portable,
+{
+    fn extent(&self) -> Option<Extent> {
+        Extent::merge(Some(Extent::of(self.get())), self.get().extent())
+    }
+
+    fn can_relocate(&self, delta: isize) -> bool {
+        self.ptr.can_relocate(delta)
+    }
+
+    unsafe fn relocate(self: Pin<&mut Self>, delta: isize) {
+        // SAFETY: The caller has upheld the safety requirements of `relocate`
+        // for the box, and so also for its relative pointer.
+        unsafe {
+            self.map_unchecked_mut(|s| &mut s.ptr).relocate(delta);
+        }
+    }
+}
+
 impl<T: ArchivePointee + ?Sized> AsRef<T> for ArchivedBox<T> {
     fn as_ref(&self) -> &T {
         self.get()
@@ -0,0 +1,27 @@
+//! Archived versions of `cmp` types.
+
+use core::cmp::Ordering;
+
+use crate::Portable;
+
+/// An archived [`Reverse`](core::cmp::Reverse).
+///
+/// Like `Reverse`, archived values are ordered in reverse.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Portable)]
+#[archive(crate)]
+#[repr(transparent)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
+pub struct ArchivedReverse<T>(pub T);
+
+impl<T: PartialOrd> PartialOrd for ArchivedReverse<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.0.partial_cmp(&self.0)
+    }
+}
+
+impl<T: Ord> Ord for ArchivedReverse<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
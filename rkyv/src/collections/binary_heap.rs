@@ -0,0 +1,97 @@
+//! An archived binary heap which stores its elements in heap order.
+
+use core::{fmt, slice};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable, Serialize,
+};
+
+/// An archived binary heap.
+///
+/// The elements are stored in the same order as the heap they were serialized
+/// from, so the greatest element is always first and can be read without any
+/// work. The ordering of the archived elements must match the ordering of the
+/// elements they were serialized from.
+#[derive(Portable)]
+#[archive(crate)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedBinaryHeap<T> {
+    inner: ArchivedVec<T>,
+}
+
+impl<T> ArchivedBinaryHeap<T> {
+    /// Returns whether the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns the greatest element in the heap, or `None` if the heap is
+    /// empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.inner.first()
+    }
+
+    /// Returns the elements of the heap as a slice, in heap order.
+    pub fn as_slice(&self) -> &[T] {
+        self.inner.as_slice()
+    }
+
+    /// Gets an iterator over the elements of the heap, in heap order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.inner.iter()
+    }
+
+    /// Resolves an archived binary heap from the given length and resolver.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: BinaryHeapResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedBinaryHeap { inner } = out);
+        ArchivedVec::resolve_from_len(len, resolver.0, inner);
+    }
+
+    /// Serializes the elements of a binary heap, given as a slice in heap
+    /// order.
+    pub fn serialize_from_slice<U, S>(
+        slice: &[U],
+        serializer: &mut S,
+    ) -> Result<BinaryHeapResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        Ok(BinaryHeapResolver(ArchivedVec::serialize_from_slice(
+            slice, serializer,
+        )?))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedBinaryHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArchivedBinaryHeap<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The resolver for archived binary heaps.
+pub struct BinaryHeapResolver(VecResolver);
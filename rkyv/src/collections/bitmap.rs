@@ -0,0 +1,819 @@
+//! An archived compressed bitmap of `u32` values.
+//!
+//! The bitmap is laid out like a [Roaring bitmap]. Values are split into chunks
+//! by their upper 16 bits, and the lower 16 bits of the values in each chunk
+//! are stored in whichever kind of container takes the least space:
+//!
+//! - Array containers store the values in ascending order.
+//! - Bitmap containers store one bit for each of the 65536 possible values.
+//! - Run containers store ascending runs of consecutive values.
+//!
+//! All of the containers' data is stored in a single vector of 16-bit words.
+//! Bitmap containers are 4096 words long, so any chunk with more than 4096
+//! values that does not compress well into runs is stored as a bitmap.
+//!
+//! [Roaring bitmap]: https://roaringbitmap.org
+
+use core::{
+    cmp::Ordering,
+    fmt, iter,
+    iter::FusedIterator,
+    mem::{size_of, size_of_val},
+    slice,
+};
+
+use munge::munge;
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    primitive::{ArchivedU16, ArchivedU32, ArchivedU64},
+    ser::{Allocator, Writer, WriterExt as _},
+    util::SerVec,
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable,
+};
+
+/// The kind of containers which store their values in a sorted array.
+const ARRAY: u16 = 0;
+/// The kind of containers which store their values as a bitmap.
+const BITMAP: u16 = 1;
+/// The kind of containers which store their values as runs.
+const RUN: u16 = 2;
+
+/// The number of words in a bitmap container.
+const BITMAP_WORDS: usize = 1 << 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Portable)]
+#[archive(crate)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(C)]
+struct Container {
+    /// The upper 16 bits of the values in the container.
+    key: ArchivedU16,
+    /// The kind of the container: `ARRAY`, `BITMAP`, or `RUN`.
+    kind: ArchivedU16,
+    /// The number of values in the container.
+    cardinality: ArchivedU32,
+    /// The number of values in all of the containers before this one.
+    rank: ArchivedU32,
+    /// The index of the container's first word.
+    offset: ArchivedU32,
+}
+
+/// An archived compressed bitmap of `u32` values.
+///
+/// See the [module docs](crate::collections::bitmap) for more details.
+#[derive(Portable)]
+#[archive(crate)]
+#[repr(C)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedBitmap {
+    len: ArchivedU64,
+    containers: ArchivedVec<Container>,
+    words: ArchivedVec<ArchivedU16>,
+}
+
+impl ArchivedBitmap {
+    /// Returns whether the bitmap contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.containers.is_empty()
+    }
+
+    /// Returns the number of values in the bitmap.
+    pub fn len(&self) -> u64 {
+        self.len.to_native()
+    }
+
+    /// Returns whether the bitmap contains the given value.
+    pub fn contains(&self, value: u32) -> bool {
+        match self.find((value >> 16) as u16) {
+            Ok(index) => self.view(index).contains(value as u16),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the number of values in the bitmap which are less than or equal
+    /// to the given value.
+    pub fn rank(&self, value: u32) -> u64 {
+        let (index, low) = match self.find((value >> 16) as u16) {
+            Ok(index) => (index, Some(value as u16)),
+            Err(0) => return 0,
+            Err(index) => (index - 1, None),
+        };
+        let container = &self.containers[index];
+        let rank = u64::from(container.rank.to_native());
+        match low {
+            Some(low) => rank + u64::from(self.view(index).rank(low)),
+            None => rank + u64::from(container.cardinality.to_native()),
+        }
+    }
+
+    /// Gets an iterator over the values of the bitmap, in ascending order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            bitmap: self,
+            next: 0,
+            high: 0,
+            values: Values::Array([].iter()),
+            remaining: self.len(),
+        }
+    }
+
+    /// Collects the values which are in both this bitmap and the other into a
+    /// new collection, in ascending order.
+    ///
+    /// Only the containers which appear in both bitmaps are visited, and the
+    /// values of the smaller container are looked up in the larger one.
+    pub fn and<B: FromIterator<u32>>(&self, other: &Self) -> B {
+        self.containers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, container)| {
+                let key = container.key.to_native();
+                let other_index = other.find(key).ok()?;
+                let other_container = &other.containers[other_index];
+                let (small, large) = if container.cardinality.to_native()
+                    <= other_container.cardinality.to_native()
+                {
+                    (self.view(index), other.view(other_index))
+                } else {
+                    (other.view(other_index), self.view(index))
+                };
+
+                let high = u32::from(key) << 16;
+                Some(
+                    small
+                        .values()
+                        .filter(move |&low| large.contains(low))
+                        .map(move |low| high | u32::from(low)),
+                )
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Collects the values which are in either this bitmap or the other into a
+    /// new collection, in ascending order.
+    pub fn or<B: FromIterator<u32>>(&self, other: &Self) -> B {
+        let mut left = self.iter().peekable();
+        let mut right = other.iter().peekable();
+        iter::from_fn(|| match (left.peek().copied(), right.peek().copied()) {
+            (Some(l), Some(r)) => match l.cmp(&r) {
+                Ordering::Less => left.next(),
+                Ordering::Greater => right.next(),
+                Ordering::Equal => {
+                    right.next();
+                    left.next()
+                }
+            },
+            (Some(_), None) => left.next(),
+            (None, _) => right.next(),
+        })
+        .collect()
+    }
+
+    /// Returns the index of the container with the given key, or the index
+    /// where it would be if there is no such container.
+    fn find(&self, key: u16) -> Result<usize, usize> {
+        self.containers
+            .binary_search_by(|container| container.key.to_native().cmp(&key))
+    }
+
+    fn view(&self, index: usize) -> View<'_> {
+        let container = &self.containers[index];
+        let offset = container.offset.to_native() as usize;
+        match container.kind.to_native() {
+            ARRAY => {
+                let len = container.cardinality.to_native() as usize;
+                View::Array(&self.words[offset..offset + len])
+            }
+            BITMAP => View::Bitmap(&self.words[offset..offset + BITMAP_WORDS]),
+            _ => {
+                let len = 2 * self.words[offset].to_native() as usize;
+                View::Run(&self.words[offset + 1..offset + 1 + len])
+            }
+        }
+    }
+
+    /// Resolves an archived bitmap from the given length and resolver.
+    pub fn resolve_from_len(
+        len: u64,
+        resolver: BitmapResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedBitmap { len: out_len, containers, words } = out);
+        out_len.write(ArchivedU64::from_native(len));
+        ArchivedVec::resolve_from_len(
+            resolver.containers_len,
+            resolver.containers,
+            containers,
+        );
+        ArchivedVec::resolve_from_len(
+            resolver.words_len,
+            resolver.words,
+            words,
+        );
+    }
+
+    /// Serializes an iterator of values as a bitmap.
+    ///
+    /// The values must be unique and yielded in ascending order. The iterator
+    /// is cloned and iterated over several times, so it should be cheap to
+    /// clone.
+    pub fn serialize_from_ordered_iter<I, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<BitmapResolver, S::Error>
+    where
+        I: Iterator<Item = u32> + Clone,
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Source,
+    {
+        let mut containers_len = 0;
+        let mut prev = None;
+        for value in iter.clone() {
+            match prev {
+                Some(prev) if prev >= value => fail!(UnorderedValues),
+                Some(prev) if prev >> 16 == value >> 16 => (),
+                _ => containers_len += 1,
+            }
+            prev = Some(value);
+        }
+
+        SerVec::with_capacity(
+            serializer,
+            containers_len,
+            |containers, serializer| {
+                plan(iter.clone(), containers);
+                let words_len = containers
+                    .last()
+                    .map_or(0, |last| last.offset as usize + last.words());
+
+                // The containers and words are written in the same order as
+                // the fields of the archived bitmap.
+                let containers_resolver =
+                    write_containers(containers, serializer)?;
+                let words = SerVec::with_capacity(
+                    serializer,
+                    BITMAP_WORDS,
+                    |buffer, serializer| {
+                        write_data(iter, containers, buffer, serializer)
+                    },
+                )??;
+
+                Ok(BitmapResolver {
+                    containers_len: containers.len(),
+                    words_len,
+                    containers: containers_resolver,
+                    words,
+                })
+            },
+        )?
+    }
+}
+
+/// A container of the bitmap which is being built.
+struct BuildContainer {
+    key: u16,
+    kind: u16,
+    cardinality: u32,
+    runs: u32,
+    rank: u32,
+    offset: u32,
+}
+
+impl BuildContainer {
+    /// Returns the number of words in the container's data.
+    fn words(&self) -> usize {
+        match self.kind {
+            ARRAY => self.cardinality as usize,
+            BITMAP => BITMAP_WORDS,
+            _ => 1 + 2 * self.runs as usize,
+        }
+    }
+}
+
+/// Plans the containers for the given values, which must be ordered.
+fn plan<I>(iter: I, containers: &mut SerVec<BuildContainer>)
+where
+    I: Iterator<Item = u32>,
+{
+    let mut prev = 0;
+    for value in iter {
+        let key = (value >> 16) as u16;
+        match containers.last_mut() {
+            Some(container) if container.key == key => {
+                container.cardinality += 1;
+                if value != prev + 1 {
+                    container.runs += 1;
+                }
+            }
+            _ => containers.push(BuildContainer {
+                key,
+                kind: ARRAY,
+                cardinality: 1,
+                runs: 1,
+                rank: 0,
+                offset: 0,
+            }),
+        }
+        prev = value;
+    }
+
+    // The rank of each container fits in a `u32`, but the total number of
+    // values may not.
+    let mut rank = 0u64;
+    let mut offset = 0;
+    for container in containers.iter_mut() {
+        let array = container.cardinality as usize;
+        let run = 1 + 2 * container.runs as usize;
+        container.kind = if run < array.min(BITMAP_WORDS) {
+            RUN
+        } else if array <= BITMAP_WORDS {
+            ARRAY
+        } else {
+            BITMAP
+        };
+        container.rank = rank as u32;
+        container.offset = offset;
+        rank += u64::from(container.cardinality);
+        offset += container.words() as u32;
+    }
+}
+
+fn write_containers<S>(
+    containers: &[BuildContainer],
+    serializer: &mut S,
+) -> Result<VecResolver, S::Error>
+where
+    S: Fallible + Writer + ?Sized,
+{
+    let pos = serializer.align_for::<Container>()?;
+    for container in containers {
+        let archived = Container {
+            key: ArchivedU16::from_native(container.key),
+            kind: ArchivedU16::from_native(container.kind),
+            cardinality: ArchivedU32::from_native(container.cardinality),
+            rank: ArchivedU32::from_native(container.rank),
+            offset: ArchivedU32::from_native(container.offset),
+        };
+
+        // SAFETY: `Container` is `repr(C)` and its fields are laid out without
+        // any padding bytes between them.
+        let bytes = unsafe {
+            slice::from_raw_parts(
+                (&archived as *const Container).cast::<u8>(),
+                size_of::<Container>(),
+            )
+        };
+        serializer.write(bytes)?;
+    }
+
+    Ok(VecResolver::from_pos(pos))
+}
+
+/// Writes the data of each container, using `buffer` to build it.
+///
+/// Every container's data fits in a buffer of `BITMAP_WORDS` words.
+fn write_data<I, S>(
+    mut iter: I,
+    containers: &[BuildContainer],
+    buffer: &mut SerVec<ArchivedU16>,
+    serializer: &mut S,
+) -> Result<VecResolver, S::Error>
+where
+    I: Iterator<Item = u32>,
+    S: Fallible + Writer + ?Sized,
+{
+    let pos = serializer.align_for::<ArchivedU16>()?;
+    for container in containers {
+        let values = iter
+            .by_ref()
+            .take(container.cardinality as usize)
+            .map(|value| value as u16);
+
+        buffer.clear();
+        match container.kind {
+            ARRAY => {
+                for low in values {
+                    buffer.push(ArchivedU16::from_native(low));
+                }
+            }
+            BITMAP => {
+                for _ in 0..BITMAP_WORDS {
+                    buffer.push(ArchivedU16::from_native(0));
+                }
+                for low in values {
+                    let word = &mut buffer[usize::from(low >> 4)];
+                    *word = ArchivedU16::from_native(
+                        word.to_native() | (1 << (low & 15)),
+                    );
+                }
+            }
+            _ => {
+                buffer.push(ArchivedU16::from_native(container.runs as u16));
+                let mut run: Option<(u16, u16)> = None;
+                for low in values {
+                    run = match run {
+                        Some((start, len))
+                            if u32::from(start) + u32::from(len) + 1
+                                == u32::from(low) =>
+                        {
+                            Some((start, len + 1))
+                        }
+                        Some((start, len)) => {
+                            buffer.push(ArchivedU16::from_native(start));
+                            buffer.push(ArchivedU16::from_native(len));
+                            Some((low, 0))
+                        }
+                        None => Some((low, 0)),
+                    };
+                }
+                if let Some((start, len)) = run {
+                    buffer.push(ArchivedU16::from_native(start));
+                    buffer.push(ArchivedU16::from_native(len));
+                }
+            }
+        }
+        write_words(buffer, serializer)?;
+    }
+
+    Ok(VecResolver::from_pos(pos))
+}
+
+fn write_words<S>(
+    words: &[ArchivedU16],
+    serializer: &mut S,
+) -> Result<(), S::Error>
+where
+    S: Fallible + Writer + ?Sized,
+{
+    // SAFETY: `ArchivedU16` has no padding bytes, so all of the bytes of
+    // `words` are initialized.
+    let bytes = unsafe {
+        slice::from_raw_parts(words.as_ptr().cast::<u8>(), size_of_val(words))
+    };
+    serializer.write(bytes)
+}
+
+#[derive(Debug)]
+struct UnorderedValues;
+
+impl fmt::Display for UnorderedValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bitmap values must be unique and yielded in ascending order",
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnorderedValues {}
+
+impl fmt::Debug for ArchivedBitmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for ArchivedBitmap {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for ArchivedBitmap {}
+
+impl<'a> IntoIterator for &'a ArchivedBitmap {
+    type Item = u32;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The resolver for archived bitmaps.
+pub struct BitmapResolver {
+    containers_len: usize,
+    words_len: usize,
+    containers: VecResolver,
+    words: VecResolver,
+}
+
+/// The data of a single container.
+#[derive(Clone, Copy)]
+enum View<'a> {
+    Array(&'a [ArchivedU16]),
+    Bitmap(&'a [ArchivedU16]),
+    /// The start and length minus one of each run, without the run count.
+    Run(&'a [ArchivedU16]),
+}
+
+impl<'a> View<'a> {
+    fn contains(self, low: u16) -> bool {
+        match self {
+            View::Array(values) => values
+                .binary_search_by(|value| value.to_native().cmp(&low))
+                .is_ok(),
+            View::Bitmap(words) => {
+                words[usize::from(low >> 4)].to_native() & (1 << (low & 15))
+                    != 0
+            }
+            View::Run(runs) => match runs_before(runs, low) {
+                0 => false,
+                index => {
+                    let start = runs[2 * index - 2].to_native();
+                    let len = runs[2 * index - 1].to_native();
+                    low - start <= len
+                }
+            },
+        }
+    }
+
+    /// Returns the number of values in the container which are less than or
+    /// equal to `low`.
+    fn rank(self, low: u16) -> u32 {
+        match self {
+            View::Array(values) => {
+                values.partition_point(|value| value.to_native() <= low) as u32
+            }
+            View::Bitmap(words) => {
+                let index = usize::from(low >> 4);
+                let before = words[..index]
+                    .iter()
+                    .map(|word| word.to_native().count_ones())
+                    .sum::<u32>();
+                let mask = u16::MAX >> (15 - (low & 15));
+                before + (words[index].to_native() & mask).count_ones()
+            }
+            View::Run(runs) => match runs_before(runs, low) {
+                0 => 0,
+                index => {
+                    let before = runs[..2 * index - 2]
+                        .chunks_exact(2)
+                        .map(|run| u32::from(run[1].to_native()) + 1)
+                        .sum::<u32>();
+                    let start = runs[2 * index - 2].to_native();
+                    let len = runs[2 * index - 1].to_native();
+                    before + u32::from((low - start).min(len)) + 1
+                }
+            },
+        }
+    }
+
+    fn values(self) -> Values<'a> {
+        match self {
+            View::Array(values) => Values::Array(values.iter()),
+            View::Bitmap(words) => Values::Bitmap {
+                words,
+                next: 0,
+                base: 0,
+                word: 0,
+            },
+            View::Run(runs) => Values::Run {
+                runs: runs.chunks_exact(2),
+                next: 0,
+                end: 0,
+            },
+        }
+    }
+}
+
+/// Returns the number of runs which start at or before `low`.
+fn runs_before(runs: &[ArchivedU16], low: u16) -> usize {
+    let mut min = 0;
+    let mut max = runs.len() / 2;
+    while min < max {
+        let mid = min + (max - min) / 2;
+        if runs[2 * mid].to_native() <= low {
+            min = mid + 1;
+        } else {
+            max = mid;
+        }
+    }
+    min
+}
+
+/// An iterator over the lower 16 bits of the values in a container.
+enum Values<'a> {
+    Array(slice::Iter<'a, ArchivedU16>),
+    Bitmap {
+        words: &'a [ArchivedU16],
+        next: usize,
+        base: u16,
+        word: u16,
+    },
+    Run {
+        runs: slice::ChunksExact<'a, ArchivedU16>,
+        next: u32,
+        end: u32,
+    },
+}
+
+impl Iterator for Values<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Values::Array(values) => {
+                values.next().map(|value| value.to_native())
+            }
+            Values::Bitmap {
+                words,
+                next,
+                base,
+                word,
+            } => loop {
+                if *word != 0 {
+                    let bit = word.trailing_zeros() as u16;
+                    *word &= *word - 1;
+                    return Some(*base + bit);
+                }
+                *word = words.get(*next)?.to_native();
+                *base = (*next as u16) << 4;
+                *next += 1;
+            },
+            Values::Run { runs, next, end } => {
+                if *next == *end {
+                    let run = runs.next()?;
+                    *next = u32::from(run[0].to_native());
+                    *end = *next + u32::from(run[1].to_native()) + 1;
+                }
+                let value = *next as u16;
+                *next += 1;
+                Some(value)
+            }
+        }
+    }
+}
+
+/// An iterator over the values of an archived bitmap, in ascending order.
+pub struct Iter<'a> {
+    bitmap: &'a ArchivedBitmap,
+    /// The index of the next container to visit.
+    next: usize,
+    /// The upper 16 bits of the values in the current container.
+    high: u32,
+    values: Values<'a>,
+    remaining: u64,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(low) = self.values.next() {
+                self.remaining -= 1;
+                return Some(self.high | u32::from(low));
+            }
+            let container = self.bitmap.containers.get(self.next)?;
+            self.high = u32::from(container.key.to_native()) << 16;
+            self.values = self.bitmap.view(self.next).values();
+            self.next += 1;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for Iter<'_> {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::fmt;
+
+    use bytecheck::Verify;
+    use rancor::{fail, Fallible, Source};
+
+    use super::{ArchivedBitmap, Container, ARRAY, BITMAP, BITMAP_WORDS, RUN};
+    use crate::primitive::ArchivedU16;
+
+    #[derive(Debug)]
+    struct InvalidContainer {
+        index: usize,
+    }
+
+    impl fmt::Display for InvalidContainer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "bitmap container at index {} is out of order, out of bounds, \
+                 or does not match its cardinality",
+                self.index,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for InvalidContainer {}
+
+    #[derive(Debug)]
+    struct LengthMismatch {
+        expected: u64,
+        actual: u64,
+    }
+
+    impl fmt::Display for LengthMismatch {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "bitmap has a length of {} but its containers have {} values",
+                self.expected, self.actual,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for LengthMismatch {}
+
+    /// Returns the number of values in the container's data, or `None` if
+    /// its data is out of bounds or out of order.
+    fn count(container: &Container, words: &[ArchivedU16]) -> Option<u64> {
+        let offset = container.offset.to_native() as usize;
+        let cardinality = container.cardinality.to_native() as usize;
+        match container.kind.to_native() {
+            ARRAY => {
+                let values =
+                    words.get(offset..offset.checked_add(cardinality)?)?;
+                values
+                    .windows(2)
+                    .all(|pair| pair[0].to_native() < pair[1].to_native())
+                    .then_some(values.len() as u64)
+            }
+            BITMAP => {
+                let bits =
+                    words.get(offset..offset.checked_add(BITMAP_WORDS)?)?;
+                Some(
+                    bits.iter()
+                        .map(|word| u64::from(word.to_native().count_ones()))
+                        .sum(),
+                )
+            }
+            RUN => {
+                let start = offset.checked_add(1)?;
+                let len = 2 * words.get(offset)?.to_native() as usize;
+                let runs = words.get(start..start.checked_add(len)?)?;
+                let mut next = 0;
+                let mut count = 0;
+                for run in runs.chunks_exact(2) {
+                    let start = u32::from(run[0].to_native());
+                    let end = start + u32::from(run[1].to_native()) + 1;
+                    if start < next || end > 1 << 16 {
+                        return None;
+                    }
+                    next = end;
+                    count += u64::from(end - start);
+                }
+                Some(count)
+            }
+            _ => None,
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedBitmap
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let mut prev_key = None;
+            let mut rank = 0;
+            for (index, container) in self.containers.iter().enumerate() {
+                let key = container.key.to_native();
+                let cardinality = container.cardinality.to_native();
+                let valid = prev_key.is_none_or(|prev| prev < key)
+                    && u64::from(container.rank.to_native()) == rank
+                    && cardinality != 0
+                    && count(container, &self.words)
+                        == Some(u64::from(cardinality));
+                if !valid {
+                    fail!(InvalidContainer { index });
+                }
+                prev_key = Some(key);
+                rank += u64::from(cardinality);
+            }
+
+            let len = self.len.to_native();
+            if len != rank {
+                fail!(LengthMismatch {
+                    expected: len,
+                    actual: rank,
+                });
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -1,20 +1,25 @@
 //! [`Archive`](crate::Archive) implementation for B-tree maps.
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 use core::{
     borrow::Borrow,
-    cmp::Ordering,
     fmt,
     marker::PhantomData,
     mem::{size_of, MaybeUninit},
-    ops::ControlFlow,
+    ops::{Bound, ControlFlow},
+    pin::Pin,
+    ptr::{addr_of, addr_of_mut},
     slice,
 };
+#[cfg(feature = "alloc")]
+use core::{iter::FusedIterator, ops::RangeBounds};
 
 use munge::munge;
 use rancor::{fail, Fallible, Source};
 
 use crate::{
-    collections::util::IteratorLengthMismatch,
+    collections::util::{is_above, is_below, IteratorLengthMismatch},
     place::Initialized,
     primitive::{ArchivedUsize, FixedUsize},
     ser::{Allocator, Writer, WriterExt as _},
@@ -123,6 +128,110 @@ struct Node<K, V, const E: usize> {
     values: [MaybeUninit<V>; E],
 }
 
+// Nodes are accessed through raw pointers so that keys can be read while
+// mutable references to the values of the same node are live.
+impl<K, V, const E: usize> Node<K, V, E> {
+    /// Returns the length of the node.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a valid node.
+    unsafe fn len_of(this: *const Self) -> usize {
+        unsafe { (*addr_of!((*this).len)).to_native() as usize }
+    }
+
+    /// Returns whether the node is an inner node.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a valid node.
+    unsafe fn is_inner(this: *const Self) -> bool {
+        matches!(unsafe { &*addr_of!((*this).kind) }, NodeKind::Inner)
+    }
+
+    /// Returns the key at the given index of the node.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a valid node which lives for `'a`, and `index`
+    /// must be less than its length.
+    unsafe fn key<'a>(this: *const Self, index: usize) -> &'a K {
+        unsafe { &*addr_of!((*this).keys).cast::<K>().add(index) }
+    }
+
+    /// Returns the value at the given index of the node.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a valid node which lives for `'a`, and `index`
+    /// must be less than its length.
+    unsafe fn value<'a>(this: *const Self, index: usize) -> &'a V {
+        unsafe { &*addr_of!((*this).values).cast::<V>().add(index) }
+    }
+
+    /// Returns a mutable reference to the value at the given index of the
+    /// node.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a valid node which lives for `'a` and may be
+    /// mutated, `index` must be less than its length, and the value must not
+    /// be aliased for `'a`.
+    unsafe fn value_mut<'a>(this: *mut Self, index: usize) -> Pin<&'a mut V> {
+        unsafe {
+            Pin::new_unchecked(
+                &mut *addr_of_mut!((*this).values).cast::<V>().add(index),
+            )
+        }
+    }
+
+    /// Returns the number of keys in the node for which `is_after` is false.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a valid node.
+    unsafe fn partition_point(
+        this: *const Self,
+        mut is_after: impl FnMut(&K) -> bool,
+    ) -> usize {
+        let len = unsafe { Self::len_of(this) };
+        (0..len)
+            .find(|&i| is_after(unsafe { Self::key(this, i) }))
+            .unwrap_or(len)
+    }
+
+    /// Returns the child node which comes before the entry at the given index,
+    /// or after the last entry if `index` is equal to the length of the node.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a valid node, and `index` must be less than or
+    /// equal to its length.
+    unsafe fn child(this: *mut Self, index: usize) -> Option<*mut Self> {
+        if !unsafe { Self::is_inner(this) } {
+            return None;
+        }
+
+        let inner = this.cast::<InnerNode<K, V, E>>();
+        let child = if index < unsafe { Self::len_of(this) } {
+            unsafe {
+                addr_of_mut!((*inner).lesser_nodes)
+                    .cast::<RawRelPtr>()
+                    .add(index)
+            }
+        } else {
+            unsafe { addr_of_mut!((*inner).greater_node) }
+        };
+        let child_ptr = unsafe { &*child };
+        if child_ptr.is_invalid() {
+            None
+        } else {
+            let offset = child_ptr.offset();
+            Some(unsafe { child.cast::<u8>().offset(offset).cast::<Self>() })
+        }
+    }
+}
+
 #[derive(Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 #[archive(crate)]
@@ -190,59 +299,89 @@ impl<K, V, const E: usize> ArchivedBTreeMap<K, V, E> {
         Q: Ord + ?Sized,
         K: Borrow<Q> + Ord,
     {
+        let (node, index) = Self::search(self.root_ptr()?, key)?;
+        // SAFETY: `search` returned the index of an entry in a node of this
+        // tree.
+        unsafe { Some((Node::key(node, index), Node::value(node, index))) }
+    }
+
+    /// Gets the key-value pair associated with the given key, or `None` if the
+    /// key is not present in the B-tree map. The value is returned as a pinned
+    /// mutable reference.
+    pub fn get_key_value_mut<Q>(
+        self: Pin<&mut Self>,
+        key: &Q,
+    ) -> Option<(&K, Pin<&mut V>)>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        let (node, index) = Self::search(self.root_mut_ptr()?, key)?;
+        // SAFETY: `search` returned the index of an entry in a node of this
+        // tree, which we have mutable access to.
+        unsafe { Some((Node::key(node, index), Node::value_mut(node, index))) }
+    }
+
+    /// Returns the value associated with the given key as a pinned mutable
+    /// reference, or `None` if the key is not present in the B-tree map.
+    pub fn get_mut<Q>(self: Pin<&mut Self>, key: &Q) -> Option<Pin<&mut V>>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        self.get_key_value_mut(key).map(|(_, value)| value)
+    }
+
+    /// Returns a pointer to the root node, or `None` if the map is empty.
+    fn root_ptr(&self) -> Option<*mut Node<K, V, E>> {
         if self.is_empty() {
-            return None;
+            None
+        } else {
+            // SAFETY: The root pointer of a non-empty map is valid. Pointers
+            // returned from this are never used to mutate the tree.
+            Some(
+                unsafe { self.root.as_ptr().cast::<Node<K, V, E>>() }
+                    .cast_mut(),
+            )
         }
+    }
 
-        let mut current = unsafe { self.root.as_ptr().cast::<Node<K, V, E>>() };
-        'outer: loop {
-            let node = unsafe { &*current };
-            for i in 0..node.len.to_native() as usize {
-                let k = unsafe { node.keys[i].assume_init_ref() };
-                match key.cmp(k.borrow()) {
-                    Ordering::Equal => {
-                        let v = unsafe { node.values[i].assume_init_ref() };
-                        return Some((k, v));
-                    }
-                    Ordering::Less => match node.kind {
-                        NodeKind::Inner => {
-                            let inner_node = unsafe {
-                                &*current.cast::<InnerNode<K, V, E>>()
-                            };
-                            let lesser_node = unsafe {
-                                inner_node.lesser_nodes[i].assume_init_ref()
-                            };
-                            if !lesser_node.is_invalid() {
-                                current = unsafe {
-                                    lesser_node.as_ptr().cast::<Node<K, V, E>>()
-                                };
-                                continue 'outer;
-                            } else {
-                                return None;
-                            }
-                        }
-                        NodeKind::Leaf => return None,
-                    },
-                    Ordering::Greater => (),
-                }
+    /// Returns a mutable pointer to the root node, or `None` if the map is
+    /// empty.
+    fn root_mut_ptr(self: Pin<&mut Self>) -> Option<*mut Node<K, V, E>> {
+        if self.is_empty() {
+            None
+        } else {
+            // SAFETY: The root pointer of a non-empty map is valid, and
+            // `root` is never moved out of the pinned map.
+            unsafe {
+                let root = self.map_unchecked_mut(|s| &mut s.root);
+                Some(root.as_mut_ptr().cast::<Node<K, V, E>>())
             }
-            match node.kind {
-                NodeKind::Inner => {
-                    let inner_node =
-                        unsafe { &*current.cast::<InnerNode<K, V, E>>() };
-                    if !inner_node.greater_node.is_invalid() {
-                        current = unsafe {
-                            inner_node
-                                .greater_node
-                                .as_ptr()
-                                .cast::<Node<K, V, E>>()
-                        };
-                    } else {
-                        return None;
-                    }
-                }
-                NodeKind::Leaf => return None,
+        }
+    }
+
+    /// Finds the node and index of the entry with the given key.
+    fn search<Q>(
+        mut current: *mut Node<K, V, E>,
+        key: &Q,
+    ) -> Option<(*mut Node<K, V, E>, usize)>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        loop {
+            // SAFETY: `current` is the root node or a child of a node in the
+            // tree, and so points to a valid node.
+            let index = unsafe {
+                Node::partition_point(current, |k| k.borrow() >= key)
+            };
+            if index < unsafe { Node::len_of(current) }
+                && unsafe { Node::key(current, index) }.borrow() == key
+            {
+                return Some((current, index));
             }
+            current = unsafe { Node::child(current, index) }?;
         }
     }
 
@@ -580,7 +719,154 @@ impl<K, V, const E: usize> ArchivedBTreeMap<K, V, E> {
         ControlFlow::Continue(())
     }
 
-    // TODO: add entries iterator if alloc feature is enabled
+    /// Returns the first key-value pair in the B-tree map, or `None` if the
+    /// map is empty.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.partition_point(|_| true).1
+    }
+
+    /// Returns the last key-value pair in the B-tree map, or `None` if the map
+    /// is empty.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.partition_point(|_| false).0
+    }
+
+    /// Returns the first key-value pair in the B-tree map with a key above the
+    /// given bound, or `None` if there is no such pair.
+    ///
+    /// `Bound::Included(key)` finds the first pair with a key greater than or
+    /// equal to `key`, `Bound::Excluded(key)` finds the first pair with a key
+    /// greater than `key`, and `Bound::Unbounded` finds the first pair in the
+    /// map.
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Option<(&K, &V)>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        self.partition_point(|k| !is_below(k.borrow(), bound)).1
+    }
+
+    /// Returns the last key-value pair in the B-tree map with a key below the
+    /// given bound, or `None` if there is no such pair.
+    ///
+    /// `Bound::Included(key)` finds the last pair with a key less than or equal
+    /// to `key`, `Bound::Excluded(key)` finds the last pair with a key less
+    /// than `key`, and `Bound::Unbounded` finds the last pair in the map.
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Option<(&K, &V)>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        self.partition_point(|k| is_above(k.borrow(), bound)).0
+    }
+
+    /// Finds the pairs on either side of the point where `is_after` becomes
+    /// true for the keys of the B-tree map.
+    ///
+    /// `is_after` must be false for some prefix of the keys in order and true
+    /// for the rest. Returns the last pair for which it is false and the first
+    /// pair for which it is true.
+    #[allow(clippy::type_complexity)]
+    fn partition_point(
+        &self,
+        mut is_after: impl FnMut(&K) -> bool,
+    ) -> (Option<(&K, &V)>, Option<(&K, &V)>) {
+        let mut before = None;
+        let mut after = None;
+        let Some(mut current) = self.root_ptr() else {
+            return (before, after);
+        };
+
+        loop {
+            // SAFETY: `current` is the root node or a child of a node in the
+            // tree, and so points to a valid node.
+            let i = unsafe { Node::partition_point(current, &mut is_after) };
+            // Entries found deeper in the tree are always closer to the
+            // partition point than the ones found above them.
+            unsafe {
+                if i > 0 {
+                    before = Some((
+                        Node::key(current, i - 1),
+                        Node::value(current, i - 1),
+                    ));
+                }
+                if i < Node::len_of(current) {
+                    after =
+                        Some((Node::key(current, i), Node::value(current, i)));
+                }
+            }
+            match unsafe { Node::child(current, i) } {
+                Some(child) => current = child,
+                None => return (before, after),
+            }
+        }
+    }
+
+    /// Gets an iterator over the entries of the B-tree map, sorted by key.
+    #[cfg(feature = "alloc")]
+    pub fn iter(&self) -> Iter<'_, K, V, E> {
+        Iter {
+            inner: RawIter::new(self.root_ptr(), |_| true, |_| false),
+            remaining: self.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Gets a mutable iterator over the entries of the B-tree map, sorted by
+    /// key.
+    #[cfg(feature = "alloc")]
+    pub fn iter_mut(self: Pin<&mut Self>) -> IterMut<'_, K, V, E> {
+        let remaining = self.len();
+        IterMut {
+            inner: RawIter::new(self.root_mut_ptr(), |_| true, |_| false),
+            remaining,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Gets an iterator over the sorted keys of the B-tree map.
+    #[cfg(feature = "alloc")]
+    pub fn keys(&self) -> Keys<'_, K, V, E> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Gets an iterator over the values of the B-tree map, sorted by key.
+    #[cfg(feature = "alloc")]
+    pub fn values(&self) -> Values<'_, K, V, E> {
+        Values { inner: self.iter() }
+    }
+
+    /// Gets a mutable iterator over the values of the B-tree map, sorted by
+    /// key.
+    #[cfg(feature = "alloc")]
+    pub fn values_mut(self: Pin<&mut Self>) -> ValuesMut<'_, K, V, E> {
+        ValuesMut {
+            inner: self.iter_mut(),
+        }
+    }
+
+    /// Gets an iterator over the entries of the B-tree map with keys in the
+    /// given range, sorted by key.
+    ///
+    /// If the start of the range is after its end, the iterator is empty.
+    #[cfg(feature = "alloc")]
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V, E>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+        R: RangeBounds<Q>,
+    {
+        let start = range.start_bound();
+        let end = range.end_bound();
+        Range {
+            inner: RawIter::new(
+                self.root_ptr(),
+                |k| !is_below(k.borrow(), start),
+                |k| is_above(k.borrow(), end),
+            ),
+            _phantom: PhantomData,
+        }
+    }
 }
 
 impl<K, V, const E: usize> fmt::Debug for ArchivedBTreeMap<K, V, E>
@@ -598,6 +884,377 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'a, K, V, const E: usize> IntoIterator for &'a ArchivedBTreeMap<K, V, E> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A path from the root of a B-tree to one of its entries.
+///
+/// Each element is a node and an index into that node. The last element is the
+/// node containing the entry, and every other element is the parent of the
+/// next along with the index of the child that the path descends into.
+#[cfg(feature = "alloc")]
+type Cursor<K, V, const E: usize> = Vec<(*mut Node<K, V, E>, usize)>;
+
+/// An iterator over the positions of the entries in a B-tree.
+#[cfg(feature = "alloc")]
+struct RawIter<K, V, const E: usize> {
+    // The front cursor points to the next entry to yield from the front. Its
+    // index is the index of that entry.
+    front: Cursor<K, V, E>,
+    // The back cursor points to the next entry to yield from the back. Its
+    // index is one greater than the index of that entry.
+    back: Cursor<K, V, E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> RawIter<K, V, E> {
+    /// Creates an iterator over the entries of the tree with the given root
+    /// from the first key for which `front_is_after` is true to the last key
+    /// for which `back_is_after` is false.
+    fn new(
+        root: Option<*mut Node<K, V, E>>,
+        mut front_is_after: impl FnMut(&K) -> bool,
+        mut back_is_after: impl FnMut(&K) -> bool,
+    ) -> Self {
+        let mut result = Self {
+            front: Vec::new(),
+            back: Vec::new(),
+        };
+        let Some(root) = root else {
+            return result;
+        };
+
+        Self::seek(&mut result.front, root, &mut front_is_after);
+        Self::normalize_front(&mut result.front);
+        Self::seek(&mut result.back, root, &mut back_is_after);
+        Self::normalize_back(&mut result.back);
+
+        // The back cursor points to the last key before the end of the range.
+        // If the front cursor is after the end of the range, the range is
+        // empty.
+        let is_empty = match result.front.last() {
+            Some(&(node, index)) => {
+                back_is_after(unsafe { Node::key(node, index) })
+            }
+            None => true,
+        };
+        if is_empty || result.back.is_empty() {
+            result.clear();
+        }
+
+        result
+    }
+
+    /// Pushes the path from `node` to the point where `is_after` becomes true
+    /// onto the cursor.
+    fn seek(
+        cursor: &mut Cursor<K, V, E>,
+        mut node: *mut Node<K, V, E>,
+        is_after: &mut impl FnMut(&K) -> bool,
+    ) {
+        loop {
+            // SAFETY: `node` is the root node or a child of a node in the
+            // tree, and so points to a valid node.
+            let index = unsafe { Node::partition_point(node, &mut *is_after) };
+            cursor.push((node, index));
+            match unsafe { Node::child(node, index) } {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+    }
+
+    /// Pops nodes off of the front cursor until it points to an entry.
+    fn normalize_front(cursor: &mut Cursor<K, V, E>) {
+        while let Some(&(node, index)) = cursor.last() {
+            if index < unsafe { Node::len_of(node) } {
+                break;
+            }
+            cursor.pop();
+        }
+    }
+
+    /// Pops nodes off of the back cursor until it points to an entry.
+    fn normalize_back(cursor: &mut Cursor<K, V, E>) {
+        while let Some(&(_, 0)) = cursor.last() {
+            cursor.pop();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> Iterator for RawIter<K, V, E> {
+    type Item = (*mut Node<K, V, E>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let &(node, index) = self.front.last()?;
+
+        if self.back.last() == Some(&(node, index + 1)) {
+            // The front and back cursors met, so this was the last entry.
+            self.clear();
+        } else {
+            self.front.last_mut().unwrap().1 = index + 1;
+            if let Some(child) = unsafe { Node::child(node, index + 1) } {
+                Self::seek(&mut self.front, child, &mut |_| true);
+            }
+            Self::normalize_front(&mut self.front);
+        }
+
+        Some((node, index))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> DoubleEndedIterator for RawIter<K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let &(node, end) = self.back.last()?;
+        let index = end - 1;
+
+        if self.front.last() == Some(&(node, index)) {
+            // The front and back cursors met, so this was the last entry.
+            self.clear();
+        } else {
+            self.back.last_mut().unwrap().1 = index;
+            if let Some(child) = unsafe { Node::child(node, index) } {
+                Self::seek(&mut self.back, child, &mut |_| false);
+            }
+            Self::normalize_back(&mut self.back);
+        }
+
+        Some((node, index))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> FusedIterator for RawIter<K, V, E> {}
+
+/// An iterator over the entries of an archived B-tree map, sorted by key.
+#[cfg(feature = "alloc")]
+pub struct Iter<'a, K, V, const E: usize = 5> {
+    inner: RawIter<K, V, E>,
+    remaining: usize,
+    _phantom: PhantomData<&'a ArchivedBTreeMap<K, V, E>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, const E: usize> Iterator for Iter<'a, K, V, E> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, index) = self.inner.next()?;
+        self.remaining -= 1;
+        // SAFETY: The raw iterator only yields entries of nodes in the tree,
+        // which is borrowed for `'a`.
+        unsafe { Some((Node::key(node, index), Node::value(node, index))) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> DoubleEndedIterator for Iter<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (node, index) = self.inner.next_back()?;
+        self.remaining -= 1;
+        // SAFETY: The raw iterator only yields entries of nodes in the tree,
+        // which is borrowed for `'a`.
+        unsafe { Some((Node::key(node, index), Node::value(node, index))) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> ExactSizeIterator for Iter<'_, K, V, E> {}
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> FusedIterator for Iter<'_, K, V, E> {}
+
+/// A mutable iterator over the entries of an archived B-tree map, sorted by
+/// key.
+#[cfg(feature = "alloc")]
+pub struct IterMut<'a, K, V, const E: usize = 5> {
+    inner: RawIter<K, V, E>,
+    remaining: usize,
+    _phantom: PhantomData<&'a mut ArchivedBTreeMap<K, V, E>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, const E: usize> Iterator for IterMut<'a, K, V, E> {
+    type Item = (&'a K, Pin<&'a mut V>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, index) = self.inner.next()?;
+        self.remaining -= 1;
+        // SAFETY: The raw iterator only yields entries of nodes in the tree,
+        // which is mutably borrowed for `'a`. Each entry is yielded at most
+        // once, so the value is not aliased.
+        unsafe { Some((Node::key(node, index), Node::value_mut(node, index))) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> DoubleEndedIterator for IterMut<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (node, index) = self.inner.next_back()?;
+        self.remaining -= 1;
+        // SAFETY: The raw iterator only yields entries of nodes in the tree,
+        // which is mutably borrowed for `'a`. Each entry is yielded at most
+        // once, so the value is not aliased.
+        unsafe { Some((Node::key(node, index), Node::value_mut(node, index))) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> ExactSizeIterator for IterMut<'_, K, V, E> {}
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> FusedIterator for IterMut<'_, K, V, E> {}
+
+/// An iterator over the keys of an archived B-tree map, in sorted order.
+#[cfg(feature = "alloc")]
+#[repr(transparent)]
+pub struct Keys<'a, K, V, const E: usize = 5> {
+    inner: Iter<'a, K, V, E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, const E: usize> Iterator for Keys<'a, K, V, E> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> DoubleEndedIterator for Keys<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> ExactSizeIterator for Keys<'_, K, V, E> {}
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> FusedIterator for Keys<'_, K, V, E> {}
+
+/// An iterator over the values of an archived B-tree map, sorted by key.
+#[cfg(feature = "alloc")]
+#[repr(transparent)]
+pub struct Values<'a, K, V, const E: usize = 5> {
+    inner: Iter<'a, K, V, E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, const E: usize> Iterator for Values<'a, K, V, E> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> DoubleEndedIterator for Values<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> ExactSizeIterator for Values<'_, K, V, E> {}
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> FusedIterator for Values<'_, K, V, E> {}
+
+/// A mutable iterator over the values of an archived B-tree map, sorted by
+/// key.
+#[cfg(feature = "alloc")]
+#[repr(transparent)]
+pub struct ValuesMut<'a, K, V, const E: usize = 5> {
+    inner: IterMut<'a, K, V, E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, const E: usize> Iterator for ValuesMut<'a, K, V, E> {
+    type Item = Pin<&'a mut V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> DoubleEndedIterator for ValuesMut<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> ExactSizeIterator for ValuesMut<'_, K, V, E> {}
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> FusedIterator for ValuesMut<'_, K, V, E> {}
+
+/// An iterator over a range of entries in an archived B-tree map, sorted by
+/// key.
+#[cfg(feature = "alloc")]
+pub struct Range<'a, K, V, const E: usize = 5> {
+    inner: RawIter<K, V, E>,
+    _phantom: PhantomData<&'a ArchivedBTreeMap<K, V, E>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, const E: usize> Iterator for Range<'a, K, V, E> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, index) = self.inner.next()?;
+        // SAFETY: The raw iterator only yields entries of nodes in the tree,
+        // which is borrowed for `'a`.
+        unsafe { Some((Node::key(node, index), Node::value(node, index))) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> DoubleEndedIterator for Range<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (node, index) = self.inner.next_back()?;
+        // SAFETY: The raw iterator only yields entries of nodes in the tree,
+        // which is borrowed for `'a`.
+        unsafe { Some((Node::key(node, index), Node::value(node, index))) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> FusedIterator for Range<'_, K, V, E> {}
+
 /// The resolver for [`ArchivedBTreeMap`].
 pub struct BTreeMapResolver {
     root_node_pos: usize,
@@ -608,12 +1265,14 @@ mod verify {
     use core::{alloc::Layout, fmt, ptr::addr_of};
 
     use bytecheck::{CheckBytes, Verify};
-    use rancor::{fail, Fallible, Source};
+    use rancor::{fail, Fallible, ResultExt as _, Source};
 
     use super::{ArchivedBTreeMap, InnerNode, Node};
     use crate::{
         collections::btree_map::NodeKind,
-        validation::{ArchiveContext, ArchiveContextExt as _},
+        validation::{
+            path::PathSegment, ArchiveContext, ArchiveContextExt as _,
+        },
         RawRelPtr,
     };
 
@@ -651,6 +1310,7 @@ mod verify {
                 return Ok(());
             }
 
+            context.check_collection_len(len)?;
             check_node_rel_ptr::<C, K, V, E>(&self.root, context)
         }
     }
@@ -799,7 +1459,8 @@ mod verify {
             // SAFETY: `key_ptr` is a subfield of a node, and so is guaranteed
             // to be properly aligned and point to enough bytes for a `K`.
             unsafe {
-                K::check_bytes(key_ptr, context)?;
+                K::check_bytes(key_ptr, context)
+                    .with_trace(|| PathSegment::Field("key"))?;
             }
             // SAFETY: `values` points to the first element of an array of `E`,
             // and the caller has guaranteed that `len` is less than `E`.
@@ -807,7 +1468,8 @@ mod verify {
             // SAFETY: `value_ptr` is a subfield of a node, and so is guaranteed
             // to be properly aligned and point to enough bytes for a `V`.
             unsafe {
-                V::check_bytes(value_ptr, context)?;
+                V::check_bytes(value_ptr, context)
+                    .with_trace(|| PathSegment::Field("value"))?;
             }
         }
 
@@ -1,10 +1,18 @@
 //! [`Archive`](crate::Archive) implementation for B-tree sets.
 
-use core::{borrow::Borrow, fmt, ops::ControlFlow};
+use core::{
+    borrow::Borrow,
+    fmt,
+    ops::{Bound, ControlFlow},
+};
+#[cfg(feature = "alloc")]
+use core::{iter::FusedIterator, ops::RangeBounds};
 
 use munge::munge;
 use rancor::{Fallible, Source};
 
+#[cfg(feature = "alloc")]
+use crate::collections::btree_map;
 use crate::{
     collections::btree_map::{ArchivedBTreeMap, BTreeMapResolver},
     ser::{Allocator, Writer},
@@ -54,6 +62,64 @@ impl<K, const E: usize> ArchivedBTreeSet<K, E> {
         self.0.len()
     }
 
+    /// Returns the first key in the set, or `None` if the set is empty.
+    pub fn first(&self) -> Option<&K> {
+        self.0.first_key_value().map(|(key, _)| key)
+    }
+
+    /// Returns the last key in the set, or `None` if the set is empty.
+    pub fn last(&self) -> Option<&K> {
+        self.0.last_key_value().map(|(key, _)| key)
+    }
+
+    /// Returns the first key in the set above the given bound, or `None` if
+    /// there is no such key.
+    ///
+    /// See [`ArchivedBTreeMap::lower_bound`] for details.
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Option<&K>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        self.0.lower_bound(bound).map(|(key, _)| key)
+    }
+
+    /// Returns the last key in the set below the given bound, or `None` if
+    /// there is no such key.
+    ///
+    /// See [`ArchivedBTreeMap::upper_bound`] for details.
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Option<&K>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        self.0.upper_bound(bound).map(|(key, _)| key)
+    }
+
+    /// Gets an iterator over the keys of the set, in sorted order.
+    #[cfg(feature = "alloc")]
+    pub fn iter(&self) -> Iter<'_, K, E> {
+        Iter {
+            inner: self.0.keys(),
+        }
+    }
+
+    /// Gets an iterator over the keys of the set in the given range, in sorted
+    /// order.
+    ///
+    /// If the start of the range is after its end, the iterator is empty.
+    #[cfg(feature = "alloc")]
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, E>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+        R: RangeBounds<Q>,
+    {
+        Range {
+            inner: self.0.range(range),
+        }
+    }
+
     /// Resolves a B-tree set from its length.
     pub fn resolve_from_len(
         len: usize,
@@ -109,5 +175,74 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'a, K, const E: usize> IntoIterator for &'a ArchivedBTreeSet<K, E> {
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the keys of an archived B-tree set, in sorted order.
+#[cfg(feature = "alloc")]
+#[repr(transparent)]
+pub struct Iter<'a, K, const E: usize = 5> {
+    inner: btree_map::Keys<'a, K, (), E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, const E: usize> Iterator for Iter<'a, K, E> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, const E: usize> DoubleEndedIterator for Iter<'_, K, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, const E: usize> ExactSizeIterator for Iter<'_, K, E> {}
+#[cfg(feature = "alloc")]
+impl<K, const E: usize> FusedIterator for Iter<'_, K, E> {}
+
+/// An iterator over a range of keys in an archived B-tree set, in sorted
+/// order.
+#[cfg(feature = "alloc")]
+#[repr(transparent)]
+pub struct Range<'a, K, const E: usize = 5> {
+    inner: btree_map::Range<'a, K, (), E>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, const E: usize> Iterator for Range<'a, K, E> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, const E: usize> DoubleEndedIterator for Range<'_, K, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, const E: usize> FusedIterator for Range<'_, K, E> {}
+
 /// The resolver for archived B-tree sets.
 pub struct BTreeSetResolver(BTreeMapResolver);
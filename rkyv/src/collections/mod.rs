@@ -1,6 +1,12 @@
 //! Archived versions of standard library containers.
 
+pub mod binary_heap;
+pub mod bitmap;
 pub mod btree_map;
 pub mod btree_set;
+pub mod phf;
+pub mod sorted_vec_map;
+pub mod sorted_vec_set;
 pub mod swiss_table;
+pub mod trie;
 pub mod util;
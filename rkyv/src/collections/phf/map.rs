@@ -0,0 +1,612 @@
+//! Archived perfect hash map implementation.
+
+use core::{
+    borrow::Borrow, cmp::Reverse, fmt, hash::Hash, iter::FusedIterator,
+    marker::PhantomData, ops::Index, slice,
+};
+
+use munge::munge;
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    collections::util::{Entry, EntryAdapter, IteratorLengthMismatch},
+    hash::{hash_value_with_seed, FxHasher64, SeededHasher},
+    primitive::ArchivedU32,
+    ser::{Allocator, Writer},
+    util::SerVec,
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable, Serialize,
+};
+
+/// Marks a bucket which no keys hash to.
+const EMPTY: u32 = u32::MAX;
+/// Marks a displacement which must be mixed into the hash to find the slot.
+/// Displacements without this bit set are the slot index.
+const DISPLACED: u32 = 1 << 31;
+/// The number of displacements to try for each bucket before giving up.
+const MAX_ATTEMPTS: u32 = 1 << 16;
+
+#[inline]
+fn mix(mut x: u64) -> u64 {
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^= x >> 33;
+    x
+}
+
+#[inline]
+fn bucket(hash: u64, len: usize) -> usize {
+    (mix(hash) % len as u64) as usize
+}
+
+#[inline]
+fn displaced_slot(hash: u64, displacement: u32, len: usize) -> usize {
+    let offset = (displacement as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    (mix(hash ^ offset) % len as u64) as usize
+}
+
+/// An archived hash map which is indexed by a minimal perfect hash function.
+///
+/// Keys are hashed with `H` keyed by the seed the map was serialized with. See
+/// the [module docs](crate::collections::phf) for more details.
+#[derive(Portable)]
+#[archive(crate)]
+#[repr(C)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedPhfMap<K, V, H: SeededHasher = FxHasher64> {
+    displacements: ArchivedVec<ArchivedU32>,
+    entries: ArchivedVec<Entry<K, V>>,
+    seed: H::Seed,
+    _phantom: PhantomData<H>,
+}
+
+impl<K, V, H: SeededHasher> ArchivedPhfMap<K, V, H> {
+    /// Returns whether the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the seed that keys are hashed with.
+    pub fn seed(&self) -> &H::Seed {
+        &self.seed
+    }
+
+    /// Returns an iterator over the key-value entries in the map.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys in the map.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the values in the map.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Returns the only entry which the given key could be stored in.
+    fn slot_of<Q: Hash + ?Sized>(&self, key: &Q) -> Option<&Entry<K, V>> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        let hash = hash_value_with_seed::<Q, H>(key, &self.seed);
+        let displacement = self.displacements[bucket(hash, len)].to_native();
+        let index = if displacement == EMPTY {
+            return None;
+        } else if displacement & DISPLACED == 0 {
+            displacement as usize
+        } else {
+            displaced_slot(hash, displacement, len)
+        };
+        Some(&self.entries[index])
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key using the
+    /// given comparison function.
+    pub fn get_key_value_with<Q, C>(&self, key: &Q, cmp: C) -> Option<(&K, &V)>
+    where
+        Q: Hash + Eq + ?Sized,
+        C: Fn(&Q, &K) -> bool,
+    {
+        let entry = self.slot_of(key)?;
+        cmp(key, &entry.key).then_some((&entry.key, &entry.value))
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_key_value_with(key, |q, k| q == k.borrow())
+    }
+
+    /// Returns a reference to the value corresponding to the supplied key using
+    /// the given comparison function.
+    pub fn get_with<Q, C>(&self, key: &Q, cmp: C) -> Option<&V>
+    where
+        Q: Hash + Eq + ?Sized,
+        C: Fn(&Q, &K) -> bool,
+    {
+        Some(self.get_key_value_with(key, cmp)?.1)
+    }
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Some(self.get_key_value(key)?.1)
+    }
+
+    /// Returns whether the map contains the given key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Serializes an iterator of key-value pairs as a perfect hash map.
+    ///
+    /// The hasher is keyed with a fixed key of all zeroes. Use
+    /// [`serialize_from_iter_with_key`](Self::serialize_from_iter_with_key) to
+    /// choose the key instead.
+    pub fn serialize_from_iter<'a, I, KU, VU, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<PhfMapResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = (&'a KU, &'a VU)>,
+        KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+        VU: 'a + Serialize<S, Archived = V>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        Self::serialize_from_iter_with_key(iter, [0; 2], serializer)
+    }
+
+    /// Serializes an iterator of key-value pairs as a perfect hash map with a
+    /// hasher keyed by the given key.
+    ///
+    /// This fails if the keys can't be separated by the hash function, which
+    /// can only happen if some keys have identical hashes.
+    pub fn serialize_from_iter_with_key<'a, I, KU, VU, S>(
+        iter: I,
+        key: [u64; 2],
+        serializer: &mut S,
+    ) -> Result<PhfMapResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = (&'a KU, &'a VU)>,
+        KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+        VU: 'a + Serialize<S, Archived = V>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        #[derive(Debug)]
+        struct TooManyEntries {
+            len: usize,
+        }
+
+        impl fmt::Display for TooManyEntries {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "perfect hash maps may contain at most {} entries, but \
+                     {} were provided",
+                    DISPLACED, self.len,
+                )
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl std::error::Error for TooManyEntries {}
+
+        let len = iter.len();
+        if len > DISPLACED as usize {
+            fail!(TooManyEntries { len });
+        }
+
+        let seed = H::seed_from_key(key);
+
+        SerVec::with_capacity(serializer, len, |items, serializer| {
+            for (key, value) in iter {
+                if items.len() == len {
+                    fail!(IteratorLengthMismatch {
+                        expected: len,
+                        actual: len + 1,
+                    });
+                }
+                let hash = hash_value_with_seed::<KU, H>(key, &seed);
+                items.push((key, value, hash));
+            }
+            if items.len() != len {
+                fail!(IteratorLengthMismatch {
+                    expected: len,
+                    actual: items.len(),
+                });
+            }
+
+            // Scratch space for the displacements, the item in each slot, and
+            // the order to place items in.
+            SerVec::with_capacity(
+                serializer,
+                3 * len,
+                |scratch, serializer| {
+                    for _ in 0..3 * len {
+                        scratch.push(0);
+                    }
+                    let (displacements, rest) = scratch.split_at_mut(len);
+                    let (slots, order) = rest.split_at_mut(len);
+                    build(
+                        items,
+                        displacements,
+                        slots,
+                        order,
+                        |&(_, _, hash)| hash,
+                    )?;
+
+                    let displacements =
+                        ArchivedVec::<ArchivedU32>::serialize_from_iter::<
+                            u32,
+                            _,
+                            _,
+                        >(
+                            displacements.iter(), serializer
+                        )?;
+                    let entries =
+                        ArchivedVec::<Entry<K, V>>::serialize_from_iter(
+                            slots.iter().map(|&i| {
+                                let (key, value, _) = items[i as usize];
+                                EntryAdapter { key, value }
+                            }),
+                            serializer,
+                        )?;
+
+                    Ok(PhfMapResolver {
+                        displacements,
+                        entries,
+                        key,
+                    })
+                },
+            )?
+        })?
+    }
+
+    /// Resolves an archived perfect hash map from a given length and
+    /// parameters.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: PhfMapResolver,
+        out: Place<Self>,
+    ) {
+        munge! {
+            let ArchivedPhfMap { displacements, entries, seed, _phantom: _ } =
+                out;
+        }
+        ArchivedVec::resolve_from_len(
+            len,
+            resolver.displacements,
+            displacements,
+        );
+        ArchivedVec::resolve_from_len(len, resolver.entries, entries);
+        seed.write(H::seed_from_key(resolver.key));
+    }
+}
+
+#[derive(Debug)]
+struct InseparableKeys;
+
+impl fmt::Display for InseparableKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to build a perfect hash function because some keys could \
+             not be separated; they may have identical hashes",
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InseparableKeys {}
+
+/// Builds the displacements for a minimal perfect hash function over `items`,
+/// and writes which item belongs in each slot to `slots`.
+fn build<T, E: Source>(
+    items: &[T],
+    displacements: &mut [u32],
+    slots: &mut [u32],
+    order: &mut [u32],
+    hash: impl Fn(&T) -> u64,
+) -> Result<(), E> {
+    let len = items.len();
+    let bucket_of = |i: u32| bucket(hash(&items[i as usize]), len);
+    let slot_of =
+        |i: u32, d: u32| displaced_slot(hash(&items[i as usize]), d, len);
+
+    // Count the number of items in each bucket, then order the items so that
+    // the largest buckets are placed first while the slots are mostly free.
+    displacements.fill(0);
+    slots.fill(EMPTY);
+    for (i, o) in (0..len as u32).zip(order.iter_mut()) {
+        displacements[bucket_of(i)] += 1;
+        *o = i;
+    }
+    order.sort_unstable_by_key(|&i| {
+        let bucket = bucket_of(i);
+        (Reverse(displacements[bucket]), bucket)
+    });
+    displacements.fill(EMPTY);
+
+    // Find a displacement which sends every item in the bucket to a different
+    // free slot.
+    let mut start = 0;
+    while start < len {
+        let bucket = bucket_of(order[start]);
+        let end = start
+            + order[start..]
+                .iter()
+                .take_while(|&&i| bucket_of(i) == bucket)
+                .count();
+        let group = &order[start..end];
+        if group.len() == 1 {
+            break;
+        }
+
+        let displacement = (0..MAX_ATTEMPTS)
+            .map(|attempt| DISPLACED | attempt)
+            .find(|&d| {
+                group.iter().enumerate().all(|(j, &i)| {
+                    let slot = slot_of(i, d);
+                    slots[slot] == EMPTY
+                        && group[..j].iter().all(|&k| slot_of(k, d) != slot)
+                })
+            });
+        let Some(displacement) = displacement else {
+            fail!(InseparableKeys);
+        };
+
+        for &i in group {
+            slots[slot_of(i, displacement)] = i;
+        }
+        displacements[bucket] = displacement;
+        start = end;
+    }
+
+    // The remaining buckets only have one item each, so they can point
+    // directly at any free slot.
+    let mut free = 0;
+    for &i in order[start..].iter() {
+        while slots[free] != EMPTY {
+            free += 1;
+        }
+        slots[free] = i;
+        displacements[bucket_of(i)] = free as u32;
+    }
+
+    Ok(())
+}
+
+impl<K, V, H> fmt::Debug for ArchivedPhfMap<K, V, H>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+    H: SeededHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, H> Eq for ArchivedPhfMap<K, V, H>
+where
+    K: Hash + Eq,
+    V: Eq,
+    H: SeededHasher,
+{
+}
+
+impl<K, V, H> PartialEq for ArchivedPhfMap<K, V, H>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    H: SeededHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            false
+        } else {
+            self.iter().all(|(key, value)| {
+                other.get(key).is_some_and(|v| *value == *v)
+            })
+        }
+    }
+}
+
+impl<K, Q, V, H> Index<&'_ Q> for ArchivedPhfMap<K, V, H>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+    H: SeededHasher,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).unwrap()
+    }
+}
+
+/// The resolver for [`ArchivedPhfMap`].
+pub struct PhfMapResolver {
+    displacements: VecResolver,
+    entries: VecResolver,
+    key: [u64; 2],
+}
+
+/// An iterator over the key-value pairs of an [`ArchivedPhfMap`].
+pub struct Iter<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| (&entry.key, &entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the keys of an [`ArchivedPhfMap`].
+pub struct Keys<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of an [`ArchivedPhfMap`].
+pub struct Values<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::fmt;
+
+    use bytecheck::Verify;
+    use rancor::{fail, Fallible, Source};
+
+    use super::{ArchivedPhfMap, DISPLACED, EMPTY};
+    use crate::hash::SeededHasher;
+
+    #[derive(Debug)]
+    struct MismatchedLengths {
+        displacements: usize,
+        entries: usize,
+    }
+
+    impl fmt::Display for MismatchedLengths {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "perfect hash map must have one displacement per entry \
+                 (displacements: {}, entries: {})",
+                self.displacements, self.entries,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for MismatchedLengths {}
+
+    #[derive(Debug)]
+    struct InvalidDisplacement {
+        index: usize,
+        displacement: u32,
+    }
+
+    impl fmt::Display for InvalidDisplacement {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "displacement {} at index {} is out of bounds",
+                self.displacement, self.index,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for InvalidDisplacement {}
+
+    unsafe impl<C, K, V, H> Verify<C> for ArchivedPhfMap<K, V, H>
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+        H: SeededHasher,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let len = self.entries.len();
+            if self.displacements.len() != len {
+                fail!(MismatchedLengths {
+                    displacements: self.displacements.len(),
+                    entries: len,
+                });
+            }
+
+            for (index, displacement) in self.displacements.iter().enumerate() {
+                let displacement = displacement.to_native();
+                if displacement != EMPTY
+                    && displacement & DISPLACED == 0
+                    && displacement as usize >= len
+                {
+                    fail!(InvalidDisplacement {
+                        index,
+                        displacement,
+                    });
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Archived perfect hash map and set implementations.
+//!
+//! These collections are built once at serialization time with a minimal
+//! perfect hash function using the compress, hash, and displace (CHD)
+//! algorithm. Every key maps to its own slot, so lookups always hash the key
+//! once, read one displacement, and compare against exactly one entry. There
+//! are no empty buckets or control bytes.
+//!
+//! Building a perfect hash function takes longer than building a hash table, so
+//! these collections are best suited to large, read-only lookup tables.
+
+pub mod map;
+pub mod set;
+
+pub use map::{ArchivedPhfMap, PhfMapResolver};
+pub use set::{ArchivedPhfSet, PhfSetResolver};
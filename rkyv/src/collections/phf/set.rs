@@ -0,0 +1,132 @@
+//! Archived perfect hash set implementation.
+
+use core::{borrow::Borrow, fmt, hash::Hash};
+
+use munge::munge;
+use rancor::{Fallible, Source};
+
+use crate::{
+    collections::phf::map::{ArchivedPhfMap, Keys, PhfMapResolver},
+    hash::{FxHasher64, SeededHasher},
+    ser::{Allocator, Writer},
+    Place, Portable, Serialize,
+};
+
+/// An archived hash set which is indexed by a minimal perfect hash function.
+/// This is a wrapper around a perfect hash map with the same key and unit
+/// value.
+#[derive(Portable)]
+#[archive(crate)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedPhfSet<K, H: SeededHasher = FxHasher64> {
+    inner: ArchivedPhfMap<K, (), H>,
+}
+
+impl<K, H: SeededHasher> ArchivedPhfSet<K, H> {
+    /// Gets the number of items in the set.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns whether there are no items in the set.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Gets an iterator over the keys of the underlying map.
+    pub fn iter(&self) -> Keys<'_, K, ()> {
+        self.inner.keys()
+    }
+
+    /// Returns the seed that keys are hashed with.
+    pub fn seed(&self) -> &H::Seed {
+        self.inner.seed()
+    }
+
+    /// Gets the key corresponding to the given key in the set.
+    pub fn get<Q>(&self, k: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.get_key_value(k).map(|(k, _)| k)
+    }
+
+    /// Returns whether the given key is in the set.
+    pub fn contains<Q>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.contains_key(k)
+    }
+
+    /// Resolves an archived perfect hash set from the given length and
+    /// parameters.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: PhfSetResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedPhfSet { inner } = out);
+        ArchivedPhfMap::resolve_from_len(len, resolver.0, inner);
+    }
+
+    /// Serializes an iterator of keys as a perfect hash set.
+    ///
+    /// The hasher is keyed with a fixed key of all zeroes. Use
+    /// [`serialize_from_iter_with_key`](Self::serialize_from_iter_with_key) to
+    /// choose the key instead.
+    pub fn serialize_from_iter<'a, KU, S, I>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<PhfSetResolver, S::Error>
+    where
+        KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+        I: ExactSizeIterator<Item = &'a KU>,
+    {
+        Self::serialize_from_iter_with_key(iter, [0; 2], serializer)
+    }
+
+    /// Serializes an iterator of keys as a perfect hash set with a hasher
+    /// keyed by the given key.
+    pub fn serialize_from_iter_with_key<'a, KU, S, I>(
+        iter: I,
+        key: [u64; 2],
+        serializer: &mut S,
+    ) -> Result<PhfSetResolver, S::Error>
+    where
+        KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+        I: ExactSizeIterator<Item = &'a KU>,
+    {
+        Ok(PhfSetResolver(
+            ArchivedPhfMap::<K, (), H>::serialize_from_iter_with_key(
+                iter.map(|x| (x, &())),
+                key,
+                serializer,
+            )?,
+        ))
+    }
+}
+
+impl<K: fmt::Debug, H: SeededHasher> fmt::Debug for ArchivedPhfSet<K, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, H: SeededHasher> PartialEq for ArchivedPhfSet<K, H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<K: Hash + Eq, H: SeededHasher> Eq for ArchivedPhfSet<K, H> {}
+
+/// The resolver for archived perfect hash sets.
+pub struct PhfSetResolver(PhfMapResolver);
@@ -0,0 +1,333 @@
+//! An archived map which stores its entries in a single vector sorted by key.
+
+use core::{
+    borrow::Borrow,
+    fmt,
+    iter::FusedIterator,
+    ops::{Index, RangeBounds},
+    slice,
+};
+
+use munge::munge;
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    collections::util::{
+        is_above, is_below, Entry, EntryAdapter, IteratorLengthMismatch,
+    },
+    ser::{Allocator, Writer},
+    util::SerVec,
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable, Serialize,
+};
+
+/// An archived map which stores its entries in a single vector, sorted by key.
+///
+/// Lookups use a binary search over the entries. This takes less space than a
+/// hash map or B-tree map because there are no control bytes, empty buckets, or
+/// node pointers, which makes it well suited to small and medium-sized maps.
+///
+/// The ordering of the archived keys must match the ordering of the keys they
+/// were serialized from.
+#[derive(Portable)]
+#[archive(crate)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedSortedVecMap<K, V> {
+    entries: ArchivedVec<Entry<K, V>>,
+}
+
+impl<K, V> ArchivedSortedVecMap<K, V> {
+    /// Returns whether the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the entries of the map as a slice, sorted by key.
+    pub fn as_slice(&self) -> &[Entry<K, V>] {
+        self.entries.as_slice()
+    }
+
+    /// Gets the key-value pair associated with the given key, or `None` if the
+    /// key is not present in the map.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        let entries = self.entries.as_slice();
+        let index = entries
+            .binary_search_by(|entry| entry.key.borrow().cmp(key))
+            .ok()?;
+        let entry = &entries[index];
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns the value associated with the given key, or `None` if the key is
+    /// not present in the map.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    /// Returns whether the map contains the given key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        self.get_key_value(key).is_some()
+    }
+
+    /// Returns the first key-value pair in the map, or `None` if the map is
+    /// empty.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.entries.first().map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Returns the last key-value pair in the map, or `None` if the map is
+    /// empty.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.entries.last().map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Gets an iterator over the sorted keys of the map.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Gets an iterator over the values of the map, sorted by key.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    /// Gets an iterator over the entries of the map with keys in the given
+    /// range, sorted by key.
+    ///
+    /// If the start of the range is after its end, the iterator is empty.
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+        R: RangeBounds<Q>,
+    {
+        let start_bound = range.start_bound();
+        let end_bound = range.end_bound();
+        let entries = self.entries.as_slice();
+        let start = entries
+            .partition_point(|entry| is_below(entry.key.borrow(), start_bound));
+        let end = entries
+            .partition_point(|entry| !is_above(entry.key.borrow(), end_bound));
+        Iter {
+            inner: entries[start..end.max(start)].iter(),
+        }
+    }
+
+    /// Resolves an archived sorted vec map from the given length and resolver.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: SortedVecMapResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedSortedVecMap { entries } = out);
+        ArchivedVec::resolve_from_len(len, resolver.0, entries);
+    }
+
+    /// Serializes an iterator of key-value pairs which are already sorted by
+    /// key as a sorted vec map.
+    ///
+    /// The keys must be unique and yielded in ascending order.
+    pub fn serialize_from_ordered_iter<'a, I, UK, UV, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<SortedVecMapResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = (&'a UK, &'a UV)> + Clone,
+        UK: 'a + Serialize<S, Archived = K>,
+        UV: 'a + Serialize<S, Archived = V>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        Ok(SortedVecMapResolver(ArchivedVec::serialize_from_iter(
+            iter.map(|(key, value)| EntryAdapter { key, value }),
+            serializer,
+        )?))
+    }
+
+    /// Serializes an iterator of key-value pairs in any order as a sorted vec
+    /// map.
+    ///
+    /// The keys must be unique.
+    pub fn serialize_from_iter<'a, I, UK, UV, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<SortedVecMapResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = (&'a UK, &'a UV)>,
+        UK: 'a + Serialize<S, Archived = K> + Ord,
+        UV: 'a + Serialize<S, Archived = V>,
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Source,
+    {
+        let len = iter.len();
+        SerVec::with_capacity(serializer, len, |entries, serializer| {
+            for entry in iter {
+                if entries.len() == len {
+                    fail!(IteratorLengthMismatch {
+                        expected: len,
+                        actual: len + 1,
+                    });
+                }
+                entries.push(entry);
+            }
+            if entries.len() != len {
+                fail!(IteratorLengthMismatch {
+                    expected: len,
+                    actual: entries.len(),
+                });
+            }
+
+            entries.sort_unstable_by_key(|&(key, _)| key);
+            Self::serialize_from_ordered_iter(
+                entries.iter().map(|&(key, value)| (key, value)),
+                serializer,
+            )
+        })?
+    }
+}
+
+impl<K, V> fmt::Debug for ArchivedSortedVecMap<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for ArchivedSortedVecMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries.as_slice() == other.entries.as_slice()
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for ArchivedSortedVecMap<K, V> {}
+
+impl<K, Q, V> Index<&Q> for ArchivedSortedVecMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).unwrap()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ArchivedSortedVecMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The resolver for archived sorted vec maps.
+pub struct SortedVecMapResolver(VecResolver);
+
+/// An iterator over the entries of an archived sorted vec map, sorted by key.
+pub struct Iter<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| (&entry.key, &entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the keys of an archived sorted vec map, in sorted order.
+pub struct Keys<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Keys<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of an archived sorted vec map, sorted by key.
+pub struct Values<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Values<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+impl<K, V> FusedIterator for Values<'_, K, V> {}
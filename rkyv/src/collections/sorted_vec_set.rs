@@ -0,0 +1,190 @@
+//! An archived set which stores its keys in a single vector in sorted order.
+
+use core::{borrow::Borrow, fmt, iter::FusedIterator, ops::RangeBounds};
+
+use munge::munge;
+use rancor::{Fallible, Source};
+
+use crate::{
+    collections::sorted_vec_map::{
+        self, ArchivedSortedVecMap, SortedVecMapResolver,
+    },
+    ser::{Allocator, Writer},
+    Place, Portable, Serialize,
+};
+
+/// An archived set which stores its keys in a single vector in sorted order.
+/// This is a wrapper around a sorted vec map with the same key and a value of
+/// `()`.
+#[derive(Portable)]
+#[archive(crate)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedSortedVecSet<K>(ArchivedSortedVecMap<K, ()>);
+
+impl<K> ArchivedSortedVecSet<K> {
+    /// Returns whether the set contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of keys in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns a reference to the key in the set, if any, that is equal to the
+    /// given key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&K>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        self.0.get_key_value(key).map(|(key, _)| key)
+    }
+
+    /// Returns whether the set contains the given key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        self.0.contains_key(key)
+    }
+
+    /// Returns the first key in the set, or `None` if the set is empty.
+    pub fn first(&self) -> Option<&K> {
+        self.0.first_key_value().map(|(key, _)| key)
+    }
+
+    /// Returns the last key in the set, or `None` if the set is empty.
+    pub fn last(&self) -> Option<&K> {
+        self.0.last_key_value().map(|(key, _)| key)
+    }
+
+    /// Gets an iterator over the keys of the set, in sorted order.
+    pub fn iter(&self) -> Iter<'_, K> {
+        Iter {
+            inner: self.0.iter(),
+        }
+    }
+
+    /// Gets an iterator over the keys of the set in the given range, in sorted
+    /// order.
+    ///
+    /// If the start of the range is after its end, the iterator is empty.
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+        R: RangeBounds<Q>,
+    {
+        Iter {
+            inner: self.0.range(range),
+        }
+    }
+
+    /// Resolves an archived sorted vec set from the given length and resolver.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: SortedVecSetResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedSortedVecSet(inner) = out);
+        ArchivedSortedVecMap::resolve_from_len(len, resolver.0, inner);
+    }
+
+    /// Serializes an iterator of keys which are already sorted as a sorted vec
+    /// set.
+    ///
+    /// The keys must be unique and yielded in ascending order.
+    pub fn serialize_from_ordered_iter<'a, I, UK, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<SortedVecSetResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = &'a UK> + Clone,
+        UK: 'a + Serialize<S, Archived = K>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        Ok(SortedVecSetResolver(
+            ArchivedSortedVecMap::serialize_from_ordered_iter(
+                iter.map(|key| (key, &())),
+                serializer,
+            )?,
+        ))
+    }
+
+    /// Serializes an iterator of keys in any order as a sorted vec set.
+    ///
+    /// The keys must be unique.
+    pub fn serialize_from_iter<'a, I, UK, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<SortedVecSetResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = &'a UK>,
+        UK: 'a + Serialize<S, Archived = K> + Ord,
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Source,
+    {
+        Ok(SortedVecSetResolver(
+            ArchivedSortedVecMap::serialize_from_iter(
+                iter.map(|key| (key, &())),
+                serializer,
+            )?,
+        ))
+    }
+}
+
+impl<K: fmt::Debug> fmt::Debug for ArchivedSortedVecSet<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq> PartialEq for ArchivedSortedVecSet<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K: Eq> Eq for ArchivedSortedVecSet<K> {}
+
+impl<'a, K> IntoIterator for &'a ArchivedSortedVecSet<K> {
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The resolver for archived sorted vec sets.
+pub struct SortedVecSetResolver(SortedVecMapResolver);
+
+/// An iterator over the keys of an archived sorted vec set, in sorted order.
+pub struct Iter<'a, K> {
+    inner: sorted_vec_map::Iter<'a, K, ()>,
+}
+
+impl<'a, K> Iterator for Iter<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K> DoubleEndedIterator for Iter<'_, K> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<K> ExactSizeIterator for Iter<'_, K> {}
+impl<K> FusedIterator for Iter<'_, K> {}
@@ -4,7 +4,7 @@
 use core::{
     borrow::Borrow,
     fmt,
-    hash::{Hash, Hasher},
+    hash::Hash,
     iter::FusedIterator,
     marker::PhantomData,
     pin::Pin,
@@ -19,7 +19,7 @@ use crate::{
         swiss_table::{ArchivedHashTable, HashTableResolver},
         util::{Entry, EntryAdapter, EntryResolver},
     },
-    hash::{hash_value, FxHasher64},
+    hash::{hash_value_with_seed, FxHasher64, SeededHasher},
     primitive::ArchivedUsize,
     ser::{Allocator, Writer, WriterExt as _},
     Place, Portable, RelPtr, Serialize,
@@ -34,13 +34,14 @@ use crate::{
     derive(bytecheck::CheckBytes),
     check_bytes(verify)
 )]
-pub struct ArchivedIndexMap<K, V, H = FxHasher64> {
+pub struct ArchivedIndexMap<K, V, H: SeededHasher = FxHasher64> {
     table: ArchivedHashTable<ArchivedUsize>,
     entries: RelPtr<Entry<K, V>>,
+    seed: H::Seed,
     _phantom: PhantomData<H>,
 }
 
-impl<K, V, H> ArchivedIndexMap<K, V, H> {
+impl<K, V, H: SeededHasher> ArchivedIndexMap<K, V, H> {
     fn entries(&self) -> &[Entry<K, V>] {
         unsafe { from_raw_parts(self.entries.as_ptr(), self.len()) }
     }
@@ -87,9 +88,12 @@ impl<K, V, H> ArchivedIndexMap<K, V, H> {
             inner: unsafe { self.raw_iter() },
         }
     }
-}
 
-impl<K, V, H: Hasher + Default> ArchivedIndexMap<K, V, H> {
+    /// Returns the seed that keys are hashed with.
+    pub fn seed(&self) -> &H::Seed {
+        &self.seed
+    }
+
     /// Gets the index, key, and value corresponding to the supplied key using
     /// the given comparison function.
     pub fn get_full_with<Q, C>(
@@ -268,7 +272,8 @@ impl<K, V, H: Hasher + Default> ArchivedIndexMap<K, V, H> {
         C: Fn(&Q, &K) -> bool,
     {
         let entries = self.entries();
-        let index = self.table.get_with(hash_value::<Q, H>(key), |i| {
+        let hash = hash_value_with_seed::<Q, H>(key, &self.seed);
+        let index = self.table.get_with(hash, |i| {
             cmp(key, &entries[i.to_native() as usize].key)
         })?;
         Some(index.to_native() as usize)
@@ -290,7 +295,9 @@ impl<K, V, H: Hasher + Default> ArchivedIndexMap<K, V, H> {
         resolver: IndexMapResolver,
         out: Place<Self>,
     ) {
-        munge!(let ArchivedIndexMap { table, entries, _phantom: _ } = out);
+        munge! {
+            let ArchivedIndexMap { table, entries, seed, _phantom: _ } = out;
+        }
         ArchivedHashTable::resolve_from_len(
             len,
             load_factor,
@@ -298,14 +305,42 @@ impl<K, V, H: Hasher + Default> ArchivedIndexMap<K, V, H> {
             table,
         );
         RelPtr::emplace(resolver.entries_pos, entries);
+        seed.write(H::seed_from_key(resolver.key));
     }
 
     /// Serializes an iterator of key-value pairs as an index map.
+    ///
+    /// The hasher is keyed with a fixed key of all zeroes. Use
+    /// [`serialize_from_iter_with_key`](Self::serialize_from_iter_with_key) to
+    /// choose the key instead.
     pub fn serialize_from_iter<'a, I, UK, UV, S>(
         iter: I,
         load_factor: (usize, usize),
         serializer: &mut S,
     ) -> Result<IndexMapResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator<Item = (&'a UK, &'a UV)>,
+        UK: 'a + Serialize<S, Archived = K> + Hash + Eq,
+        UV: 'a + Serialize<S, Archived = V>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        Self::serialize_from_iter_with_key(
+            iter,
+            [0; 2],
+            load_factor,
+            serializer,
+        )
+    }
+
+    /// Serializes an iterator of key-value pairs as an index map with a hasher
+    /// keyed by the given key.
+    pub fn serialize_from_iter_with_key<'a, I, UK, UV, S>(
+        iter: I,
+        key: [u64; 2],
+        load_factor: (usize, usize),
+        serializer: &mut S,
+    ) -> Result<IndexMapResolver, S::Error>
     where
         I: Clone + ExactSizeIterator<Item = (&'a UK, &'a UV)>,
         UK: 'a + Serialize<S, Archived = K> + Hash + Eq,
@@ -316,10 +351,12 @@ impl<K, V, H: Hasher + Default> ArchivedIndexMap<K, V, H> {
         use crate::util::SerVec;
 
         // Serialize hash table
+        let seed = H::seed_from_key(key);
         let table_resolver =
             ArchivedHashTable::<ArchivedUsize>::serialize_from_iter(
                 0..iter.len(),
-                iter.clone().map(|(key, _)| hash_value::<UK, H>(key)),
+                iter.clone()
+                    .map(|(key, _)| hash_value_with_seed::<UK, H>(key, &seed)),
                 load_factor,
                 serializer,
             )?;
@@ -351,6 +388,7 @@ impl<K, V, H: Hasher + Default> ArchivedIndexMap<K, V, H> {
                 Ok(IndexMapResolver {
                     table_resolver,
                     entries_pos,
+                    key,
                 })
             },
         )?
@@ -361,6 +399,7 @@ impl<K, V, H> fmt::Debug for ArchivedIndexMap<K, V, H>
 where
     K: fmt::Debug,
     V: fmt::Debug,
+    H: SeededHasher,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
@@ -371,13 +410,14 @@ impl<K, V, H> PartialEq for ArchivedIndexMap<K, V, H>
 where
     K: PartialEq,
     V: PartialEq,
+    H: SeededHasher,
 {
     fn eq(&self, other: &Self) -> bool {
         self.iter().eq(other.iter())
     }
 }
 
-impl<K: Eq, V: Eq, H> Eq for ArchivedIndexMap<K, V, H> {}
+impl<K: Eq, V: Eq, H: SeededHasher> Eq for ArchivedIndexMap<K, V, H> {}
 
 struct RawIter<'a, K, V> {
     current: *const Entry<K, V>,
@@ -487,6 +527,7 @@ impl<K, V> FusedIterator for Values<'_, K, V> {}
 pub struct IndexMapResolver {
     table_resolver: HashTableResolver,
     entries_pos: usize,
+    key: [u64; 2],
 }
 
 #[cfg(feature = "bytecheck")]
@@ -497,6 +538,7 @@ mod verify {
     use super::ArchivedIndexMap;
     use crate::{
         collections::util::Entry,
+        hash::SeededHasher,
         validation::{ArchiveContext, ArchiveContextExt},
     };
 
@@ -506,6 +548,7 @@ mod verify {
         C::Error: Source,
         K: CheckBytes<C>,
         V: CheckBytes<C>,
+        H: SeededHasher,
     {
         fn verify(
             &self,
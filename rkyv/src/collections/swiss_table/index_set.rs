@@ -1,11 +1,7 @@
 //! An archived index set implementation based on Google's high-performance
 //! SwissTable hash map.
 
-use core::{
-    borrow::Borrow,
-    fmt,
-    hash::{Hash, Hasher},
-};
+use core::{borrow::Borrow, fmt, hash::Hash};
 
 use munge::munge;
 use rancor::{Fallible, Source};
@@ -14,7 +10,7 @@ use crate::{
     collections::swiss_table::{
         index_map::Keys, ArchivedIndexMap, IndexMapResolver,
     },
-    hash::FxHasher64,
+    hash::{FxHasher64, SeededHasher},
     ser::{Allocator, Writer},
     Place, Portable, Serialize,
 };
@@ -24,11 +20,11 @@ use crate::{
 #[archive(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 #[repr(transparent)]
-pub struct ArchivedIndexSet<K, H = FxHasher64> {
+pub struct ArchivedIndexSet<K, H: SeededHasher = FxHasher64> {
     inner: ArchivedIndexMap<K, (), H>,
 }
 
-impl<K, H> ArchivedIndexSet<K, H> {
+impl<K, H: SeededHasher> ArchivedIndexSet<K, H> {
     /// Returns whether the index set contains no values.
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
@@ -43,9 +39,12 @@ impl<K, H> ArchivedIndexSet<K, H> {
     pub fn len(&self) -> usize {
         self.inner.len()
     }
-}
 
-impl<K, H: Default + Hasher> ArchivedIndexSet<K, H> {
+    /// Returns the seed that keys are hashed with.
+    pub fn seed(&self) -> &H::Seed {
+        self.inner.seed()
+    }
+
     /// Returns whether a key is present in the hash set.
     pub fn contains<Q>(&self, k: &Q) -> bool
     where
@@ -99,11 +98,37 @@ impl<K, H: Default + Hasher> ArchivedIndexSet<K, H> {
     }
 
     /// Serializes an iterator of keys as an index set.
+    ///
+    /// The hasher is keyed with a fixed key of all zeroes. Use
+    /// [`serialize_from_iter_with_key`](Self::serialize_from_iter_with_key) to
+    /// choose the key instead.
     pub fn serialize_from_iter<'a, I, UK, S>(
         iter: I,
         load_factor: (usize, usize),
         serializer: &mut S,
     ) -> Result<IndexSetResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator<Item = &'a UK>,
+        UK: 'a + Serialize<S, Archived = K> + Hash + Eq,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        Self::serialize_from_iter_with_key(
+            iter,
+            [0; 2],
+            load_factor,
+            serializer,
+        )
+    }
+
+    /// Serializes an iterator of keys as an index set with a hasher keyed by
+    /// the given key.
+    pub fn serialize_from_iter_with_key<'a, I, UK, S>(
+        iter: I,
+        key: [u64; 2],
+        load_factor: (usize, usize),
+        serializer: &mut S,
+    ) -> Result<IndexSetResolver, S::Error>
     where
         I: Clone + ExactSizeIterator<Item = &'a UK>,
         UK: 'a + Serialize<S, Archived = K> + Hash + Eq,
@@ -111,8 +136,9 @@ impl<K, H: Default + Hasher> ArchivedIndexSet<K, H> {
         S::Error: Source,
     {
         Ok(IndexSetResolver(
-            ArchivedIndexMap::<K, (), H>::serialize_from_iter(
+            ArchivedIndexMap::<K, (), H>::serialize_from_iter_with_key(
                 iter.map(|x| (x, &())),
+                key,
                 load_factor,
                 serializer,
             )?,
@@ -120,19 +146,19 @@ impl<K, H: Default + Hasher> ArchivedIndexSet<K, H> {
     }
 }
 
-impl<K: fmt::Debug, H> fmt::Debug for ArchivedIndexSet<K, H> {
+impl<K: fmt::Debug, H: SeededHasher> fmt::Debug for ArchivedIndexSet<K, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set().entries(self.iter()).finish()
     }
 }
 
-impl<K: PartialEq, H> PartialEq for ArchivedIndexSet<K, H> {
+impl<K: PartialEq, H: SeededHasher> PartialEq for ArchivedIndexSet<K, H> {
     fn eq(&self, other: &Self) -> bool {
         self.iter().eq(other.iter())
     }
 }
 
-impl<K: Eq, H> Eq for ArchivedIndexSet<K, H> {}
+impl<K: Eq, H: SeededHasher> Eq for ArchivedIndexSet<K, H> {}
 
 /// The resolver for archived index sets.
 pub struct IndexSetResolver(IndexMapResolver);
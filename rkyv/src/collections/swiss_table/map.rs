@@ -1,13 +1,8 @@
 //! Archived hash map implementation using an archived SwissTable.
 
 use core::{
-    borrow::Borrow,
-    fmt,
-    hash::{Hash, Hasher},
-    iter::FusedIterator,
-    marker::PhantomData,
-    ops::Index,
-    pin::Pin,
+    borrow::Borrow, fmt, hash::Hash, iter::FusedIterator, marker::PhantomData,
+    ops::Index, pin::Pin,
 };
 
 use munge::munge;
@@ -18,22 +13,26 @@ use crate::{
         swiss_table::table::{ArchivedHashTable, HashTableResolver, RawIter},
         util::{Entry, EntryAdapter},
     },
-    hash::{hash_value, FxHasher64},
+    hash::{hash_value_with_seed, FxHasher64, SeededHasher},
     ser::{Allocator, Writer},
     Place, Portable, Serialize,
 };
 
 /// An archived SwissTable hash map.
+///
+/// Keys are hashed with `H` keyed by the seed the hash map was serialized
+/// with. See [`SeededHasher`] for more details.
 #[derive(Portable)]
 #[archive(crate)]
-#[repr(transparent)]
+#[repr(C)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
-pub struct ArchivedHashMap<K, V, H = FxHasher64> {
+pub struct ArchivedHashMap<K, V, H: SeededHasher = FxHasher64> {
     table: ArchivedHashTable<Entry<K, V>>,
+    seed: H::Seed,
     _phantom: PhantomData<H>,
 }
 
-impl<K, V, H> ArchivedHashMap<K, V, H> {
+impl<K, V, H: SeededHasher> ArchivedHashMap<K, V, H> {
     /// Returns whether the hash map is empty.
     pub const fn is_empty(&self) -> bool {
         self.table.is_empty()
@@ -90,9 +89,17 @@ impl<K, V, H> ArchivedHashMap<K, V, H> {
             _phantom: PhantomData,
         }
     }
-}
 
-impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
+    /// Returns the seed that keys are hashed with.
+    pub fn seed(&self) -> &H::Seed {
+        &self.seed
+    }
+
+    /// Hashes a key with the seed of the hash map.
+    fn hash_key<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+        hash_value_with_seed::<Q, H>(key, &self.seed)
+    }
+
     /// Returns the key-value pair corresponding to the supplied key using the
     /// given comparison function.
     pub fn get_key_value_with<Q, C>(&self, key: &Q, cmp: C) -> Option<(&K, &V)>
@@ -102,7 +109,7 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
     {
         let entry = self
             .table
-            .get_with(hash_value::<Q, H>(key), |e| cmp(key, &e.key))?;
+            .get_with(self.hash_key(key), |e| cmp(key, &e.key))?;
         Some((&entry.key, &entry.value))
     }
 
@@ -146,9 +153,9 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
         Q: Hash + Eq + ?Sized,
         C: Fn(&Q, &K) -> bool,
     {
+        let hash = self.hash_key(key);
         let table = unsafe { Pin::map_unchecked_mut(self, |s| &mut s.table) };
-        let entry = table
-            .get_with_mut(hash_value::<Q, H>(key), |e| cmp(key, &e.key))?;
+        let entry = table.get_with_mut(hash, |e| cmp(key, &e.key))?;
         let entry = unsafe { Pin::into_inner_unchecked(entry) };
         let key = &entry.key;
         let value = unsafe { Pin::new_unchecked(&mut entry.value) };
@@ -202,6 +209,10 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
     }
 
     /// Serializes an iterator of key-value pairs as a hash map.
+    ///
+    /// The hasher is keyed with a fixed key of all zeroes. Use
+    /// [`serialize_from_iter_with_key`](Self::serialize_from_iter_with_key) to
+    /// choose the key instead.
     pub fn serialize_from_iter<'a, I, KU, VU, S>(
         iter: I,
         load_factor: (usize, usize),
@@ -214,13 +225,37 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
         S: Fallible + Writer + Allocator + ?Sized,
         S::Error: Source,
     {
-        ArchivedHashTable::<Entry<K, V>>::serialize_from_iter(
-            iter.clone().map(|(key, value)| EntryAdapter { key, value }),
-            iter.map(|(key, _)| hash_value::<KU, H>(key)),
+        Self::serialize_from_iter_with_key(
+            iter,
+            [0; 2],
             load_factor,
             serializer,
         )
-        .map(HashMapResolver)
+    }
+
+    /// Serializes an iterator of key-value pairs as a hash map with a hasher
+    /// keyed by the given key.
+    pub fn serialize_from_iter_with_key<'a, I, KU, VU, S>(
+        iter: I,
+        key: [u64; 2],
+        load_factor: (usize, usize),
+        serializer: &mut S,
+    ) -> Result<HashMapResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator<Item = (&'a KU, &'a VU)>,
+        KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+        VU: 'a + Serialize<S, Archived = V>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        let seed = H::seed_from_key(key);
+        let table = ArchivedHashTable::<Entry<K, V>>::serialize_from_iter(
+            iter.clone().map(|(key, value)| EntryAdapter { key, value }),
+            iter.map(|(key, _)| hash_value_with_seed::<KU, H>(key, &seed)),
+            load_factor,
+            serializer,
+        )?;
+        Ok(HashMapResolver { table, key })
     }
 
     /// Resolves an archived hash map from a given length and parameters.
@@ -230,13 +265,14 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
         resolver: HashMapResolver,
         out: Place<Self>,
     ) {
-        munge!(let ArchivedHashMap { table, _phantom: _ } = out);
+        munge!(let ArchivedHashMap { table, seed, _phantom: _ } = out);
         ArchivedHashTable::<Entry<K, V>>::resolve_from_len(
             len,
             load_factor,
-            resolver.0,
+            resolver.table,
             table,
-        )
+        );
+        seed.write(H::seed_from_key(resolver.key));
     }
 }
 
@@ -244,6 +280,7 @@ impl<K, V, H> fmt::Debug for ArchivedHashMap<K, V, H>
 where
     K: fmt::Debug,
     V: fmt::Debug,
+    H: SeededHasher,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
@@ -254,7 +291,7 @@ impl<K, V, H> Eq for ArchivedHashMap<K, V, H>
 where
     K: Hash + Eq,
     V: Eq,
-    H: Default + Hasher,
+    H: SeededHasher,
 {
 }
 
@@ -262,7 +299,7 @@ impl<K, V, H> PartialEq for ArchivedHashMap<K, V, H>
 where
     K: Hash + Eq,
     V: PartialEq,
-    H: Default + Hasher,
+    H: SeededHasher,
 {
     fn eq(&self, other: &Self) -> bool {
         if self.len() != other.len() {
@@ -279,7 +316,7 @@ impl<K, Q, V, H> Index<&'_ Q> for ArchivedHashMap<K, V, H>
 where
     K: Eq + Hash + Borrow<Q>,
     Q: Eq + Hash + ?Sized,
-    H: Default + Hasher,
+    H: SeededHasher,
 {
     type Output = V;
 
@@ -289,12 +326,15 @@ where
 }
 
 /// The resolver for [`ArchivedHashMap`].
-pub struct HashMapResolver(HashTableResolver);
+pub struct HashMapResolver {
+    table: HashTableResolver,
+    key: [u64; 2],
+}
 
 /// An iterator over the key-value pairs of an [`ArchivedHashMap`].
 pub struct Iter<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for Iter<'a, K, V, H> {
@@ -319,7 +359,7 @@ impl<K, V, H> FusedIterator for Iter<'_, K, V, H> {}
 /// An iterator over the mutable key-value pairs of an [`ArchivedHashMap`].
 pub struct IterMut<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for IterMut<'a, K, V, H> {
@@ -345,7 +385,7 @@ impl<K, V, H> FusedIterator for IterMut<'_, K, V, H> {}
 /// An iterator over the keys of an [`ArchivedHashMap`].
 pub struct Keys<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for Keys<'a, K, V, H> {
@@ -370,7 +410,7 @@ impl<K, V, H> FusedIterator for Keys<'_, K, V, H> {}
 /// An iterator over the values of an [`ArchivedHashMap`].
 pub struct Values<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for Values<'a, K, V, H> {
@@ -395,7 +435,7 @@ impl<K, V, H> FusedIterator for Values<'_, K, V, H> {}
 /// An iterator over the mutable values of an [`ArchivedHashMap`].
 pub struct ValuesMut<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for ValuesMut<'a, K, V, H> {
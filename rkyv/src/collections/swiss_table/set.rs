@@ -1,17 +1,13 @@
 //! Archived hash set implementation using an archived SwissTable.
 
-use core::{
-    borrow::Borrow,
-    fmt,
-    hash::{Hash, Hasher},
-};
+use core::{borrow::Borrow, fmt, hash::Hash};
 
 use munge::munge;
 use rancor::{Fallible, Source};
 
 use crate::{
     collections::swiss_table::map::{ArchivedHashMap, HashMapResolver, Keys},
-    hash::FxHasher64,
+    hash::{FxHasher64, SeededHasher},
     ser::{Allocator, Writer},
     Place, Portable, Serialize,
 };
@@ -22,11 +18,11 @@ use crate::{
 #[archive(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 #[repr(transparent)]
-pub struct ArchivedHashSet<K, H = FxHasher64> {
+pub struct ArchivedHashSet<K, H: SeededHasher = FxHasher64> {
     inner: ArchivedHashMap<K, (), H>,
 }
 
-impl<K, H> ArchivedHashSet<K, H> {
+impl<K, H: SeededHasher> ArchivedHashSet<K, H> {
     /// Gets the number of items in the hash set.
     pub const fn len(&self) -> usize {
         self.inner.len()
@@ -41,9 +37,12 @@ impl<K, H> ArchivedHashSet<K, H> {
     pub fn iter(&self) -> Keys<K, (), H> {
         self.inner.keys()
     }
-}
 
-impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
+    /// Returns the seed that keys are hashed with.
+    pub fn seed(&self) -> &H::Seed {
+        self.inner.seed()
+    }
+
     /// Gets the key corresponding to the given key in the hash set.
     pub fn get<Q>(&self, k: &Q) -> Option<&K>
     where
@@ -74,11 +73,37 @@ impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
     }
 
     /// Serializes an iterator of keys as a hash set.
+    ///
+    /// The hasher is keyed with a fixed key of all zeroes. Use
+    /// [`serialize_from_iter_with_key`](Self::serialize_from_iter_with_key) to
+    /// choose the key instead.
     pub fn serialize_from_iter<'a, KU, S, I>(
         iter: I,
         load_factor: (usize, usize),
         serializer: &mut S,
     ) -> Result<HashSetResolver, S::Error>
+    where
+        KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+        I: Clone + ExactSizeIterator<Item = &'a KU>,
+    {
+        Self::serialize_from_iter_with_key(
+            iter,
+            [0; 2],
+            load_factor,
+            serializer,
+        )
+    }
+
+    /// Serializes an iterator of keys as a hash set with a hasher keyed by the
+    /// given key.
+    pub fn serialize_from_iter_with_key<'a, KU, S, I>(
+        iter: I,
+        key: [u64; 2],
+        load_factor: (usize, usize),
+        serializer: &mut S,
+    ) -> Result<HashSetResolver, S::Error>
     where
         KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
         S: Fallible + Writer + Allocator + ?Sized,
@@ -86,8 +111,9 @@ impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
         I: Clone + ExactSizeIterator<Item = &'a KU>,
     {
         Ok(HashSetResolver(
-            ArchivedHashMap::<K, (), H>::serialize_from_iter(
+            ArchivedHashMap::<K, (), H>::serialize_from_iter_with_key(
                 iter.map(|x| (x, &())),
+                key,
                 load_factor,
                 serializer,
             )?,
@@ -95,19 +121,19 @@ impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
     }
 }
 
-impl<K: fmt::Debug, H> fmt::Debug for ArchivedHashSet<K, H> {
+impl<K: fmt::Debug, H: SeededHasher> fmt::Debug for ArchivedHashSet<K, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set().entries(self.iter()).finish()
     }
 }
 
-impl<K: Hash + Eq, H: Hasher + Default> PartialEq for ArchivedHashSet<K, H> {
+impl<K: Hash + Eq, H: SeededHasher> PartialEq for ArchivedHashSet<K, H> {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner
     }
 }
 
-impl<K: Hash + Eq, H: Hasher + Default> Eq for ArchivedHashSet<K, H> {}
+impl<K: Hash + Eq, H: SeededHasher> Eq for ArchivedHashSet<K, H> {}
 
 /// The resolver for archived hash sets.
 pub struct HashSetResolver(HashMapResolver);
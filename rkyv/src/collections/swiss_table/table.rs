@@ -16,6 +16,9 @@
 //! - Because the available SIMD group width may be less than the maximum group
 //!   width, each probe reads N groups before striding where N is the maximum
 //!   group width divided by the SIMD group width.
+//! - With AVX2, lookups read two consecutive probes at once instead of reading
+//!   wider groups, so the archived format does not depend on the SIMD group
+//!   width.
 
 use core::{
     alloc::Layout,
@@ -30,6 +33,11 @@ use core::{
 use munge::munge;
 use rancor::{fail, Fallible, OptionExt, Panic, ResultExt as _, Source};
 
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "avx2",
+))]
+use crate::simd::GroupPair;
 use crate::{
     collections::util::IteratorLengthMismatch,
     primitive::ArchivedUsize,
@@ -78,10 +86,18 @@ impl ProbeSeq {
 
     #[inline]
     fn move_next(&mut self, bucket_mask: usize) {
+        self.pos += self.stride;
+        self.pos &= bucket_mask;
+        self.stride += MAX_GROUP_WIDTH;
+    }
+
+    #[inline]
+    fn move_next_within(&mut self, bucket_mask: usize, capacity: usize) {
         loop {
-            self.pos += self.stride;
-            self.pos &= bucket_mask;
-            self.stride += MAX_GROUP_WIDTH;
+            self.move_next(bucket_mask);
+            if self.pos < capacity {
+                break;
+            }
         }
     }
 }
@@ -137,6 +153,10 @@ impl<T> ArchivedHashTable<T> {
         let capacity = self.capacity();
         let bucket_mask = Self::bucket_mask(capacity);
 
+        #[cfg(not(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "avx2",
+        )))]
         loop {
             let mut any_empty = false;
 
@@ -164,12 +184,54 @@ impl<T> ArchivedHashTable<T> {
                 return None;
             }
 
-            loop {
-                probe_seq.move_next(bucket_mask);
-                if probe_seq.pos < self.capacity() {
-                    break;
+            probe_seq.move_next_within(bucket_mask, capacity);
+        }
+
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            target_feature = "avx2",
+        ))]
+        loop {
+            // Read this probe and the next one together. The second probe only
+            // counts if the first one has no empty buckets. AVX2 uses 16-byte
+            // groups, so each probe is a single group.
+            let first = probe_seq.pos;
+            probe_seq.next_group();
+            probe_seq.move_next_within(bucket_mask, capacity);
+            let second = probe_seq.pos;
+
+            let pair = unsafe {
+                GroupPair::read(self.control(first), self.control(second))
+            };
+            let empty = pair.match_empty();
+            let first_empty = empty
+                .lowest_set_bit()
+                .map_or(false, |bit| bit < Group::WIDTH);
+
+            for bit in pair.match_byte(h2_hash) {
+                let index = if bit < Group::WIDTH {
+                    (first + bit) % capacity
+                } else if first_empty {
+                    return None;
+                } else {
+                    (second + bit - Group::WIDTH) % capacity
+                };
+                let bucket_ptr = unsafe { self.bucket(index) };
+                let bucket = unsafe { bucket_ptr.as_ref() };
+
+                // Opt: These can be marked as likely true on nightly.
+                if cmp(bucket) {
+                    return Some(bucket_ptr);
                 }
             }
+
+            // Opt: These can be marked as likely true on nightly.
+            if empty.any_bit_set() {
+                return None;
+            }
+
+            probe_seq.next_group();
+            probe_seq.move_next_within(bucket_mask, capacity);
         }
     }
 
@@ -425,12 +487,7 @@ impl<T> ArchivedHashTable<T> {
                         probe_seq.next_group();
                     }
 
-                    loop {
-                        probe_seq.move_next(bucket_mask);
-                        if probe_seq.pos < capacity {
-                            break;
-                        }
-                    }
+                    probe_seq.move_next_within(bucket_mask, capacity);
                 }
             }
 
@@ -560,12 +617,14 @@ mod verify {
     use core::fmt;
 
     use bytecheck::{CheckBytes, Verify};
-    use rancor::{fail, Fallible, Source};
+    use rancor::{fail, Fallible, ResultExt as _, Source};
 
     use super::ArchivedHashTable;
     use crate::{
         simd::Group,
-        validation::{ArchiveContext, ArchiveContextExt as _},
+        validation::{
+            path::PathSegment, ArchiveContext, ArchiveContextExt as _,
+        },
     };
 
     #[derive(Debug)]
@@ -619,6 +678,7 @@ mod verify {
             if self.len() >= cap {
                 fail!(InvalidLength { len, cap });
             }
+            context.check_collection_len(len)?;
 
             // Check memory allocation
             let control_count = Self::control_count(cap)?;
@@ -647,7 +707,8 @@ mod verify {
                             T::check_bytes(
                                 self.bucket(index).as_ptr(),
                                 context,
-                            )?;
+                            )
+                            .with_trace(|| PathSegment::Index(index))?;
                         }
                     }
 
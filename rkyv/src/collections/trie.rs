@@ -0,0 +1,702 @@
+//! An archived map from strings to values, stored as a compressed trie.
+//!
+//! The trie is a radix tree: every node is labeled with the bytes which are
+//! added to the key of its parent, and nodes with only one child and no value
+//! are merged into their child. Keys which share a prefix only store it once,
+//! which makes tries a compact way to store large dictionaries of strings.
+//!
+//! Nodes are stored in a single vector in preorder. This means that the nodes
+//! in the subtree of a node immediately follow it, and that visiting nodes in
+//! order visits their keys in lexicographic order. Prefix and range queries
+//! find the span of nodes which match and then walk it from front to back.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::{
+    cmp::Ordering,
+    iter::FusedIterator,
+    ops::{Bound, RangeBounds},
+};
+use core::{fmt, iter, mem::size_of, slice};
+
+use munge::munge;
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    collections::util::IteratorLengthMismatch,
+    primitive::ArchivedU32,
+    ser::{Allocator, Writer, WriterExt as _},
+    util::SerVec,
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable, Serialize,
+};
+
+/// The value index of nodes which do not have a value.
+const NONE: u32 = u32::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Portable)]
+#[archive(crate)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(C)]
+struct Node {
+    /// The offset of the node's label in the label bytes.
+    label_start: ArchivedU32,
+    /// The length of the node's label.
+    label_len: ArchivedU32,
+    /// The length of the key of the node's parent.
+    depth: ArchivedU32,
+    /// The index one past the last node in the node's subtree.
+    end: ArchivedU32,
+    /// The index of the node's value, or `NONE` if it does not have one.
+    value: ArchivedU32,
+}
+
+/// An archived map from strings to values, stored as a compressed trie.
+///
+/// See the [module docs](crate::collections::trie) for more details.
+#[derive(Portable)]
+#[archive(crate)]
+#[repr(C)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedTrieMap<V> {
+    nodes: ArchivedVec<Node>,
+    labels: ArchivedVec<u8>,
+    values: ArchivedVec<V>,
+}
+
+impl<V> ArchivedTrieMap<V> {
+    /// Returns whether the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the value associated with the given key, or `None` if the key is
+    /// not present in the map.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        let (index, exact) = self.prefix_node(key.as_bytes())?;
+        if !exact {
+            return None;
+        }
+        match self.nodes[index].value.to_native() {
+            NONE => None,
+            value => Some(&self.values[value as usize]),
+        }
+    }
+
+    /// Returns whether the map contains the given key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the values of the map, sorted by key.
+    pub fn values(&self) -> &[V] {
+        self.values.as_slice()
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    #[cfg(feature = "alloc")]
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter::new(self, 0, self.nodes.len())
+    }
+
+    /// Gets an iterator over the entries of the map with keys that start with
+    /// the given prefix, sorted by key.
+    #[cfg(feature = "alloc")]
+    pub fn starts_with(&self, prefix: &str) -> Iter<'_, V> {
+        match self.prefix_node(prefix.as_bytes()) {
+            Some((index, _)) => Iter::new(self, index, self.end(index)),
+            None => Iter::new(self, 0, 0),
+        }
+    }
+
+    /// Gets an iterator over the entries of the map with keys in the given
+    /// range, sorted by key.
+    ///
+    /// Keys are compared lexicographically by their bytes. If the start of the
+    /// range is after its end, the iterator is empty.
+    #[cfg(feature = "alloc")]
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, V>
+    where
+        Q: AsRef<str> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(key) => self.lower_bound(key.as_ref(), true),
+            Bound::Excluded(key) => self.lower_bound(key.as_ref(), false),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.lower_bound(key.as_ref(), false),
+            Bound::Excluded(key) => self.lower_bound(key.as_ref(), true),
+            Bound::Unbounded => self.nodes.len(),
+        };
+        Iter::new(self, start, end.max(start))
+    }
+
+    fn label(&self, index: usize) -> &[u8] {
+        let node = &self.nodes[index];
+        let start = node.label_start.to_native() as usize;
+        let len = node.label_len.to_native() as usize;
+        &self.labels[start..start + len]
+    }
+
+    fn end(&self, index: usize) -> usize {
+        self.nodes[index].end.to_native() as usize
+    }
+
+    /// Returns the indices of the children of the given node, in order.
+    fn children(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let end = self.end(index);
+        let first = Some(index + 1).filter(|&child| child < end);
+        iter::successors(first, move |&child| {
+            Some(self.end(child)).filter(|&next| next < end)
+        })
+    }
+
+    /// Finds the first node whose key starts with the given prefix, and
+    /// whether that node's key is equal to the prefix.
+    ///
+    /// The nodes with keys that start with the prefix are exactly the subtree
+    /// of the returned node.
+    fn prefix_node(&self, prefix: &[u8]) -> Option<(usize, bool)> {
+        let mut index = 0;
+        let mut rest = prefix;
+        while let Some(&first) = rest.first() {
+            let child = self
+                .children(index)
+                .find(|&child| self.label(child)[0] == first)?;
+            let label = self.label(child);
+            if rest.len() < label.len() {
+                return label.starts_with(rest).then_some((child, false));
+            }
+            let (head, tail) = rest.split_at(label.len());
+            if head != label {
+                return None;
+            }
+            index = child;
+            rest = tail;
+        }
+        Some((index, true))
+    }
+
+    /// Returns the index of the first node with a key greater than or equal to
+    /// the given key, or strictly greater than it if `inclusive` is false.
+    #[cfg(feature = "alloc")]
+    fn lower_bound(&self, key: &str, inclusive: bool) -> usize {
+        let mut index = 0;
+        let mut rest = key.as_bytes();
+        loop {
+            if rest.is_empty() {
+                return if inclusive { index } else { index + 1 };
+            }
+
+            // The key of the current node is a proper prefix of the key, so
+            // the first node above the bound is one of its descendants or
+            // comes after its subtree.
+            let mut next = None;
+            for child in self.children(index) {
+                let label = self.label(child);
+                let len = label.len().min(rest.len());
+                match label[..len].cmp(&rest[..len]) {
+                    Ordering::Less => (),
+                    Ordering::Greater => return child,
+                    Ordering::Equal if label.len() > rest.len() => {
+                        return child
+                    }
+                    Ordering::Equal => {
+                        next = Some(child);
+                        break;
+                    }
+                }
+            }
+
+            let Some(child) = next else {
+                return self.end(index);
+            };
+            rest = &rest[self.label(child).len()..];
+            index = child;
+        }
+    }
+
+    /// Resolves an archived trie map from the given length and resolver.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: TrieMapResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedTrieMap { nodes, labels, values } = out);
+        ArchivedVec::resolve_from_len(
+            resolver.nodes_len,
+            resolver.nodes,
+            nodes,
+        );
+        ArchivedVec::resolve_from_len(
+            resolver.labels_len,
+            resolver.labels,
+            labels,
+        );
+        ArchivedVec::resolve_from_len(len, resolver.values, values);
+    }
+
+    /// Serializes an iterator of key-value pairs as a trie map.
+    ///
+    /// The keys must be unique and yielded in ascending order, as they are
+    /// from a `BTreeMap`.
+    pub fn serialize_from_ordered_iter<'a, I, UK, UV, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<TrieMapResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = (&'a UK, &'a UV)>,
+        UK: 'a + AsRef<str> + ?Sized,
+        UV: 'a + Serialize<S, Archived = V>,
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Source,
+    {
+        let len = iter.len();
+        // A trie has at most one node per key, one branching node per key
+        // after the first, and the root.
+        let max_nodes = len.saturating_mul(2).saturating_add(1);
+        if max_nodes >= NONE as usize {
+            fail!(TooLarge);
+        }
+
+        SerVec::with_capacity(serializer, len, |entries, serializer| {
+            for (key, value) in iter {
+                if entries.len() == len {
+                    fail!(IteratorLengthMismatch {
+                        expected: len,
+                        actual: len + 1,
+                    });
+                }
+                let key = key.as_ref().as_bytes();
+                if let Some(&(prev, _)) = entries.last() {
+                    if prev >= key {
+                        fail!(UnorderedKeys);
+                    }
+                }
+                entries.push((key, value));
+            }
+            if entries.len() != len {
+                fail!(IteratorLengthMismatch {
+                    expected: len,
+                    actual: entries.len(),
+                });
+            }
+
+            SerVec::with_capacity(
+                serializer,
+                max_nodes,
+                |nodes, serializer| {
+                    SerVec::with_capacity(
+                        serializer,
+                        max_nodes,
+                        |tasks, _| build(entries, nodes, tasks),
+                    )?;
+
+                    let labels_len =
+                        nodes.iter().map(|node| node.label_len).sum::<usize>();
+                    if labels_len > u32::MAX as usize {
+                        fail!(TooLarge);
+                    }
+
+                    // The nodes, labels, and values are written in the same order
+                    // as the fields of the archived trie.
+                    let nodes_resolver = write_nodes(nodes, serializer)?;
+                    let labels = write_labels(entries, nodes, serializer)?;
+                    let values =
+                        ArchivedVec::<V>::serialize_from_iter::<UV, _, _>(
+                            entries.iter().map(|&(_, value)| value),
+                            serializer,
+                        )?;
+
+                    Ok(TrieMapResolver {
+                        nodes_len: nodes.len(),
+                        labels_len,
+                        nodes: nodes_resolver,
+                        labels,
+                        values,
+                    })
+                },
+            )?
+        })?
+    }
+}
+
+/// A node of the trie which is being built.
+#[derive(Clone, Copy)]
+struct BuildNode {
+    /// The index of a key which passes through the node.
+    key: usize,
+    depth: usize,
+    label_len: usize,
+    parent: usize,
+    end: usize,
+    value: u32,
+}
+
+impl BuildNode {
+    fn label<'a, T>(&self, entries: &[(&'a [u8], T)]) -> &'a [u8] {
+        // The root has an empty label, even when there are no keys.
+        if self.label_len == 0 {
+            return &[];
+        }
+        &entries[self.key].0[self.depth..self.depth + self.label_len]
+    }
+}
+
+/// A span of keys which share a prefix and need a node built for them.
+struct Task {
+    start: usize,
+    end: usize,
+    depth: usize,
+    parent: usize,
+}
+
+/// Builds the nodes of the trie for `entries` in preorder.
+fn build<T>(
+    entries: &[(&[u8], T)],
+    nodes: &mut SerVec<BuildNode>,
+    tasks: &mut SerVec<Task>,
+) {
+    let has_value = entries.first().is_some_and(|(key, _)| key.is_empty());
+    nodes.push(BuildNode {
+        key: 0,
+        depth: 0,
+        label_len: 0,
+        parent: 0,
+        end: 0,
+        value: if has_value { 0 } else { NONE },
+    });
+    push_children(entries, tasks, has_value as usize, entries.len(), 0, 0);
+
+    while let Some(task) = tasks.pop() {
+        let first = entries[task.start].0;
+        let last = entries[task.end - 1].0;
+        let common = first[task.depth..]
+            .iter()
+            .zip(&last[task.depth..])
+            .take_while(|(a, b)| a == b)
+            .count();
+        let depth = task.depth + common;
+
+        // Because the keys are sorted, only the first key can end at this
+        // node.
+        let has_value = first.len() == depth;
+        let index = nodes.len();
+        nodes.push(BuildNode {
+            key: task.start,
+            depth: task.depth,
+            label_len: common,
+            parent: task.parent,
+            end: 0,
+            value: if has_value { task.start as u32 } else { NONE },
+        });
+        push_children(
+            entries,
+            tasks,
+            task.start + has_value as usize,
+            task.end,
+            depth,
+            index,
+        );
+    }
+
+    // Each node's subtree ends where the subtree of its last descendant ends.
+    for index in (0..nodes.len()).rev() {
+        let end = nodes[index].end.max(index + 1);
+        nodes[index].end = end;
+        if index != 0 {
+            let parent = nodes[index].parent;
+            nodes[parent].end = nodes[parent].end.max(end);
+        }
+    }
+}
+
+/// Groups the keys in `start..end` by their byte at `depth` and pushes a task
+/// for each group. The groups are pushed in reverse order so that they are
+/// popped in order.
+fn push_children<T>(
+    entries: &[(&[u8], T)],
+    tasks: &mut SerVec<Task>,
+    start: usize,
+    mut end: usize,
+    depth: usize,
+    parent: usize,
+) {
+    while end > start {
+        let byte = entries[end - 1].0[depth];
+        let group_start = start
+            + entries[start..end].partition_point(|(key, _)| key[depth] < byte);
+        tasks.push(Task {
+            start: group_start,
+            end,
+            depth,
+            parent,
+        });
+        end = group_start;
+    }
+}
+
+/// Writes the archived nodes of the trie.
+fn write_nodes<S>(
+    nodes: &[BuildNode],
+    serializer: &mut S,
+) -> Result<VecResolver, S::Error>
+where
+    S: Fallible + Writer + ?Sized,
+{
+    let pos = serializer.align_for::<Node>()?;
+    let mut label_start = 0;
+    for node in nodes {
+        let archived = Node {
+            label_start: ArchivedU32::from_native(label_start as u32),
+            label_len: ArchivedU32::from_native(node.label_len as u32),
+            depth: ArchivedU32::from_native(node.depth as u32),
+            end: ArchivedU32::from_native(node.end as u32),
+            value: ArchivedU32::from_native(node.value),
+        };
+        label_start += node.label_len;
+
+        // SAFETY: `Node` is `repr(C)` and only contains `ArchivedU32`s, so it
+        // has no padding bytes.
+        let bytes = unsafe {
+            slice::from_raw_parts(
+                (&archived as *const Node).cast::<u8>(),
+                size_of::<Node>(),
+            )
+        };
+        serializer.write(bytes)?;
+    }
+
+    Ok(VecResolver::from_pos(pos))
+}
+
+/// Writes the labels of the trie's nodes, in the same order as the nodes.
+fn write_labels<T, S>(
+    entries: &[(&[u8], T)],
+    nodes: &[BuildNode],
+    serializer: &mut S,
+) -> Result<VecResolver, S::Error>
+where
+    S: Fallible + Writer + ?Sized,
+{
+    let pos = serializer.pos();
+    for node in nodes {
+        serializer.write(node.label(entries))?;
+    }
+    Ok(VecResolver::from_pos(pos))
+}
+
+#[derive(Debug)]
+struct TooLarge;
+
+impl fmt::Display for TooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "trie maps may contain at most 2^31 - 2 entries and 4 GiB of \
+             labels",
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TooLarge {}
+
+#[derive(Debug)]
+struct UnorderedKeys;
+
+impl fmt::Display for UnorderedKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "trie map keys must be unique and yielded in ascending order",
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnorderedKeys {}
+
+#[cfg(feature = "alloc")]
+impl<V: fmt::Debug> fmt::Debug for ArchivedTrieMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V: PartialEq> PartialEq for ArchivedTrieMap<V> {
+    fn eq(&self, other: &Self) -> bool {
+        // Tries with the same entries always have the same structure.
+        self.nodes.as_slice() == other.nodes.as_slice()
+            && self.labels.as_slice() == other.labels.as_slice()
+            && self.values.as_slice() == other.values.as_slice()
+    }
+}
+
+impl<V: Eq> Eq for ArchivedTrieMap<V> {}
+
+/// The resolver for archived trie maps.
+pub struct TrieMapResolver {
+    nodes_len: usize,
+    labels_len: usize,
+    nodes: VecResolver,
+    labels: VecResolver,
+    values: VecResolver,
+}
+
+/// An iterator over the entries of an archived trie map, sorted by key.
+///
+/// Keys are not stored contiguously in a trie, so the iterator rebuilds each
+/// key as it goes.
+#[cfg(feature = "alloc")]
+pub struct Iter<'a, V> {
+    trie: &'a ArchivedTrieMap<V>,
+    next: usize,
+    end: usize,
+    key: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, V> Iter<'a, V> {
+    fn new(trie: &'a ArchivedTrieMap<V>, start: usize, end: usize) -> Self {
+        // Start with the key of the parent of the first node.
+        let mut key = Vec::new();
+        if start < end {
+            let mut index = 0;
+            while let Some(child) = trie
+                .children(index)
+                .find(|&child| child < start && start < trie.end(child))
+            {
+                key.extend_from_slice(trie.label(child));
+                index = child;
+            }
+        }
+
+        Self {
+            trie,
+            next: start,
+            end,
+            key,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.end {
+            let index = self.next;
+            self.next += 1;
+
+            let node = &self.trie.nodes[index];
+            self.key.truncate(node.depth.to_native() as usize);
+            self.key.extend_from_slice(self.trie.label(index));
+
+            let value = node.value.to_native();
+            if value != NONE {
+                let key = String::from_utf8_lossy(&self.key).into_owned();
+                return Some((key, &self.trie.values[value as usize]));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<V> FusedIterator for Iter<'_, V> {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::fmt;
+
+    use bytecheck::Verify;
+    use rancor::{fail, Fallible, Source};
+
+    use super::{ArchivedTrieMap, NONE};
+
+    #[derive(Debug)]
+    struct MissingRoot;
+
+    impl fmt::Display for MissingRoot {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "trie map must have a root node")
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for MissingRoot {}
+
+    #[derive(Debug)]
+    struct InvalidNode {
+        index: usize,
+    }
+
+    impl fmt::Display for InvalidNode {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "trie node at index {} has an out-of-bounds label, subtree, \
+                 or value",
+                self.index,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for InvalidNode {}
+
+    unsafe impl<C, V> Verify<C> for ArchivedTrieMap<V>
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            if self.nodes.is_empty() {
+                fail!(MissingRoot);
+            }
+
+            let nodes_len = self.nodes.len();
+            let labels_len = self.labels.len();
+            let values_len = self.values.len();
+            for (index, node) in self.nodes.iter().enumerate() {
+                let label_start = node.label_start.to_native() as usize;
+                let label_len = node.label_len.to_native() as usize;
+                let end = node.end.to_native() as usize;
+                let value = node.value.to_native();
+
+                let label_in_bounds = label_start
+                    .checked_add(label_len)
+                    .is_some_and(|label_end| label_end <= labels_len);
+                // Every node except the root adds at least one byte to its
+                // parent's key.
+                let label_valid = (index == 0) == (label_len == 0);
+                let end_valid = index < end && end <= nodes_len;
+                let value_valid =
+                    value == NONE || (value as usize) < values_len;
+                if !label_in_bounds
+                    || !label_valid
+                    || !end_valid
+                    || !value_valid
+                {
+                    fail!(InvalidNode { index });
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Utilities for archived collections.
 
-use core::fmt;
+use core::{fmt, ops::Bound};
 
 use munge::munge;
 use rancor::Fallible;
@@ -56,6 +56,7 @@ where
 #[archive(crate)]
 #[repr(C)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 pub struct Entry<K, V> {
     /// The entry's key.
     pub key: K,
@@ -63,6 +64,24 @@ pub struct Entry<K, V> {
     pub value: V,
 }
 
+/// Returns whether `key` is below the start `bound` of a range.
+pub(crate) fn is_below<Q: Ord + ?Sized>(key: &Q, bound: Bound<&Q>) -> bool {
+    match bound {
+        Bound::Included(start) => key < start,
+        Bound::Excluded(start) => key <= start,
+        Bound::Unbounded => false,
+    }
+}
+
+/// Returns whether `key` is above the end `bound` of a range.
+pub(crate) fn is_above<Q: Ord + ?Sized>(key: &Q, bound: Bound<&Q>) -> bool {
+    match bound {
+        Bound::Included(end) => key > end,
+        Bound::Excluded(end) => key >= end,
+        Bound::Unbounded => false,
+    }
+}
+
 /// An error describing that an iterator's length did not match the number of
 /// elements it yielded.
 #[derive(Debug)]
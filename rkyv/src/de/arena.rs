@@ -0,0 +1,197 @@
+//! Deserializing into memory allocated from an arena.
+
+use core::{alloc::Layout, ptr::NonNull};
+
+#[cfg(feature = "alloc")]
+use hashbrown::hash_map::HashMap;
+use rancor::{Fallible, Strategy};
+
+use crate::de::{ErasedPtr, Pooling};
+
+/// A deserializer that can allocate memory from an arena.
+///
+/// This is the deserialization counterpart of
+/// [`Allocator`](crate::ser::Allocator). Memory allocated from the arena is
+/// valid for `'a`, which allows archived values to be deserialized into arena
+/// references like `&'a T`, `&'a [T]`, and `&'a str`. Arena-allocated values
+/// are never dropped, so types with nontrivial `Drop` implementations will leak
+/// their resources.
+///
+/// The standard library's `Box`, `Vec`, `String`, `Rc`, and `Arc` always use
+/// the global allocator on stable Rust, so they cannot be deserialized into an
+/// arena. Instead, archived boxes, vecs, and strings can be deserialized into
+/// arena references, and archived `Rc`s and `Arc`s can be deserialized into
+/// arena references which are shared between every pointer to the same value
+/// with [`ArenaSharing`]. With the `bumpalo` feature enabled, archived values
+/// can also be deserialized into `bumpalo`'s `Box`, `Vec`, and `String`.
+///
+/// # Safety
+///
+/// `alloc_arena` must return a pointer to unaliased memory which fits the
+/// provided layout and remains valid for `'a`.
+pub unsafe trait ArenaAllocator<'a, E = <Self as Fallible>::Error> {
+    /// Allocates memory for the given layout from the arena.
+    ///
+    /// # Safety
+    ///
+    /// `layout` must have non-zero size.
+    unsafe fn alloc_arena(&mut self, layout: Layout) -> Result<NonNull<u8>, E>;
+}
+
+unsafe impl<'a, T, E> ArenaAllocator<'a, E> for Strategy<T, E>
+where
+    T: ArenaAllocator<'a, E> + ?Sized,
+{
+    unsafe fn alloc_arena(&mut self, layout: Layout) -> Result<NonNull<u8>, E> {
+        // SAFETY: The safety requirements for `alloc_arena()` are the same as
+        // the requirements for `T::alloc_arena`.
+        unsafe { T::alloc_arena(self, layout) }
+    }
+}
+
+/// A deserializer that can share values allocated from an arena.
+///
+/// This trait is required to deserialize `Rc` and `Arc` into arena references.
+/// It keeps its own registry of shared values, separate from [`Pooling`], so
+/// that values allocated from the arena are never confused with values owned by
+/// a real `Rc` or `Arc`.
+pub trait ArenaSharing<'a, E = <Self as Fallible>::Error>:
+    ArenaAllocator<'a, E>
+{
+    /// Gets the pointer to a previously-deserialized shared value.
+    fn get_arena_shared(&mut self, address: usize) -> Option<ErasedPtr>;
+
+    /// Adds the pointer to a deserialized shared value to the registry.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated from the arena and must point to a
+    /// valid, initialized value.
+    unsafe fn add_arena_shared(
+        &mut self,
+        address: usize,
+        ptr: ErasedPtr,
+    ) -> Result<(), E>;
+}
+
+impl<'a, T, E> ArenaSharing<'a, E> for Strategy<T, E>
+where
+    T: ArenaSharing<'a, E> + ?Sized,
+{
+    fn get_arena_shared(&mut self, address: usize) -> Option<ErasedPtr> {
+        T::get_arena_shared(self, address)
+    }
+
+    unsafe fn add_arena_shared(
+        &mut self,
+        address: usize,
+        ptr: ErasedPtr,
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `add_arena_shared()` are the
+        // same as the requirements for `T::add_arena_shared`.
+        unsafe { T::add_arena_shared(self, address, ptr) }
+    }
+}
+
+/// A deserializer that can provide a `bumpalo` arena.
+///
+/// This trait is required to deserialize `bumpalo`'s collections.
+#[cfg(feature = "bumpalo")]
+pub trait BumpArena<'a> {
+    /// Returns the arena to allocate in.
+    fn bump(&self) -> &'a bumpalo::Bump;
+}
+
+#[cfg(feature = "bumpalo")]
+impl<'a, T, E> BumpArena<'a> for Strategy<T, E>
+where
+    T: BumpArena<'a> + ?Sized,
+{
+    fn bump(&self) -> &'a bumpalo::Bump {
+        T::bump(self)
+    }
+}
+
+/// A deserializer adapter that adds an arena to another deserializer.
+///
+/// The arena is used to satisfy [`ArenaAllocator`] and all other capabilities
+/// are forwarded to the inner deserializer. With the `alloc` feature enabled,
+/// it also keeps a registry of shared values to satisfy [`ArenaSharing`].
+#[derive(Debug, Default)]
+pub struct WithArena<A, D> {
+    arena: A,
+    inner: D,
+    #[cfg(feature = "alloc")]
+    shared: HashMap<usize, ErasedPtr>,
+}
+
+impl<A, D> WithArena<A, D> {
+    /// Creates a new deserializer from an arena and an inner deserializer.
+    pub fn new(arena: A, inner: D) -> Self {
+        Self {
+            arena,
+            inner,
+            #[cfg(feature = "alloc")]
+            shared: HashMap::new(),
+        }
+    }
+
+    /// Consumes the adapter and returns the arena and the inner deserializer.
+    pub fn into_parts(self) -> (A, D) {
+        (self.arena, self.inner)
+    }
+}
+
+unsafe impl<'a, A, D, E> ArenaAllocator<'a, E> for WithArena<A, D>
+where
+    A: ArenaAllocator<'a, E>,
+{
+    unsafe fn alloc_arena(&mut self, layout: Layout) -> Result<NonNull<u8>, E> {
+        // SAFETY: The safety requirements for `alloc_arena()` are the same as
+        // the requirements for `A::alloc_arena`.
+        unsafe { self.arena.alloc_arena(layout) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, A, D, E> ArenaSharing<'a, E> for WithArena<A, D>
+where
+    A: ArenaAllocator<'a, E>,
+{
+    fn get_arena_shared(&mut self, address: usize) -> Option<ErasedPtr> {
+        self.shared.get(&address).copied()
+    }
+
+    unsafe fn add_arena_shared(
+        &mut self,
+        address: usize,
+        ptr: ErasedPtr,
+    ) -> Result<(), E> {
+        self.shared.insert(address, ptr);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bumpalo")]
+impl<'a, A: BumpArena<'a>, D> BumpArena<'a> for WithArena<A, D> {
+    fn bump(&self) -> &'a bumpalo::Bump {
+        self.arena.bump()
+    }
+}
+
+impl<A, D: Pooling<E>, E> Pooling<E> for WithArena<A, D> {
+    fn get_shared_ptr(&mut self, address: usize) -> Option<ErasedPtr> {
+        self.inner.get_shared_ptr(address)
+    }
+
+    unsafe fn add_shared_ptr(
+        &mut self,
+        address: usize,
+        ptr: ErasedPtr,
+        drop: unsafe fn(ErasedPtr),
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `add_shared_ptr` are the same as
+        // the requirements for calling this function.
+        unsafe { self.inner.add_shared_ptr(address, ptr, drop) }
+    }
+}
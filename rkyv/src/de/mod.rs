@@ -1,11 +1,12 @@
 //! Deserialization traits, deserializers, and adapters.
 
+pub mod arena;
 pub mod pooling;
 
 use rancor::Strategy;
 
 #[doc(inline)]
-pub use self::pooling::*;
+pub use self::{arena::*, pooling::*};
 
 /// A deserializer suitable for environments where allocations cannot be made.
 pub type CoreDeserializer<E> = Strategy<Unpool, E>;
@@ -102,7 +102,7 @@ impl ErasedPtr {
     ///
     /// `self` must be created from a valid pointer to `T`.
     #[inline]
-    unsafe fn downcast_unchecked<T>(&self) -> *mut T
+    pub(crate) unsafe fn downcast_unchecked<T>(&self) -> *mut T
     where
         T: Pointee + ?Sized,
         Metadata: Into<T::Metadata>,
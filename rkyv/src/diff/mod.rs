@@ -0,0 +1,44 @@
+//! Diffing and patching archives.
+//!
+//! # Structural diffs
+//!
+//! [`Diff`] compares two archived values field by field and produces a
+//! [`Change`] describing only the parts that differ. Structs and enums compare
+//! their fields, sequences compare their elements, and maps compare the values
+//! of their entries by key. Values which can't be compared any further are
+//! replaced wholesale, and only those replaced values are stored in the change
+//! as archives of their own.
+//!
+//! Changes are themselves archivable, so the patch format is an rkyv archive.
+//! [`diff`] produces the bytes of an archived change between two archives of
+//! the same type, and [`apply`] applies those bytes to the old archive to
+//! produce an archive of the new value. `Diff` can be derived for structs and
+//! enums with `#[derive(Diff)]`.
+//!
+//! # Byte-level patches
+//!
+//! A [`Patch`] describes how to construct a new archive from an old one
+//! without knowing its type. It is made of a sequence of [`PatchOp`]s that
+//! either copy a range of bytes from the old archive or insert new bytes.
+//! Because rkyv lays out values deterministically, subtrees which did not
+//! change between the two values are archived as identical runs of bytes and
+//! are encoded as cheap copies. Only the changed values and the pointers
+//! leading to them end up in the patch.
+
+mod patch;
+#[cfg(feature = "bytecheck")]
+mod structural;
+
+#[cfg(feature = "bytecheck")]
+pub use rkyv_derive::Diff;
+
+pub use self::patch::{
+    ArchivedPatch, ArchivedPatchOp, Patch, PatchOp, PatchOpResolver,
+    PatchResolver,
+};
+#[cfg(feature = "bytecheck")]
+pub use self::structural::{
+    apply, diff, invalid_change, replace, replaced, unchanged, ArchivedChange,
+    ArchivedFieldChange, Change, ChangeResolver, Diff, FieldChange,
+    FieldChangeResolver, FieldChanges, FieldPatches,
+};
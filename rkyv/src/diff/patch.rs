@@ -0,0 +1,370 @@
+//! Byte-level patches between archives.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    hash::{BuildHasherDefault, Hasher as _},
+};
+#[cfg(feature = "std")]
+use std::collections::hash_map;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::hash_map;
+use rancor::{fail, Source};
+
+use crate::{hash::FxHasher64, util::AlignedVec, Archive, Serialize};
+
+/// The size of the blocks used to find matching runs of bytes.
+const BLOCK_SIZE: usize = 16;
+
+/// A single operation of a [`Patch`].
+#[derive(Archive, Serialize, Debug, PartialEq, Eq)]
+#[archive(crate)]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(feature = "bytecheck", archive_attr(derive(bytecheck::CheckBytes)))]
+pub enum PatchOp {
+    /// Copies `len` bytes starting at `start` from the old archive.
+    Copy {
+        /// The position of the first byte to copy.
+        start: usize,
+        /// The number of bytes to copy.
+        len: usize,
+    },
+    /// Inserts the given bytes.
+    Insert(Vec<u8>),
+}
+
+/// A patch which transforms one archive into another.
+///
+/// # Examples
+/// ```
+/// use rkyv::{diff::Patch, rancor::Error, to_bytes};
+///
+/// let old = to_bytes::<Error>(&vec![1u32; 64]).unwrap();
+/// let mut value = vec![1u32; 64];
+/// value[10] = 2;
+/// let new = to_bytes::<Error>(&value).unwrap();
+///
+/// let patch = Patch::new(&old, &new);
+/// assert!(patch.inserted_len() < new.len());
+///
+/// let patch_bytes = to_bytes::<Error>(&patch).unwrap();
+/// let archived = rkyv::access::<rkyv::Archived<Patch>, Error>(&patch_bytes)
+///     .unwrap();
+/// assert_eq!(archived.apply::<Error>(&old).unwrap().as_slice(), &*new);
+/// ```
+#[derive(Archive, Serialize, Debug, PartialEq, Eq)]
+#[archive(crate)]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(feature = "bytecheck", archive_attr(derive(bytecheck::CheckBytes)))]
+pub struct Patch {
+    old_len: usize,
+    old_hash: u64,
+    new_len: usize,
+    ops: Vec<PatchOp>,
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = FxHasher64::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// A polynomial rolling hash over a window of `BLOCK_SIZE` bytes.
+struct RollingHash {
+    hash: u64,
+    /// `BASE` raised to the power of `BLOCK_SIZE - 1`.
+    top: u64,
+}
+
+impl RollingHash {
+    const BASE: u64 = 0x100_0000_01b3;
+
+    fn new(block: &[u8]) -> Self {
+        let mut hash = 0u64;
+        let mut top = 1u64;
+        for (i, &b) in block.iter().enumerate() {
+            hash = hash.wrapping_mul(Self::BASE).wrapping_add(b as u64);
+            if i != 0 {
+                top = top.wrapping_mul(Self::BASE);
+            }
+        }
+        Self { hash, top }
+    }
+
+    fn roll(&mut self, out: u8, in_: u8) {
+        self.hash = self
+            .hash
+            .wrapping_sub((out as u64).wrapping_mul(self.top))
+            .wrapping_mul(Self::BASE)
+            .wrapping_add(in_ as u64);
+    }
+}
+
+impl Patch {
+    /// Computes a patch which transforms `old` into `new`.
+    ///
+    /// This operates on the raw bytes of the archives and does not check that
+    /// they are valid.
+    pub fn new(old: &[u8], new: &[u8]) -> Self {
+        let mut ops = Vec::new();
+
+        let mut index = hash_map::HashMap::<
+            u64,
+            usize,
+            BuildHasherDefault<FxHasher64>,
+        >::default();
+        if old.len() >= BLOCK_SIZE {
+            for start in (0..=old.len() - BLOCK_SIZE).step_by(BLOCK_SIZE) {
+                let hash = RollingHash::new(&old[start..start + BLOCK_SIZE]);
+                index.entry(hash.hash).or_insert(start);
+            }
+        }
+
+        let mut literal_start = 0;
+        let mut i = 0;
+        let mut rolling = None;
+        while i + BLOCK_SIZE <= new.len() {
+            let hash = rolling.get_or_insert_with(|| {
+                RollingHash::new(&new[i..i + BLOCK_SIZE])
+            });
+
+            let found = index.get(&hash.hash).copied().filter(|&start| {
+                old[start..start + BLOCK_SIZE] == new[i..i + BLOCK_SIZE]
+            });
+            if let Some(mut start) = found {
+                // Extend the match backwards into the pending literal bytes
+                let mut begin = i;
+                while start > 0
+                    && begin > literal_start
+                    && old[start - 1] == new[begin - 1]
+                {
+                    start -= 1;
+                    begin -= 1;
+                }
+                // And forwards as far as possible
+                let mut len = i + BLOCK_SIZE - begin;
+                while start + len < old.len()
+                    && begin + len < new.len()
+                    && old[start + len] == new[begin + len]
+                {
+                    len += 1;
+                }
+
+                Self::push_insert(&mut ops, &new[literal_start..begin]);
+                Self::push_copy(&mut ops, start, len);
+                i = begin + len;
+                literal_start = i;
+                rolling = None;
+            } else {
+                if i + BLOCK_SIZE < new.len() {
+                    hash.roll(new[i], new[i + BLOCK_SIZE]);
+                }
+                i += 1;
+            }
+        }
+        Self::push_insert(&mut ops, &new[literal_start..]);
+
+        Self {
+            old_len: old.len(),
+            old_hash: hash_bytes(old),
+            new_len: new.len(),
+            ops,
+        }
+    }
+
+    fn push_insert(ops: &mut Vec<PatchOp>, bytes: &[u8]) {
+        if !bytes.is_empty() {
+            ops.push(PatchOp::Insert(bytes.to_vec()));
+        }
+    }
+
+    fn push_copy(ops: &mut Vec<PatchOp>, start: usize, len: usize) {
+        if let Some(PatchOp::Copy {
+            start: prev_start,
+            len: prev_len,
+        }) = ops.last_mut()
+        {
+            if *prev_start + *prev_len == start {
+                *prev_len += len;
+                return;
+            }
+        }
+        ops.push(PatchOp::Copy { start, len });
+    }
+
+    /// Returns the operations of the patch.
+    pub fn ops(&self) -> &[PatchOp] {
+        &self.ops
+    }
+
+    /// Returns the total number of bytes inserted by the patch.
+    pub fn inserted_len(&self) -> usize {
+        self.ops
+            .iter()
+            .map(|op| match op {
+                PatchOp::Copy { .. } => 0,
+                PatchOp::Insert(bytes) => bytes.len(),
+            })
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+enum PatchError {
+    BaseMismatch,
+    CopyOutOfBounds { start: usize, len: usize },
+    LengthMismatch { expected: usize, actual: usize },
+    TooLong,
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::BaseMismatch => {
+                write!(f, "patch does not apply to the given archive")
+            }
+            PatchError::CopyOutOfBounds { start, len } => write!(
+                f,
+                "patch copies {} bytes from position {} which is out of bounds",
+                len, start,
+            ),
+            PatchError::LengthMismatch { expected, actual } => write!(
+                f,
+                "patched archive has length {} but expected {}",
+                actual, expected,
+            ),
+            PatchError::TooLong => {
+                write!(f, "patched archive is longer than the address space")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PatchError {}
+
+impl ArchivedPatch {
+    /// Returns the operations of the patch.
+    pub fn ops(&self) -> &[ArchivedPatchOp] {
+        &self.ops
+    }
+
+    /// Applies the patch to `old` and returns the bytes of the new archive.
+    ///
+    /// Returns an error if `old` is not the archive that the patch was created
+    /// from. The returned bytes are not checked for validity.
+    pub fn apply<E: Source>(&self, old: &[u8]) -> Result<AlignedVec, E> {
+        if old.len() != self.old_len.to_native() as usize
+            || hash_bytes(old) != self.old_hash.to_native()
+        {
+            fail!(PatchError::BaseMismatch);
+        }
+
+        // The length of the new archive is only trusted once it matches the
+        // bytes actually copied from `old` and inserted by the patch.
+        let mut len = 0usize;
+        for op in self.ops.iter() {
+            let op_len = match op {
+                ArchivedPatchOp::Copy { start, len } => {
+                    let start = start.to_native() as usize;
+                    let len = len.to_native() as usize;
+                    let end = start.checked_add(len);
+                    if end.map_or(true, |end| end > old.len()) {
+                        fail!(PatchError::CopyOutOfBounds { start, len });
+                    }
+                    len
+                }
+                ArchivedPatchOp::Insert(bytes) => bytes.len(),
+            };
+            len = match len.checked_add(op_len) {
+                Some(len) => len,
+                None => fail!(PatchError::TooLong),
+            };
+        }
+        let new_len = self.new_len.to_native() as usize;
+        if len != new_len {
+            fail!(PatchError::LengthMismatch {
+                expected: new_len,
+                actual: len,
+            });
+        }
+
+        let mut result = AlignedVec::with_capacity(new_len);
+        for op in self.ops.iter() {
+            match op {
+                ArchivedPatchOp::Copy { start, len } => {
+                    let start = start.to_native() as usize;
+                    let len = len.to_native() as usize;
+                    result.extend_from_slice(&old[start..start + len]);
+                }
+                ArchivedPatchOp::Insert(bytes) => {
+                    result.extend_from_slice(bytes)
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::String, vec::Vec};
+
+    use rancor::{Failure, Panic};
+
+    use super::Patch;
+    use crate::{access, to_bytes, util::AlignedVec, Archived};
+
+    fn apply(patch: &Patch, old: &[u8]) -> Result<AlignedVec, Failure> {
+        let bytes = to_bytes::<Panic>(patch).unwrap();
+        access::<Archived<Patch>, Panic>(&bytes).unwrap().apply(old)
+    }
+
+    #[test]
+    fn roundtrip_patches() {
+        let old_value = (0..200u32)
+            .map(|i| (i, format!("item {i}")))
+            .collect::<Vec<(u32, String)>>();
+        let mut new_value = old_value.clone();
+        new_value[17].1.push_str(" changed");
+        new_value.insert(120, (1000, "inserted".into()));
+        new_value.pop();
+
+        let old = to_bytes::<Panic>(&old_value).unwrap();
+        let new = to_bytes::<Panic>(&new_value).unwrap();
+
+        let patch = Patch::new(&old, &new);
+        assert!(patch.inserted_len() < new.len() / 2);
+        assert_eq!(apply(&patch, &old).unwrap().as_slice(), new.as_slice());
+
+        // Patches only apply to the archive they were created from
+        assert!(apply(&patch, &new).is_err());
+
+        // Degenerate inputs
+        for (a, b) in [(&[][..], &new[..]), (&old[..], &[][..]), (&[], &[])] {
+            assert_eq!(apply(&Patch::new(a, b), a).unwrap().as_slice(), b);
+        }
+
+        // Copies may repeat parts of the old archive
+        let repeated = [&old[..], &old[..]].concat();
+        let patch = Patch::new(&old, &repeated);
+        assert_eq!(patch.inserted_len(), 0);
+        assert_eq!(apply(&patch, &old).unwrap().as_slice(), repeated);
+    }
+
+    #[test]
+    fn untrusted_length() {
+        let old = to_bytes::<Panic>(&(0..100u32).collect::<Vec<_>>()).unwrap();
+        let new = to_bytes::<Panic>(&(0..120u32).collect::<Vec<_>>()).unwrap();
+
+        // The new length must match the bytes that the patch actually writes
+        let mut patch = Patch::new(&old, &new);
+        patch.new_len = u32::MAX as usize;
+        assert!(apply(&patch, &old).is_err());
+        patch.new_len = new.len() - 1;
+        assert!(apply(&patch, &old).is_err());
+    }
+}
@@ -0,0 +1,461 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use bytecheck::CheckBytes;
+use rancor::{fail, Source, Strategy};
+
+use crate::{
+    access,
+    de::Pool,
+    ser::{Allocator, DefaultSerializer, Writer},
+    util::AlignedVec,
+    validation::validators::DefaultValidator,
+    vec::ArchivedVec,
+    Archive, Archived, Deserialize, Serialize,
+};
+
+/// A change which transforms one archived value into another.
+#[derive(Archive, Serialize, Debug, PartialEq, Eq)]
+#[archive(
+    crate,
+    serialize_bounds(__S: Allocator + Writer),
+)]
+#[archive_attr(
+    derive(Debug, bytecheck::CheckBytes),
+    check_bytes(bounds(
+        __C: crate::validation::ArchiveContext,
+        <__C as rancor::Fallible>::Error: Source,
+    )),
+)]
+pub enum Change {
+    /// Replaces the value with a new one.
+    ///
+    /// Contains the bytes of an archive of the new value.
+    Replace(Vec<u8>),
+    /// Changes some of the fields of a struct or enum variant, or the value of
+    /// an `Option` or `Box`.
+    Fields(
+        #[omit_bounds]
+        #[archive_attr(omit_bounds)]
+        Vec<FieldChange>,
+    ),
+    /// Changes the length and some of the elements of a sequence.
+    ///
+    /// Elements past the end of the old sequence are always replaced.
+    Elements {
+        /// The length of the new sequence.
+        len: usize,
+        /// The changed elements of the sequence.
+        #[omit_bounds]
+        #[archive_attr(omit_bounds)]
+        changes: Vec<FieldChange>,
+    },
+    /// Removes, changes, and inserts entries of a map.
+    ///
+    /// Entries of the old map are identified by their position in its
+    /// iteration order.
+    Entries {
+        /// The positions of the removed entries.
+        removed: Vec<usize>,
+        /// The changed values of the remaining entries.
+        #[omit_bounds]
+        #[archive_attr(omit_bounds)]
+        changed: Vec<FieldChange>,
+        /// The bytes of an archive of the inserted entries.
+        inserted: Vec<u8>,
+    },
+}
+
+/// A change to the field or element at an index.
+#[derive(Archive, Serialize, Debug, PartialEq, Eq)]
+#[archive(crate)]
+#[archive_attr(derive(Debug, bytecheck::CheckBytes))]
+pub struct FieldChange {
+    /// The index of the changed field or element.
+    pub index: usize,
+    /// The change to the field or element.
+    pub change: Change,
+}
+
+/// A type which can be diffed and patched structurally.
+///
+/// Diffs are computed between two archived values. Patching an archived value
+/// produces a new unarchived value, which can then be serialized to get the
+/// bytes of the new archive.
+///
+/// Values are diffed and patched with the default serializer, validator, and
+/// deserializer. Wholly replaced values are deserialized and serialized again,
+/// so a patched archive deserializes to a value equal to the one the new
+/// archive deserializes to. The bytes of the archives are only equal if the
+/// type archives deterministically: a `HashMap`, for example, is rebuilt with
+/// a new hasher and may lay out its entries differently.
+///
+/// This trait can be derived for structs and enums with `#[derive(Diff)]`. All
+/// of the fields of the type must implement `Diff`.
+pub trait Diff<E: Source>:
+    Archive<
+        Archived: Deserialize<Self, Strategy<Pool, E>>
+                      + for<'a> CheckBytes<Strategy<DefaultValidator<'a>, E>>,
+    > + for<'a> Serialize<DefaultSerializer<'a, AlignedVec, E>>
+{
+    /// Returns the change which transforms `old` into `new`, or `None` if they
+    /// are equal.
+    fn diff(
+        old: &Self::Archived,
+        new: &Self::Archived,
+    ) -> Result<Option<Change>, E>;
+
+    /// Applies `change` to `old` and returns the new value.
+    fn patch(old: &Self::Archived, change: &ArchivedChange) -> Result<Self, E>;
+}
+
+/// Returns a change which replaces a value with `new`.
+pub fn replace<T: Diff<E>, E: Source>(new: &T::Archived) -> Result<Change, E> {
+    let value = unchanged::<T, E>(new)?;
+    Ok(Change::Replace(crate::to_bytes::<E>(&value)?.to_vec()))
+}
+
+/// Returns the value replaced by the archive in `bytes`.
+///
+/// The archive is checked for validity before deserializing it.
+pub fn replaced<T: Diff<E>, E: Source>(bytes: &[u8]) -> Result<T, E> {
+    // Archived bytes in a change are not aligned.
+    let mut aligned = AlignedVec::with_capacity(bytes.len());
+    aligned.extend_from_slice(bytes);
+    crate::from_bytes::<T, E>(&aligned)
+}
+
+/// Deserializes an archived value which was not changed.
+pub fn unchanged<T: Diff<E>, E: Source>(old: &T::Archived) -> Result<T, E> {
+    crate::deserialize::<T, _, E>(old, &mut Pool::new())
+}
+
+/// Returns an error indicating that a change does not apply to the kind of
+/// value being patched.
+pub fn invalid_change<T, E: Source>() -> Result<T, E> {
+    fail!(ChangeError::Mismatch)
+}
+
+/// Collects changes to the fields or elements of a value.
+#[derive(Debug, Default)]
+pub struct FieldChanges {
+    changes: Vec<FieldChange>,
+}
+
+impl FieldChanges {
+    /// Returns a new set of changes with no changed fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the change between `old` and `new` for the field at `index`, if
+    /// they are different.
+    ///
+    /// Fields must be added in increasing index order.
+    pub fn push<T: Diff<E>, E: Source>(
+        &mut self,
+        index: usize,
+        old: &T::Archived,
+        new: &T::Archived,
+    ) -> Result<(), E> {
+        if let Some(change) = T::diff(old, new)? {
+            self.changes.push(FieldChange { index, change });
+        }
+        Ok(())
+    }
+
+    /// Adds a change to the field at `index`.
+    ///
+    /// Fields must be added in increasing index order.
+    pub fn push_change(&mut self, index: usize, change: Change) {
+        self.changes.push(FieldChange { index, change });
+    }
+
+    /// Returns whether no fields were changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns a [`Change::Fields`] of the changed fields, or `None` if no
+    /// fields were changed.
+    pub fn finish(self) -> Option<Change> {
+        (!self.changes.is_empty()).then_some(Change::Fields(self.changes))
+    }
+
+    /// Returns the changed fields.
+    pub fn into_vec(self) -> Vec<FieldChange> {
+        self.changes
+    }
+}
+
+/// Applies archived changes to the fields or elements of a value.
+///
+/// Fields must be patched in increasing index order. Any changes that were
+/// not applied once patching is finished are reported as an error.
+#[derive(Debug)]
+pub struct FieldPatches<'a> {
+    changes: &'a [ArchivedFieldChange],
+}
+
+impl<'a> FieldPatches<'a> {
+    /// Returns patches which apply the given field changes.
+    pub fn new(changes: &'a ArchivedVec<ArchivedFieldChange>) -> Self {
+        Self {
+            changes: changes.as_slice(),
+        }
+    }
+
+    /// Returns the change for the field at `index`, if there is one.
+    pub fn take(&mut self, index: usize) -> Option<&'a ArchivedChange> {
+        let (first, rest) = self.changes.split_first()?;
+        if first.index.to_native() as usize == index {
+            self.changes = rest;
+            Some(&first.change)
+        } else {
+            None
+        }
+    }
+
+    /// Patches the field at `index` with its change, or deserializes it if it
+    /// was not changed.
+    pub fn patch<T: Diff<E>, E: Source>(
+        &mut self,
+        index: usize,
+        old: &T::Archived,
+    ) -> Result<T, E> {
+        match self.take(index) {
+            Some(change) => T::patch(old, change),
+            None => unchanged::<T, E>(old),
+        }
+    }
+
+    /// Returns an error if any of the changes were not applied.
+    pub fn finish<E: Source>(self) -> Result<(), E> {
+        match self.changes.first() {
+            None => Ok(()),
+            Some(change) => fail!(ChangeError::UnusedChange {
+                index: change.index.to_native() as usize,
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ChangeError {
+    Mismatch,
+    UnusedChange { index: usize },
+}
+
+impl fmt::Display for ChangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeError::Mismatch => {
+                write!(f, "change does not apply to the kind of value patched")
+            }
+            ChangeError::UnusedChange { index } => write!(
+                f,
+                "change to index {} was out of order or out of bounds",
+                index,
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChangeError {}
+
+/// Computes the structural change which transforms the archive `old` into the
+/// archive `new`, and returns the bytes of the archived change.
+///
+/// Both archives are checked to be valid archives of `T` before diffing.
+///
+/// # Examples
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use rkyv::{
+///     diff::{apply, diff},
+///     rancor::Error,
+///     to_bytes,
+/// };
+///
+/// let mut config = BTreeMap::new();
+/// for i in 0..100 {
+///     config.insert(format!("key_{i}"), format!("value_{i}"));
+/// }
+/// let old = to_bytes::<Error>(&config).unwrap();
+/// config.insert("key_50".to_string(), "changed".to_string());
+/// let new = to_bytes::<Error>(&config).unwrap();
+///
+/// type Config = BTreeMap<String, String>;
+/// let patch = diff::<Config, Error>(&old, &new).unwrap();
+/// assert!(patch.len() < new.len() / 10);
+///
+/// let patched = apply::<Config, Error>(&old, &patch).unwrap();
+/// assert_eq!(patched.as_slice(), new.as_slice());
+/// ```
+pub fn diff<T: Diff<E>, E: Source>(
+    old: &[u8],
+    new: &[u8],
+) -> Result<AlignedVec, E> {
+    let old = access::<T::Archived, E>(old)?;
+    let new = access::<T::Archived, E>(new)?;
+    crate::to_bytes::<E>(&T::diff(old, new)?)
+}
+
+/// Applies an archived structural change to the archive `old` and returns the
+/// bytes of the new archive.
+///
+/// See [`Diff`] for when the result is equal to the bytes the change was
+/// computed from.
+///
+/// The old archive and the change are both checked for validity before
+/// patching.
+pub fn apply<T: Diff<E>, E: Source>(
+    old: &[u8],
+    change: &[u8],
+) -> Result<AlignedVec, E> {
+    let archived = access::<T::Archived, E>(old)?;
+    match access::<Archived<Option<Change>>, E>(change)?.as_ref() {
+        None => {
+            let mut result = AlignedVec::with_capacity(old.len());
+            result.extend_from_slice(old);
+            Ok(result)
+        }
+        Some(change) => crate::to_bytes::<E>(&T::patch(archived, change)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        collections::BTreeMap,
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    #[cfg(feature = "std")]
+    use std::collections::{BTreeMap, HashMap};
+
+    use rancor::Error;
+
+    use super::{apply, diff};
+    use crate::{
+        diff::Diff, to_bytes, Archive, Archived, Deserialize, Serialize,
+    };
+
+    #[derive(Archive, Serialize, Deserialize, Diff, Debug, PartialEq)]
+    #[archive(crate)]
+    #[archive_attr(derive(bytecheck::CheckBytes))]
+    enum Shape {
+        Point,
+        Circle { radius: u32 },
+        Label(u8, String),
+    }
+
+    #[derive(Archive, Serialize, Deserialize, Diff, Debug, PartialEq)]
+    #[archive(crate)]
+    #[archive_attr(derive(bytecheck::CheckBytes))]
+    struct Config {
+        version: u32,
+        name: String,
+        shapes: Vec<Shape>,
+        settings: BTreeMap<String, Option<u64>>,
+    }
+
+    fn config() -> Config {
+        Config {
+            version: 1,
+            name: "edge".to_string(),
+            shapes: (0..50).map(|radius| Shape::Circle { radius }).collect(),
+            settings: (0..50)
+                .map(|i| (format!("setting_{i}"), Some(i)))
+                .collect(),
+        }
+    }
+
+    fn roundtrip(old: &Config, new: &Config) -> usize {
+        let old = to_bytes::<Error>(old).unwrap();
+        let new = to_bytes::<Error>(new).unwrap();
+        let patch = diff::<Config, Error>(&old, &new).unwrap();
+        let patched = apply::<Config, Error>(&old, &patch).unwrap();
+        assert_eq!(patched.as_slice(), new.as_slice());
+        patch.len()
+    }
+
+    #[test]
+    fn derived_roundtrip() {
+        let old = config();
+
+        // Equal archives produce an empty change
+        let unchanged = roundtrip(&old, &config());
+
+        let mut new = config();
+        new.version = 2;
+        new.shapes[3] = Shape::Point;
+        new.shapes[7] = Shape::Label(7, "seven".to_string());
+        new.shapes.push(Shape::Circle { radius: 100 });
+        new.settings.remove("setting_10");
+        new.settings.insert("setting_20".to_string(), None);
+        new.settings.insert("added".to_string(), Some(1));
+        let changed = roundtrip(&old, &new);
+        assert!(unchanged < changed);
+        assert!(changed < to_bytes::<Error>(&new).unwrap().len() / 2);
+
+        // Shrinking sequences
+        let mut new = config();
+        new.shapes.truncate(10);
+        new.settings.clear();
+        roundtrip(&old, &new);
+    }
+
+    #[test]
+    fn mismatched_change() {
+        let old = to_bytes::<Error>(&config()).unwrap();
+        let mut new = config();
+        new.name = "changed".to_string();
+        let new = to_bytes::<Error>(&new).unwrap();
+        let patch = diff::<Config, Error>(&old, &new).unwrap();
+
+        // A change to a struct can't be applied to a different type
+        let other = to_bytes::<Error>(&vec![0u32; 4]).unwrap();
+        assert!(apply::<Vec<u32>, Error>(&other, &patch).is_err());
+
+        // Field changes must refer to fields which exist
+        let change = Some(super::Change::Fields(vec![super::FieldChange {
+            index: 10,
+            change: super::Change::Replace(Vec::new()),
+        }]));
+        let change = to_bytes::<Error>(&change).unwrap();
+        assert!(apply::<Config, Error>(&old, &change).is_err());
+
+        // The change is itself a valid archive
+        crate::access::<Archived<Option<super::Change>>, Error>(&patch)
+            .unwrap();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map_roundtrip() {
+        type Scores = HashMap<String, u32>;
+
+        let old = (0..50)
+            .map(|i| (format!("player_{i}"), i))
+            .collect::<Scores>();
+        let mut new = old.clone();
+        new.insert("player_10".to_string(), 100);
+        new.insert("added".to_string(), 1);
+
+        let old_bytes = to_bytes::<Error>(&old).unwrap();
+        let new_bytes = to_bytes::<Error>(&new).unwrap();
+        let patch = diff::<Scores, Error>(&old_bytes, &new_bytes).unwrap();
+        let patched = apply::<Scores, Error>(&old_bytes, &patch).unwrap();
+
+        // Hash maps are not archived deterministically, so only the values are
+        // compared.
+        assert_eq!(crate::from_bytes::<Scores, Error>(&patched).unwrap(), new);
+    }
+}
@@ -0,0 +1,393 @@
+//! Self-describing archive envelopes.
+//!
+//! By default, archives carry no information about how they were written. The
+//! root is assumed to sit at the end of the buffer, and a buffer written with a
+//! different endianness, pointer width, or alignment configuration will be
+//! silently misread.
+//!
+//! An envelope prefixes an archive with a fixed-size [`Header`] that records:
+//!
+//! - A magic number identifying the buffer as an rkyv archive
+//! - The version of the envelope format
+//! - The [`Format`] the archive was written with
+//! - The position of the root object
+//! - The total length of the archive, including the header
+//!
+//! The header is always encoded as little-endian bytes so that it can be read
+//! regardless of the format of the archive it describes. Enveloped archives can
+//! be written with [`to_bytes_enveloped`] and accessed with
+//! [`access_enveloped`], which rejects archives written with a mismatched
+//! format.
+
+use core::fmt;
+
+#[cfg(feature = "bytecheck")]
+use bytecheck::CheckBytes;
+#[cfg(feature = "bytecheck")]
+use rancor::Strategy;
+use rancor::{fail, Source};
+
+#[cfg(feature = "bytecheck")]
+use crate::validation::validators::DefaultValidator;
+use crate::{primitive::ArchivedUsize, util::access_pos_unchecked, Portable};
+#[cfg(feature = "alloc")]
+use crate::{
+    ser::{sharing::Share, DefaultSerializer, Serializer},
+    util::{serialize, with_arena, AlignedVec},
+    Serialize,
+};
+
+/// The magic number at the start of every enveloped archive.
+pub const MAGIC: [u8; 4] = *b"rkyv";
+
+/// The current version of the envelope format.
+pub const VERSION: u16 = 1;
+
+/// The size of an envelope header in bytes.
+///
+/// This is a multiple of the largest alignment of any builtin archived type, so
+/// an archive written after the header remains properly aligned.
+pub const HEADER_SIZE: usize = 32;
+
+const BIG_ENDIAN: u16 = 1 << 0;
+const UNALIGNED: u16 = 1 << 1;
+const POINTER_WIDTH_SHIFT: u32 = 2;
+const POINTER_WIDTH_MASK: u16 = 0b11 << POINTER_WIDTH_SHIFT;
+
+/// The configuration an archive was written with.
+///
+/// Archives written with one format cannot be read with a build of rkyv that
+/// uses a different format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Format {
+    bits: u16,
+}
+
+impl Format {
+    /// Returns the format used by the current build of rkyv.
+    pub const fn current() -> Self {
+        let mut bits = 0;
+        if cfg!(feature = "big_endian") {
+            bits |= BIG_ENDIAN;
+        }
+        if cfg!(feature = "unaligned") {
+            bits |= UNALIGNED;
+        }
+        let width = match core::mem::size_of::<ArchivedUsize>() {
+            2 => 0,
+            4 => 1,
+            _ => 2,
+        };
+        bits |= width << POINTER_WIDTH_SHIFT;
+        Self { bits }
+    }
+
+    /// Creates a format from its raw encoded bits.
+    pub const fn from_bits(bits: u16) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the raw encoded bits of the format.
+    pub const fn to_bits(self) -> u16 {
+        self.bits
+    }
+
+    /// Returns whether archives in this format are big-endian.
+    pub const fn is_big_endian(self) -> bool {
+        self.bits & BIG_ENDIAN != 0
+    }
+
+    /// Returns whether archives in this format are unaligned.
+    pub const fn is_unaligned(self) -> bool {
+        self.bits & UNALIGNED != 0
+    }
+
+    /// Returns the width of archived pointers and `usize`s in bits.
+    ///
+    /// Returns `None` if the encoded pointer width is invalid.
+    pub const fn pointer_width(self) -> Option<u32> {
+        match (self.bits & POINTER_WIDTH_MASK) >> POINTER_WIDTH_SHIFT {
+            0 => Some(16),
+            1 => Some(32),
+            2 => Some(64),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_big_endian() {
+            write!(f, "big-endian, ")?;
+        } else {
+            write!(f, "little-endian, ")?;
+        }
+        match self.pointer_width() {
+            Some(width) => write!(f, "{}-bit pointers, ", width)?,
+            None => write!(f, "invalid pointer width, ")?,
+        }
+        if self.is_unaligned() {
+            write!(f, "unaligned")
+        } else {
+            write!(f, "aligned")
+        }
+    }
+}
+
+/// The header of an enveloped archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// The version of the envelope format.
+    pub version: u16,
+    /// The format the archive was written with.
+    pub format: Format,
+    /// The position of the root object, relative to the start of the buffer.
+    pub root_pos: u64,
+    /// The total length of the archive in bytes, including the header.
+    pub len: u64,
+}
+
+impl Header {
+    /// Reads a header from the start of the given bytes.
+    ///
+    /// This only checks the magic number and that the buffer is long enough to
+    /// contain a header. Use [`check`](Header::check) to verify that the
+    /// archive can be read by the current build.
+    pub fn read<E: Source>(bytes: &[u8]) -> Result<Self, E> {
+        if bytes.len() < HEADER_SIZE {
+            fail!(EnvelopeError::TooShort { len: bytes.len() });
+        }
+        if bytes[0..4] != MAGIC {
+            fail!(EnvelopeError::InvalidMagic);
+        }
+
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        let u64_at = |i: usize| {
+            let mut le = [0; 8];
+            le.copy_from_slice(&bytes[i..i + 8]);
+            u64::from_le_bytes(le)
+        };
+
+        Ok(Self {
+            version: u16_at(4),
+            format: Format::from_bits(u16_at(6)),
+            root_pos: u64_at(8),
+            len: u64_at(16),
+        })
+    }
+
+    /// Writes the header to the start of the given bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is shorter than [`HEADER_SIZE`].
+    pub fn write(&self, bytes: &mut [u8]) {
+        let out = &mut bytes[..HEADER_SIZE];
+        out.fill(0);
+        out[0..4].copy_from_slice(&MAGIC);
+        out[4..6].copy_from_slice(&self.version.to_le_bytes());
+        out[6..8].copy_from_slice(&self.format.to_bits().to_le_bytes());
+        out[8..16].copy_from_slice(&self.root_pos.to_le_bytes());
+        out[16..24].copy_from_slice(&self.len.to_le_bytes());
+    }
+
+    /// Checks that an archive with this header and the given bytes can be
+    /// read by the current build, and returns the position of the root object.
+    pub fn check<E: Source>(&self, bytes: &[u8]) -> Result<usize, E> {
+        if self.version != VERSION {
+            fail!(EnvelopeError::UnsupportedVersion {
+                version: self.version,
+            });
+        }
+        let current = Format::current();
+        if self.format != current {
+            fail!(EnvelopeError::FormatMismatch {
+                expected: current,
+                found: self.format,
+            });
+        }
+        if self.len != bytes.len() as u64 {
+            fail!(EnvelopeError::LengthMismatch {
+                expected: self.len,
+                found: bytes.len(),
+            });
+        }
+        if self.root_pos < HEADER_SIZE as u64 || self.root_pos > self.len {
+            fail!(EnvelopeError::InvalidRootPosition {
+                root_pos: self.root_pos,
+                len: self.len,
+            });
+        }
+        Ok(self.root_pos as usize)
+    }
+}
+
+#[derive(Debug)]
+enum EnvelopeError {
+    TooShort { len: usize },
+    InvalidMagic,
+    UnsupportedVersion { version: u16 },
+    FormatMismatch { expected: Format, found: Format },
+    LengthMismatch { expected: u64, found: usize },
+    InvalidRootPosition { root_pos: u64, len: u64 },
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { len } => write!(
+                f,
+                "buffer of length {} is too short to contain an envelope \
+                 header of length {}",
+                len, HEADER_SIZE,
+            ),
+            Self::InvalidMagic => {
+                write!(f, "buffer does not start with the rkyv magic number")
+            }
+            Self::UnsupportedVersion { version } => write!(
+                f,
+                "unsupported envelope version {}, expected version {}",
+                version, VERSION,
+            ),
+            Self::FormatMismatch { expected, found } => write!(
+                f,
+                "archive format mismatch: archive was written as ({}) but \
+                 this build reads ({})",
+                found, expected,
+            ),
+            Self::LengthMismatch { expected, found } => write!(
+                f,
+                "archive length mismatch: header records {} bytes but buffer \
+                 has {} bytes",
+                expected, found,
+            ),
+            Self::InvalidRootPosition { root_pos, len } => write!(
+                f,
+                "invalid root position {} for archive of length {}",
+                root_pos, len,
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EnvelopeError {}
+
+/// Serializes the given value into an enveloped archive and returns the
+/// resulting bytes in an [`AlignedVec`].
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     envelope::{access_enveloped, to_bytes_enveloped, Header},
+///     rancor::Error,
+///     Archived,
+/// };
+///
+/// let value = vec![1, 2, 3, 4];
+///
+/// let bytes = to_bytes_enveloped::<Error>(&value).unwrap();
+/// let header = Header::read::<Error>(&bytes).unwrap();
+/// assert_eq!(header.len, bytes.len() as u64);
+///
+/// let archived = access_enveloped::<Archived<Vec<i32>>, Error>(&bytes).unwrap();
+/// assert_eq!(archived, &value);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_bytes_enveloped<E>(
+    value: &impl for<'a> Serialize<DefaultSerializer<'a, AlignedVec, E>>,
+) -> Result<AlignedVec, E>
+where
+    E: Source,
+{
+    with_arena(|arena| {
+        let mut writer = AlignedVec::new();
+        writer.extend_from_slice(&[0; HEADER_SIZE]);
+        let mut serializer =
+            Serializer::new(writer, arena.acquire(), Share::new());
+        let root_pos = serialize(value, &mut serializer)?;
+        let mut bytes = serializer.into_writer();
+
+        let header = Header {
+            version: VERSION,
+            format: Format::current(),
+            root_pos: root_pos as u64,
+            len: bytes.len() as u64,
+        };
+        header.write(&mut bytes);
+
+        Ok(bytes)
+    })
+}
+
+/// Accesses the root of an enveloped archive after checking its header and
+/// validating its contents.
+///
+/// Archives written with a different envelope version or [`Format`] are
+/// rejected with an error describing the mismatch.
+#[cfg(feature = "bytecheck")]
+pub fn access_enveloped<T, E>(bytes: &[u8]) -> Result<&T, E>
+where
+    T: Portable + for<'a> CheckBytes<Strategy<DefaultValidator<'a>, E>>,
+    E: Source,
+{
+    let root_pos = Header::read::<E>(bytes)?.check::<E>(bytes)?;
+    crate::validation::util::access_pos::<T, E>(bytes, root_pos)
+}
+
+/// Accesses the root of an enveloped archive after checking its header, but
+/// without validating its contents.
+///
+/// # Safety
+///
+/// The bytes after the header must represent a valid archived `T` located at
+/// the root position recorded in the header.
+pub unsafe fn access_enveloped_unchecked<T, E>(bytes: &[u8]) -> Result<&T, E>
+where
+    T: Portable,
+    E: Source,
+{
+    let root_pos = Header::read::<E>(bytes)?.check::<E>(bytes)?;
+    // SAFETY: The caller has guaranteed that a valid `T` is located at the
+    // root position.
+    Ok(unsafe { access_pos_unchecked::<T>(bytes, root_pos) })
+}
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    use rancor::{Error, Panic};
+
+    use super::{access_enveloped, to_bytes_enveloped, Format, Header};
+    use crate::Archived;
+
+    #[test]
+    fn roundtrip_enveloped() {
+        let value = vec!["hello".to_string(), "world".to_string()];
+        let bytes = to_bytes_enveloped::<Panic>(&value).unwrap();
+        let archived =
+            access_enveloped::<Archived<Vec<String>>, Panic>(&bytes).unwrap();
+        assert_eq!(archived, &value);
+    }
+
+    #[test]
+    fn rejects_mismatched_format() {
+        let mut bytes = to_bytes_enveloped::<Panic>(&42u32).unwrap();
+        let mut header = Header::read::<Panic>(&bytes).unwrap();
+        header.format = Format::from_bits(Format::current().to_bits() ^ 1);
+        header.write(&mut bytes);
+
+        assert!(access_enveloped::<Archived<u32>, Error>(&bytes).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_magic_and_length() {
+        let mut bytes = to_bytes_enveloped::<Panic>(&42u32).unwrap();
+        assert!(access_enveloped::<Archived<u32>, Error>(
+            &bytes[..bytes.len() - 4]
+        )
+        .is_err());
+
+        bytes[0] = b'x';
+        assert!(access_enveloped::<Archived<u32>, Error>(&bytes).is_err());
+    }
+}
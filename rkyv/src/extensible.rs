@@ -0,0 +1,918 @@
+//! Archived types which can change over time.
+//!
+//! # Extensible structs
+//!
+//! Deriving `Archive` with `#[archive(extensible)]` stores the fields of a
+//! struct out-of-line behind a small header containing their length and
+//! version. Fields added in later versions are marked with
+//! `#[archive(since = N)]` and must come after all of the fields from earlier
+//! versions.
+//!
+//! Readers with an older version of the struct only validate and access the
+//! fields that they know about. Readers with a newer version of the struct get
+//! `None` from the accessors of fields which are missing from older archives,
+//! and deserialize those fields with their `Default` values.
+//!
+//! ```
+//! use rkyv::{rancor::Error, Archive, Deserialize, Serialize};
+//!
+//! mod v1 {
+//!     use rkyv::{bytecheck::CheckBytes, Archive, Deserialize, Serialize};
+//!
+//!     #[derive(Archive, Serialize, Deserialize)]
+//!     #[archive(extensible)]
+//!     #[archive_attr(derive(CheckBytes))]
+//!     pub struct Config {
+//!         pub name: String,
+//!     }
+//! }
+//!
+//! mod v2 {
+//!     use rkyv::{bytecheck::CheckBytes, Archive, Deserialize, Serialize};
+//!
+//!     #[derive(Archive, Serialize, Deserialize)]
+//!     #[archive(extensible)]
+//!     #[archive_attr(derive(CheckBytes))]
+//!     pub struct Config {
+//!         pub name: String,
+//!         #[archive(since = 1)]
+//!         pub retries: u32,
+//!     }
+//! }
+//!
+//! let old_bytes = rkyv::to_bytes::<Error>(&v1::Config {
+//!     name: "old".to_string(),
+//! })
+//! .unwrap();
+//! let new_bytes = rkyv::to_bytes::<Error>(&v2::Config {
+//!     name: "new".to_string(),
+//!     retries: 3,
+//! })
+//! .unwrap();
+//!
+//! // Old readers can access the fields they know about in newer archives.
+//! let archived =
+//!     rkyv::access::<v1::ArchivedConfig, Error>(&new_bytes).unwrap();
+//! assert_eq!(archived.version(), 1);
+//! assert_eq!(archived.name(), "new");
+//!
+//! // New readers see missing fields in older archives as `None`, and
+//! // deserialize them with their default values.
+//! let archived =
+//!     rkyv::access::<v2::ArchivedConfig, Error>(&old_bytes).unwrap();
+//! assert_eq!(archived.name(), "old");
+//! assert!(archived.retries().is_none());
+//! let config = rkyv::from_bytes::<v2::Config, Error>(&old_bytes).unwrap();
+//! assert_eq!(config.retries, 0);
+//! ```
+//!
+//! # Open enums
+//!
+//! Deriving `Archive` with `#[archive(open)]` stores the fields of each variant
+//! of an enum out-of-line behind a header containing the tag of the variant and
+//! the length of its fields. Readers skip over variants with unknown tags
+//! instead of failing validation, and see them as an extra `Unknown(tag)`
+//! variant. Alternatively, a unit variant can be marked with
+//! `#[archive(unknown)]` to receive unknown variants instead, including when
+//! deserializing. New variants must be added after existing variants, or be
+//! given explicit discriminants, so that the tags of existing variants don't
+//! change.
+//!
+//! ```
+//! use rkyv::{rancor::Error, Archive, Deserialize, Serialize};
+//!
+//! mod v1 {
+//!     use rkyv::{bytecheck::CheckBytes, Archive, Deserialize, Serialize};
+//!
+//!     #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+//!     #[archive_attr(derive(CheckBytes))]
+//!     pub enum Event {
+//!         Started,
+//!         Progress(u32),
+//!         #[archive(unknown)]
+//!         Other,
+//!     }
+//! }
+//!
+//! mod v2 {
+//!     use rkyv::{bytecheck::CheckBytes, Archive, Deserialize, Serialize};
+//!
+//!     #[derive(Archive, Serialize, Deserialize)]
+//!     #[archive_attr(derive(CheckBytes))]
+//!     pub enum Event {
+//!         Started,
+//!         Progress(u32),
+//!         #[archive(unknown)]
+//!         Other,
+//!         Finished { message: String },
+//!     }
+//! }
+//!
+//! let bytes = rkyv::to_bytes::<Error>(&v2::Event::Finished {
+//!     message: "done".to_string(),
+//! })
+//! .unwrap();
+//!
+//! // Old readers see variants they don't know about as their unknown variant.
+//! let archived = rkyv::access::<v1::ArchivedEvent, Error>(&bytes).unwrap();
+//! assert!(matches!(archived.variant(), v1::ArchivedEventVariant::Other(3)));
+//! let event = rkyv::from_bytes::<v1::Event, Error>(&bytes).unwrap();
+//! assert_eq!(event, v1::Event::Other);
+//!
+//! // New readers can access the fields of every variant.
+//! let archived = rkyv::access::<v2::ArchivedEvent, Error>(&bytes).unwrap();
+//! match archived.variant() {
+//!     v2::ArchivedEventVariant::Finished(fields) => {
+//!         assert_eq!(fields.message, "done");
+//!     }
+//!     _ => panic!("expected the finished variant"),
+//! }
+//! ```
+
+use core::{
+    fmt,
+    marker::PhantomData,
+    mem::{size_of, MaybeUninit},
+};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    primitive::{ArchivedU32, ArchivedUsize},
+    ser::{Writer, WriterExt as _},
+    Archive as _, Place, Portable, RawRelPtr,
+};
+
+/// The archived fields of an extensible struct.
+///
+/// This is implemented by `#[derive(Archive)]` with `#[archive(extensible)]`.
+///
+/// # Safety
+///
+/// - The implementing type must be `#[repr(C)]`.
+/// - The fields of the implementing type must be ordered by the version they
+///   were added in.
+/// - `VERSION` must be the latest version of any field.
+/// - `len_in` must return `size_of::<Self>()` for versions greater than or
+///   equal to `VERSION`. For earlier versions, it must return the offset of the
+///   end of the last field which is present in that version.
+pub unsafe trait ExtensibleFields: Portable {
+    /// The latest version of the fields.
+    const VERSION: u32;
+
+    /// Returns the number of bytes needed to store the fields which are
+    /// present in the given version.
+    fn len_in(version: u32) -> usize;
+}
+
+/// An archived struct which can gain new fields over time.
+///
+/// The fields are stored out-of-line, and are preceded by a header with their
+/// length and version.
+#[derive(Portable)]
+#[archive(crate)]
+#[repr(C)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedExtensible<T> {
+    ptr: RawRelPtr,
+    len: ArchivedUsize,
+    version: ArchivedU32,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> ArchivedExtensible<T> {
+    /// Returns the version of the struct that was archived.
+    pub fn version(&self) -> u32 {
+        self.version.to_native()
+    }
+}
+
+impl<T: ExtensibleFields> ArchivedExtensible<T> {
+    /// Returns the archived fields if all of them are present.
+    ///
+    /// This returns `None` if the struct was archived with an older version
+    /// which is missing some of the fields.
+    pub fn get(&self) -> Option<&T> {
+        if self.version() >= T::VERSION {
+            // SAFETY: The archived version has every field, so the fields
+            // are all valid.
+            Some(unsafe { &*self.ptr.as_ptr().cast::<T>() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the field of type `F` at the given offset if it is present in
+    /// the archived version.
+    ///
+    /// # Safety
+    ///
+    /// `T` must have a field of type `F` at the given offset which was added
+    /// in version `since`.
+    pub unsafe fn field<F>(&self, since: u32, offset: usize) -> Option<&F> {
+        if self.version() >= since {
+            // SAFETY: The caller has guaranteed that there is a field of type
+            // `F` at `offset`, and it is present in the archived version.
+            Some(unsafe {
+                &*self.ptr.as_ptr().cast::<u8>().add(offset).cast::<F>()
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the field of type `F` at the given offset without checking
+    /// whether it is present in the archived version.
+    ///
+    /// # Safety
+    ///
+    /// `T` must have a field of type `F` at the given offset which is present
+    /// in the archived version.
+    pub unsafe fn field_unchecked<F>(&self, offset: usize) -> &F {
+        // SAFETY: The caller has guaranteed that there is a field of type `F`
+        // at `offset`, and it is present in the archived version.
+        unsafe { &*self.ptr.as_ptr().cast::<u8>().add(offset).cast::<F>() }
+    }
+
+    /// Serializes the fields of an extensible struct.
+    ///
+    /// `resolve` is called to write the fields into their place, after which
+    /// they are written to the serializer.
+    pub fn serialize_fields<S>(
+        serializer: &mut S,
+        resolve: impl FnOnce(Place<T>),
+    ) -> Result<ExtensibleResolver, S::Error>
+    where
+        S: Fallible + Writer + ?Sized,
+    {
+        let pos = serializer.align_for::<T>()?;
+        let mut fields = MaybeUninit::<T>::zeroed();
+        // SAFETY: `fields.as_mut_ptr()` points to a local zeroed
+        // `MaybeUninit`, and so is properly aligned, dereferenceable, and all
+        // of its bytes are initialized.
+        let out = unsafe { Place::new_unchecked(pos, fields.as_mut_ptr()) };
+        resolve(out);
+        serializer.write(out.as_slice())?;
+        Ok(ExtensibleResolver { pos })
+    }
+
+    /// Resolves an archived extensible struct from its resolver.
+    pub fn resolve_from_resolver(
+        resolver: ExtensibleResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let Self { ptr, len, version, _phantom: _ } = out);
+        RawRelPtr::emplace(resolver.pos, ptr);
+        size_of::<T>().resolve((), len);
+        T::VERSION.resolve((), version);
+    }
+}
+
+impl<T: ExtensibleFields + fmt::Debug> fmt::Debug for ArchivedExtensible<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get() {
+            Some(fields) => fields.fmt(f),
+            None => f
+                .debug_struct("ArchivedExtensible")
+                .field("version", &self.version())
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// The resolver for an [`ArchivedExtensible`].
+pub struct ExtensibleResolver {
+    pos: usize,
+}
+
+/// An archived enum which can gain new variants over time.
+///
+/// The fields of the active variant are stored out-of-line, and are preceded
+/// by a header with the tag of the variant and the length of its fields. `T`
+/// is the archived enum, which describes the known variants.
+#[derive(Portable)]
+#[archive(crate)]
+#[repr(C)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedOpenEnum<T> {
+    ptr: RawRelPtr,
+    len: ArchivedUsize,
+    tag: u8,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> ArchivedOpenEnum<T> {
+    /// Returns the tag of the archived variant.
+    pub fn tag(&self) -> u8 {
+        self.tag
+    }
+
+    /// Returns the fields of the archived variant.
+    ///
+    /// # Safety
+    ///
+    /// `F` must be the type of the fields of the variant with the archived
+    /// tag.
+    pub unsafe fn fields<F>(&self) -> &F {
+        // SAFETY: The caller has guaranteed that the fields of the archived
+        // variant have type `F`.
+        unsafe { &*self.ptr.as_ptr().cast::<F>() }
+    }
+
+    /// Serializes the fields of a variant of an open enum.
+    ///
+    /// `resolve` is called to write the fields into their place, after which
+    /// they are written to the serializer. Variants without fields should use
+    /// `()` for their fields.
+    pub fn serialize_variant<F, S>(
+        serializer: &mut S,
+        resolve: impl FnOnce(Place<F>),
+    ) -> Result<OpenEnumResolver, S::Error>
+    where
+        S: Fallible + Writer + ?Sized,
+    {
+        let pos = serializer.align_for::<F>()?;
+        let mut fields = MaybeUninit::<F>::zeroed();
+        // SAFETY: `fields.as_mut_ptr()` points to a local zeroed
+        // `MaybeUninit`, and so is properly aligned, dereferenceable, and all
+        // of its bytes are initialized.
+        let out = unsafe { Place::new_unchecked(pos, fields.as_mut_ptr()) };
+        resolve(out);
+        serializer.write(out.as_slice())?;
+        Ok(OpenEnumResolver {
+            pos,
+            len: size_of::<F>(),
+        })
+    }
+
+    /// Resolves an archived open enum with the given tag from its resolver.
+    pub fn resolve_from_resolver(
+        tag: u8,
+        resolver: OpenEnumResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let Self { ptr, len, tag: out_tag, _phantom: _ } = out);
+        RawRelPtr::emplace(resolver.pos, ptr);
+        resolver.len.resolve((), len);
+        tag.resolve((), out_tag);
+    }
+}
+
+impl<T> fmt::Debug for ArchivedOpenEnum<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivedOpenEnum")
+            .field("tag", &self.tag)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The resolver for an [`ArchivedOpenEnum`].
+pub struct OpenEnumResolver {
+    pos: usize,
+    len: usize,
+}
+
+/// An error indicating that an open enum had a variant which the reader
+/// doesn't know about.
+#[derive(Debug)]
+pub struct UnknownVariant {
+    tag: u8,
+}
+
+impl UnknownVariant {
+    /// Returns a new `UnknownVariant` error for the given tag.
+    pub fn new(tag: u8) -> Self {
+        Self { tag }
+    }
+
+    /// Returns the tag of the unknown variant.
+    pub fn tag(&self) -> u8 {
+        self.tag
+    }
+}
+
+impl fmt::Display for UnknownVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown variant with tag {}", self.tag)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownVariant {}
+
+#[cfg(feature = "bytecheck")]
+pub use self::verify::{check_field, CheckFields, CheckVariants};
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{alloc::Layout, fmt, mem::align_of};
+
+    use bytecheck::{CheckBytes, Verify};
+    use rancor::{fail, Fallible, ResultExt as _, Source};
+
+    use super::{ArchivedExtensible, ArchivedOpenEnum, ExtensibleFields};
+    use crate::validation::{
+        path::PathSegment, ArchiveContext, ArchiveContextExt as _,
+    };
+
+    /// The archived fields of an extensible struct which can be validated.
+    ///
+    /// This is implemented by `#[derive(Archive)]` with
+    /// `#[archive(extensible)]`.
+    ///
+    /// # Safety
+    ///
+    /// `check_fields` must only succeed if every field of `Self` which is
+    /// present in the given version is valid.
+    pub unsafe trait CheckFields<C: Fallible + ?Sized>:
+        ExtensibleFields
+    {
+        /// Checks the fields present in the given version.
+        ///
+        /// # Safety
+        ///
+        /// `value` must point to at least `Self::len_in(version)` readable
+        /// bytes.
+        unsafe fn check_fields(
+            value: *const Self,
+            version: u32,
+            context: &mut C,
+        ) -> Result<(), C::Error>;
+    }
+
+    /// The variants of an open enum which can be validated.
+    ///
+    /// This is implemented by `#[derive(Archive)]` with `#[archive(open)]`.
+    ///
+    /// # Safety
+    ///
+    /// - `fields_len` must return the size of the fields of the variant with
+    ///   the given tag, or `None` if the tag is unknown.
+    /// - `check_variant` must only succeed if every field of the variant with
+    ///   the given tag is valid.
+    pub unsafe trait CheckVariants<C: Fallible + ?Sized> {
+        /// Returns the size of the fields of the variant with the given tag,
+        /// or `None` if the tag is unknown.
+        fn fields_len(tag: u8) -> Option<usize>;
+
+        /// Checks the fields of the variant with the given tag.
+        ///
+        /// # Safety
+        ///
+        /// `tag` must be a known tag, and `fields` must point to at least
+        /// `Self::fields_len(tag)` readable bytes.
+        unsafe fn check_variant(
+            tag: u8,
+            fields: *const u8,
+            context: &mut C,
+        ) -> Result<(), C::Error>;
+    }
+
+    #[derive(Debug)]
+    struct FieldsTooShort {
+        len: usize,
+        expected: usize,
+    }
+
+    impl fmt::Display for FieldsTooShort {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "extensible fields were too short (length: {}, expected at \
+                 least: {})",
+                self.len, self.expected,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for FieldsTooShort {}
+
+    #[derive(Debug)]
+    struct UnalignedField {
+        offset: usize,
+        align: usize,
+    }
+
+    impl fmt::Display for UnalignedField {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "extensible field at offset {} was not aligned to {} bytes",
+                self.offset, self.align,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for UnalignedField {}
+
+    /// Checks the field named `name` of type `F` at the given offset from
+    /// `base`.
+    ///
+    /// Older versions of an extensible struct may have a lower alignment than
+    /// newer versions, so the alignment of each field is checked separately.
+    ///
+    /// # Safety
+    ///
+    /// `base` must point to at least `offset + size_of::<F>()` readable bytes.
+    pub unsafe fn check_field<F, C>(
+        base: *const u8,
+        offset: usize,
+        name: &'static str,
+        context: &mut C,
+    ) -> Result<(), C::Error>
+    where
+        F: CheckBytes<C>,
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        // SAFETY: The caller has guaranteed that `base + offset` is readable.
+        let ptr = unsafe { base.add(offset).cast::<F>() };
+        if !ptr.is_aligned() {
+            fail!(UnalignedField {
+                offset,
+                align: align_of::<F>(),
+            });
+        }
+        // SAFETY: The caller has guaranteed that `ptr` points to at least
+        // `size_of::<F>()` readable bytes, and we checked that it is
+        // properly aligned.
+        unsafe {
+            F::check_bytes(ptr, context).with_trace(|| PathSegment::Field(name))
+        }
+    }
+
+    unsafe impl<T, C> Verify<C> for ArchivedExtensible<T>
+    where
+        T: CheckFields<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let version = self.version();
+            let len = self.len.to_native() as usize;
+            let expected = T::len_in(version);
+            if len < expected {
+                fail!(FieldsTooShort { len, expected });
+            }
+
+            let ptr = self.ptr.as_ptr_wrapping().cast::<u8>();
+            let layout = Layout::from_size_align(len, 1).into_error()?;
+            context.in_subtree_raw(ptr, layout, |context| unsafe {
+                T::check_fields(ptr.cast::<T>(), version, context)
+            })
+        }
+    }
+
+    unsafe impl<T, C> Verify<C> for ArchivedOpenEnum<T>
+    where
+        T: CheckVariants<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            // Variants with unknown tags are skipped so that older readers
+            // can still validate the rest of the archive.
+            let Some(expected) = T::fields_len(self.tag) else {
+                return Ok(());
+            };
+            let len = self.len.to_native() as usize;
+            if len < expected {
+                fail!(FieldsTooShort { len, expected });
+            }
+            if len == 0 {
+                return Ok(());
+            }
+
+            let ptr = self.ptr.as_ptr_wrapping().cast::<u8>();
+            let layout = Layout::from_size_align(len, 1).into_error()?;
+            context.in_subtree_raw(ptr, layout, |context| unsafe {
+                T::check_variant(self.tag, ptr, context)
+            })
+        }
+    }
+}
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+
+    use core::mem::{offset_of, size_of};
+
+    use rancor::{Error, Failure};
+
+    use super::{ArchivedExtensible, ArchivedOpenEnum};
+    use crate::{
+        access,
+        de::Pool,
+        from_bytes,
+        primitive::{ArchivedU32, ArchivedUsize},
+        to_bytes, Archive, Deserialize, Serialize,
+    };
+
+    mod v1 {
+        #[cfg(not(feature = "std"))]
+        use alloc::string::String;
+
+        use crate::{Archive, Deserialize, Serialize};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate, extensible)]
+        #[archive_attr(derive(bytecheck::CheckBytes))]
+        pub struct Example {
+            pub id: u8,
+            pub name: String,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate, open)]
+        #[archive_attr(derive(bytecheck::CheckBytes))]
+        pub enum Shape {
+            Point,
+            Circle { radius: u32 },
+            Pair(u8, String),
+        }
+    }
+
+    mod v3 {
+        #[cfg(not(feature = "std"))]
+        use alloc::{string::String, vec::Vec};
+
+        use crate::{Archive, Deserialize, Serialize};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate, extensible)]
+        #[archive_attr(derive(bytecheck::CheckBytes))]
+        pub struct Example {
+            pub id: u8,
+            pub name: String,
+            #[archive(since = 2)]
+            pub score: u64,
+            #[archive(since = 2)]
+            pub tags: Vec<String>,
+            #[archive(since = 3)]
+            pub enabled: bool,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate, open)]
+        #[archive_attr(derive(bytecheck::CheckBytes))]
+        pub enum Shape {
+            Point,
+            Circle { radius: u32 },
+            Pair(u8, String),
+            Polygon(Vec<(u32, u32)>),
+        }
+    }
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[archive(crate, extensible)]
+    #[archive_attr(derive(bytecheck::CheckBytes))]
+    struct Generic<T> {
+        value: T,
+        #[archive(since = 1)]
+        extra: u32,
+    }
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[archive(crate)]
+    #[archive_attr(derive(bytecheck::CheckBytes))]
+    enum GenericShape<T> {
+        Value(T),
+        #[archive(unknown)]
+        Other,
+    }
+
+    fn example_v3() -> v3::Example {
+        v3::Example {
+            id: 7,
+            name: "seven".to_string(),
+            score: 42,
+            tags: vec!["a".to_string(), "b".to_string()],
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn same_version() {
+        let value = example_v3();
+        let bytes = to_bytes::<Error>(&value).unwrap();
+        let archived = access::<v3::ArchivedExample, Error>(&bytes).unwrap();
+        assert_eq!(archived.version(), 3);
+        assert_eq!(archived.get().unwrap().score, 42);
+        assert_eq!(archived.tags().unwrap().len(), 2);
+        assert_eq!(archived.enabled(), Some(&true));
+
+        let deserialized = from_bytes::<v3::Example, Error>(&bytes).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    fn old_reader_new_archive() {
+        let bytes = to_bytes::<Error>(&example_v3()).unwrap();
+        let archived = access::<v1::ArchivedExample, Error>(&bytes).unwrap();
+        assert_eq!(archived.version(), 3);
+        assert_eq!(*archived.id(), 7);
+        assert_eq!(archived.name(), "seven");
+
+        let deserialized = from_bytes::<v1::Example, Error>(&bytes).unwrap();
+        assert_eq!(
+            deserialized,
+            v1::Example {
+                id: 7,
+                name: "seven".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn new_reader_old_archive() {
+        let bytes = to_bytes::<Error>(&v1::Example {
+            id: 1,
+            name: "one".to_string(),
+        })
+        .unwrap();
+        let archived = access::<v3::ArchivedExample, Error>(&bytes).unwrap();
+        assert_eq!(archived.version(), 0);
+        assert!(archived.get().is_none());
+        assert_eq!(archived.name(), "one");
+        assert!(archived.score().is_none());
+        assert!(archived.tags().is_none());
+        assert!(archived.enabled().is_none());
+
+        let mut deserialized = example_v3();
+        crate::deserialize_into::<_, _, Error>(
+            archived,
+            &mut deserialized,
+            &mut Pool::new(),
+        )
+        .unwrap();
+        let expected = v3::Example {
+            id: 1,
+            name: "one".to_string(),
+            score: 0,
+            tags: Vec::new(),
+            enabled: false,
+        };
+        assert_eq!(deserialized, expected);
+        assert_eq!(from_bytes::<v3::Example, Error>(&bytes).unwrap(), expected);
+    }
+
+    #[test]
+    fn invalid_version() {
+        let mut bytes = to_bytes::<Error>(&v1::Example {
+            id: 1,
+            name: "one".to_string(),
+        })
+        .unwrap();
+        // The root header starts at the root of the archive. Claiming that the
+        // archive is from a newer version makes its fields too short.
+        let pos = bytes.len() - size_of::<v1::ArchivedExample>()
+            + offset_of!(ArchivedExtensible<()>, version);
+        unsafe {
+            bytes
+                .as_mut_ptr()
+                .add(pos)
+                .cast::<ArchivedU32>()
+                .write_unaligned(ArchivedU32::from_native(3));
+        }
+        assert!(access::<v3::ArchivedExample, Failure>(&bytes).is_err());
+        assert!(access::<v1::ArchivedExample, Failure>(&bytes).is_ok());
+    }
+
+    #[test]
+    fn generic() {
+        let value = Generic {
+            value: "hello".to_string(),
+            extra: 3,
+        };
+        let bytes = to_bytes::<Error>(&value).unwrap();
+        let archived =
+            access::<ArchivedGeneric<String>, Error>(&bytes).unwrap();
+        assert_eq!(archived.value(), "hello");
+        assert_eq!(archived.extra().map(|extra| extra.to_native()), Some(3));
+        assert_eq!(
+            from_bytes::<Generic<String>, Error>(&bytes).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn open_enum_same_version() {
+        for value in [
+            v3::Shape::Point,
+            v3::Shape::Circle { radius: 5 },
+            v3::Shape::Pair(2, "two".to_string()),
+            v3::Shape::Polygon(vec![(0, 0), (1, 2)]),
+        ] {
+            let bytes = to_bytes::<Error>(&value).unwrap();
+            access::<v3::ArchivedShape, Error>(&bytes).unwrap();
+            let deserialized = from_bytes::<v3::Shape, Error>(&bytes).unwrap();
+            assert_eq!(deserialized, value);
+        }
+
+        let bytes =
+            to_bytes::<Error>(&v3::Shape::Pair(2, "two".to_string())).unwrap();
+        let archived = access::<v3::ArchivedShape, Error>(&bytes).unwrap();
+        match archived.variant() {
+            v3::ArchivedShapeVariant::Pair(fields) => {
+                assert_eq!(fields.0, 2);
+                assert_eq!(fields.1, "two");
+            }
+            _ => panic!("expected the pair variant"),
+        }
+    }
+
+    #[test]
+    fn open_enum_unknown_variant() {
+        let bytes =
+            to_bytes::<Error>(&v3::Shape::Polygon(vec![(0, 0), (1, 2)]))
+                .unwrap();
+        let archived = access::<v1::ArchivedShape, Error>(&bytes).unwrap();
+        assert!(matches!(
+            archived.variant(),
+            v1::ArchivedShapeVariant::Unknown(3)
+        ));
+        assert!(from_bytes::<v1::Shape, Failure>(&bytes).is_err());
+
+        let bytes =
+            to_bytes::<Error>(&v3::Shape::Circle { radius: 5 }).unwrap();
+        let archived = access::<v1::ArchivedShape, Error>(&bytes).unwrap();
+        match archived.variant() {
+            v1::ArchivedShapeVariant::Circle(fields) => {
+                assert_eq!(fields.radius, 5)
+            }
+            _ => panic!("expected the circle variant"),
+        }
+        assert_eq!(
+            from_bytes::<v1::Shape, Error>(&bytes).unwrap(),
+            v1::Shape::Circle { radius: 5 },
+        );
+    }
+
+    #[test]
+    fn open_enum_fields_too_short() {
+        let mut bytes =
+            to_bytes::<Error>(&v1::Shape::Circle { radius: 5 }).unwrap();
+        // The root header starts at the root of the archive. Known variants
+        // must have enough bytes for all of their fields.
+        let pos = bytes.len() - size_of::<v1::ArchivedShape>()
+            + offset_of!(ArchivedOpenEnum<()>, len);
+        unsafe {
+            bytes
+                .as_mut_ptr()
+                .add(pos)
+                .cast::<ArchivedUsize>()
+                .write_unaligned(ArchivedUsize::from_native(1));
+        }
+        assert!(access::<v1::ArchivedShape, Failure>(&bytes).is_err());
+    }
+
+    #[test]
+    fn open_enum_catch_all() {
+        let value = GenericShape::Value("hello".to_string());
+        let bytes = to_bytes::<Error>(&value).unwrap();
+        let archived =
+            access::<ArchivedGenericShape<String>, Error>(&bytes).unwrap();
+        match archived.variant() {
+            ArchivedGenericShapeVariant::Value(fields) => {
+                assert_eq!(fields.0, "hello")
+            }
+            ArchivedGenericShapeVariant::Other(_) => {
+                panic!("expected the value variant")
+            }
+        }
+        assert_eq!(
+            from_bytes::<GenericShape<String>, Error>(&bytes).unwrap(),
+            value,
+        );
+
+        let bytes = to_bytes::<Error>(&v3::Shape::Polygon(Vec::new())).unwrap();
+        let archived =
+            access::<ArchivedGenericShape<String>, Error>(&bytes).unwrap();
+        assert!(matches!(
+            archived.variant(),
+            ArchivedGenericShapeVariant::Other(3)
+        ));
+        assert_eq!(
+            from_bytes::<GenericShape<String>, Error>(&bytes).unwrap(),
+            GenericShape::Other,
+        );
+    }
+}
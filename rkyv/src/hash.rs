@@ -1,11 +1,37 @@
 //! Hashing support for archived hash maps and sets.
+//!
+//! Archived hash maps and sets are built with a [`SeededHasher`]. The default
+//! [`FxHasher64`] is fast but unseeded, so anyone who chooses the keys of a
+//! hash map can also choose which of them collide. Tables built from untrusted
+//! keys should use a keyed hasher like [`SipHasher13`] with a secret key, which
+//! serializers provide through [`WithSeed`](crate::ser::seed::WithSeed).
 
 use core::{
     hash::{Hash, Hasher},
     ops::BitXor as _,
 };
 
-use crate::primitive::{FixedIsize, FixedUsize};
+use crate::{
+    place::Initialized,
+    primitive::{ArchivedU64, FixedIsize, FixedUsize},
+    Portable,
+};
+
+/// A hasher which archived hash tables can be built with.
+///
+/// Archived hash tables store the seed of the hasher they were built with, and
+/// key their hasher with that seed whenever they look up a key.
+pub trait SeededHasher: Hasher {
+    /// The seed stored in archived hash tables. Unseeded hashers use `()` so
+    /// that the seed does not take up any space.
+    type Seed: Copy + Initialized + Portable;
+
+    /// Creates a seed from the given key.
+    fn seed_from_key(key: [u64; 2]) -> Self::Seed;
+
+    /// Returns a new hasher keyed with the given seed.
+    fn with_seed(seed: &Self::Seed) -> Self;
+}
 
 /// A cross-platform 64-bit implementation of fxhash.
 #[derive(Default)]
@@ -114,6 +140,175 @@ impl Hasher for FxHasher64 {
     }
 }
 
+impl SeededHasher for FxHasher64 {
+    type Seed = ();
+
+    #[inline]
+    fn seed_from_key(_: [u64; 2]) -> Self::Seed {}
+
+    #[inline]
+    fn with_seed(_: &Self::Seed) -> Self {
+        Self::default()
+    }
+}
+
+/// A cross-platform implementation of SipHash-1-3.
+///
+/// This is the same keyed hash function that the standard library uses for its
+/// hash maps. Unlike the standard library's hasher, it always hashes integers
+/// in little-endian byte order so that archived hash tables can be shared
+/// between platforms.
+#[derive(Clone, Debug)]
+pub struct SipHasher13 {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    tail: u64,
+    ntail: usize,
+    length: u64,
+}
+
+impl SipHasher13 {
+    /// Returns a new hasher keyed with the given keys.
+    pub const fn new_with_keys(k0: u64, k1: u64) -> Self {
+        Self {
+            v0: k0 ^ 0x736f6d6570736575,
+            v1: k1 ^ 0x646f72616e646f6d,
+            v2: k0 ^ 0x6c7967656e657261,
+            v3: k1 ^ 0x7465646279746573,
+            tail: 0,
+            ntail: 0,
+            length: 0,
+        }
+    }
+
+    #[inline]
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13) ^ self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16) ^ self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21) ^ self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17) ^ self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    #[inline]
+    fn compress(&mut self, word: u64) {
+        self.v3 ^= word;
+        self.round();
+        self.v0 ^= word;
+    }
+}
+
+#[inline]
+fn read_le(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .enumerate()
+        .fold(0, |word, (i, &byte)| word | (byte as u64) << (8 * i))
+}
+
+impl Hasher for SipHasher13 {
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len() as u64);
+
+        if self.ntail != 0 {
+            let fill = (8 - self.ntail).min(bytes.len());
+            self.tail |= read_le(&bytes[..fill]) << (8 * self.ntail);
+            self.ntail += fill;
+            bytes = &bytes[fill..];
+            if self.ntail < 8 {
+                return;
+            }
+            self.compress(self.tail);
+        }
+
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            self.compress(read_le(word));
+        }
+        let rest = words.remainder();
+        self.tail = read_le(rest);
+        self.ntail = rest.len();
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = self.clone();
+        state.compress((self.length & 0xff) << 56 | self.tail);
+        state.v2 ^= 0xff;
+        state.round();
+        state.round();
+        state.round();
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write(&[i]);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write(&(i as FixedUsize).to_le_bytes());
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write(&(i as FixedIsize).to_le_bytes());
+    }
+}
+
+impl SeededHasher for SipHasher13 {
+    type Seed = [ArchivedU64; 2];
+
+    #[inline]
+    fn seed_from_key(key: [u64; 2]) -> Self::Seed {
+        key.map(ArchivedU64::from_native)
+    }
+
+    #[inline]
+    fn with_seed(seed: &Self::Seed) -> Self {
+        Self::new_with_keys(seed[0].to_native(), seed[1].to_native())
+    }
+}
+
+/// Returns a random key for a [`SeededHasher`].
+///
+/// Each call returns a different key, drawn from the same source of randomness
+/// as the standard library's `RandomState`.
+#[cfg(feature = "std")]
+pub fn random_key() -> [u64; 2] {
+    use std::{collections::hash_map::RandomState, hash::BuildHasher as _};
+
+    let state = RandomState::new();
+    [state.hash_one(0u8), state.hash_one(1u8)]
+}
+
 /// Hashes the given value with the default value of the specified `Hasher`.
 pub fn hash_value<Q, H: Hasher + Default>(value: &Q) -> u64
 where
@@ -123,3 +318,44 @@ where
     value.hash(&mut state);
     state.finish()
 }
+
+/// Hashes the given value with the specified `Hasher` keyed with `seed`.
+pub fn hash_value_with_seed<Q, H: SeededHasher>(
+    value: &Q,
+    seed: &H::Seed,
+) -> u64
+where
+    Q: Hash + ?Sized,
+{
+    let mut state = H::with_seed(seed);
+    value.hash(&mut state);
+    state.finish()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use core::hash::Hasher;
+    #[allow(deprecated)]
+    use std::collections::hash_map::DefaultHasher;
+
+    use super::SipHasher13;
+
+    #[test]
+    fn sip_hasher_13_matches_std() {
+        let bytes = (0..64).collect::<Vec<u8>>();
+        for len in 0..bytes.len() {
+            for split in 0..=len {
+                // The standard library's default hasher is SipHash-1-3 keyed
+                // with zeros.
+                let mut expected = DefaultHasher::new();
+                expected.write(&bytes[..len]);
+
+                let mut hasher = SipHasher13::new_with_keys(0, 0);
+                hasher.write(&bytes[..split]);
+                hasher.write(&bytes[split..len]);
+
+                assert_eq!(hasher.finish(), expected.finish());
+            }
+        }
+    }
+}
@@ -58,6 +58,17 @@ where
         }
         unsafe { Ok(Box::from_raw(out)) }
     }
+
+    fn deserialize_into(
+        &self,
+        target: &mut Box<T>,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        if !self.get().deserialize_unsized_into(target, deserializer)? {
+            *target = self.deserialize(deserializer)?;
+        }
+        Ok(())
+    }
 }
 
 impl<T, U> PartialEq<Box<U>> for ArchivedBox<T>
@@ -0,0 +1,95 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BinaryHeap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BinaryHeap;
+
+use rancor::Fallible;
+
+use crate::{
+    collections::binary_heap::{ArchivedBinaryHeap, BinaryHeapResolver},
+    ser::{Allocator, Writer},
+    Archive, Deserialize, Place, Serialize,
+};
+
+impl<T: Archive + Ord> Archive for BinaryHeap<T> {
+    type Archived = ArchivedBinaryHeap<T::Archived>;
+    type Resolver = BinaryHeapResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedBinaryHeap::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for BinaryHeap<T>
+where
+    T: Serialize<S> + Ord,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBinaryHeap::<T::Archived>::serialize_from_slice(
+            self.as_slice(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D> Deserialize<BinaryHeap<T>, D> for ArchivedBinaryHeap<T::Archived>
+where
+    T: Archive + Ord,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<BinaryHeap<T>, D::Error> {
+        // The elements are already in heap order, so rebuilding the heap from
+        // them does not reorder anything.
+        let mut vec = Vec::with_capacity(self.len());
+        for item in self.iter() {
+            vec.push(item.deserialize(deserializer)?);
+        }
+        Ok(BinaryHeap::from(vec))
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<BinaryHeap<U>> for ArchivedBinaryHeap<T> {
+    fn eq(&self, other: &BinaryHeap<U>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::collections::BinaryHeap;
+    #[cfg(feature = "std")]
+    use std::collections::BinaryHeap;
+
+    use crate::{
+        deserialize, primitive::ArchivedI32, rancor::Error, test::to_archived,
+    };
+
+    #[test]
+    fn binary_heap() {
+        let heap = [5, 1, 8, 3, 9, 2, 7]
+            .into_iter()
+            .collect::<BinaryHeap<i32>>();
+
+        // `BinaryHeap` does not implement `PartialEq`, so the deserialized heap
+        // is compared by its slice.
+        to_archived(&heap, |archived| {
+            assert_eq!(archived, &heap);
+            assert_eq!(archived.len(), 7);
+            assert_eq!(archived.peek(), Some(&ArchivedI32::from_native(9)));
+
+            let deserialized =
+                deserialize::<BinaryHeap<i32>, _, Error>(archived, &mut ())
+                    .unwrap();
+            assert_eq!(deserialized.as_slice(), heap.as_slice());
+        });
+    }
+}
@@ -71,6 +71,42 @@ where
             None => Ok(result),
         }
     }
+
+    fn deserialize_into(
+        &self,
+        target: &mut BTreeMap<K, V>,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        // Each key is deserialized once. Values whose keys are already in the
+        // map are moved out of it and deserialized in place.
+        let mut result = BTreeMap::new();
+        let r = self.visit(|ak, av| {
+            let k = match ak.deserialize(deserializer) {
+                Ok(k) => k,
+                Err(e) => return ControlFlow::Break(e),
+            };
+            let v = match target.remove(&k) {
+                Some(mut v) => {
+                    av.deserialize_into(&mut v, deserializer).map(|()| v)
+                }
+                None => av.deserialize(deserializer),
+            };
+            match v {
+                Ok(v) => {
+                    result.insert(k, v);
+                    ControlFlow::Continue(())
+                }
+                Err(e) => ControlFlow::Break(e),
+            }
+        });
+        match r {
+            Some(e) => Err(e),
+            None => {
+                *target = result;
+                Ok(())
+            }
+        }
+    }
 }
 
 impl<K, V, AK, AV> PartialEq<BTreeMap<K, V>> for ArchivedBTreeMap<AK, AV>
@@ -98,8 +134,15 @@ where
 
 #[cfg(test)]
 mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+    use core::ops::Bound;
+
     use super::BTreeMap;
-    use crate::test::roundtrip;
+    use crate::{
+        test::{roundtrip, roundtrip_into, to_archived},
+        Archived,
+    };
 
     #[test]
     fn roundtrip_btree_map() {
@@ -117,6 +160,28 @@ mod tests {
         roundtrip(&BTreeMap::<String, i32>::new());
     }
 
+    #[test]
+    fn deserialize_into_btree_map() {
+        let mut value = BTreeMap::new();
+        value.insert("hello".to_string(), "world".to_string());
+        value.insert("foo".to_string(), "bar".to_string());
+
+        let mut target = BTreeMap::new();
+        roundtrip_into(&value, &mut target);
+        let value_ptr = target["hello"].as_ptr();
+
+        // Same keys, so the values are deserialized in place
+        value.insert("hello".to_string(), "there".to_string());
+        roundtrip_into(&value, &mut target);
+        assert_eq!(target["hello"].as_ptr(), value_ptr);
+
+        // Keys which are still in the map keep their values
+        value.remove("foo");
+        value.insert("baz".to_string(), "bat".to_string());
+        roundtrip_into(&value, &mut target);
+        assert_eq!(target["hello"].as_ptr(), value_ptr);
+    }
+
     #[test]
     fn roundtrip_btree_map_zst() {
         let mut value = BTreeMap::new();
@@ -149,4 +214,215 @@ mod tests {
 
         roundtrip(&value);
     }
+
+    #[test]
+    fn archived_btree_map_iter() {
+        fn bound<T>(kind: usize, key: &T) -> Bound<&T> {
+            match kind {
+                0 => Bound::Included(key),
+                1 => Bound::Excluded(key),
+                _ => Bound::Unbounded,
+            }
+        }
+
+        // These lengths cover empty trees, single nodes, full trees, and trees
+        // with partially-filled last levels.
+        for len in [0u32, 1, 4, 5, 6, 20, 35, 36, 100, 215, 216, 300] {
+            let value =
+                (0..len).map(|i| (2 * i, i)).collect::<BTreeMap<u32, u32>>();
+            let expected =
+                value.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+
+            to_archived(&value, |archived| {
+                let native = |(k, v): (&Archived<u32>, &Archived<u32>)| {
+                    (k.to_native(), v.to_native())
+                };
+
+                assert_eq!(archived.iter().len(), len as usize);
+                assert_eq!(
+                    archived.iter().map(native).collect::<Vec<_>>(),
+                    expected
+                );
+                assert!(archived
+                    .iter()
+                    .rev()
+                    .map(native)
+                    .eq(expected.iter().rev().copied()));
+                assert!(archived
+                    .keys()
+                    .map(|k| k.to_native())
+                    .eq(value.keys().copied()));
+                assert!(archived
+                    .values()
+                    .rev()
+                    .map(|v| v.to_native())
+                    .eq(value.values().rev().copied()));
+                assert_eq!(
+                    archived.first_key_value().map(native),
+                    value.first_key_value().map(|(k, v)| (*k, *v)),
+                );
+                assert_eq!(
+                    archived.last_key_value().map(native),
+                    value.last_key_value().map(|(k, v)| (*k, *v)),
+                );
+
+                // Alternate between the front and back of the iterator.
+                let mut iter = archived.iter();
+                let mut front = Vec::new();
+                let mut back = Vec::new();
+                while let Some(entry) = iter.next() {
+                    front.push(native(entry));
+                    assert_eq!(
+                        iter.len(),
+                        len as usize - front.len() - back.len()
+                    );
+                    match iter.next_back() {
+                        Some(entry) => back.push(native(entry)),
+                        None => break,
+                    }
+                }
+                assert_eq!(iter.next(), None);
+                front.extend(back.into_iter().rev());
+                assert_eq!(front, expected);
+
+                let keys = [
+                    0,
+                    1,
+                    2,
+                    len,
+                    len + 1,
+                    (2 * len).saturating_sub(1),
+                    2 * len,
+                    2 * len + 5,
+                ];
+                for start in keys {
+                    for end in keys {
+                        let archived_start =
+                            Archived::<u32>::from_native(start);
+                        let archived_end = Archived::<u32>::from_native(end);
+                        for start_kind in 0..3 {
+                            for end_kind in 0..3 {
+                                let range = (
+                                    bound(start_kind, &archived_start),
+                                    bound(end_kind, &archived_end),
+                                );
+                                let native_range = (
+                                    bound(start_kind, &start),
+                                    bound(end_kind, &end),
+                                );
+                                let is_valid = start < end
+                                    || start == end
+                                        && (start_kind, end_kind) != (1, 1)
+                                    || start_kind == 2
+                                    || end_kind == 2;
+                                if !is_valid {
+                                    assert_eq!(
+                                        archived.range(range).next(),
+                                        None
+                                    );
+                                    continue;
+                                }
+
+                                let expected = value
+                                    .range(native_range)
+                                    .map(|(k, v)| (*k, *v))
+                                    .collect::<Vec<_>>();
+                                assert!(archived
+                                    .range(range)
+                                    .map(native)
+                                    .eq(expected.iter().copied()));
+                                assert!(archived
+                                    .range(range)
+                                    .rev()
+                                    .map(native)
+                                    .eq(expected.iter().rev().copied()));
+                            }
+                        }
+
+                        if end == start {
+                            let key = &archived_start;
+                            for kind in 0..3 {
+                                assert_eq!(
+                                    archived
+                                        .lower_bound(bound(kind, key))
+                                        .map(native),
+                                    value
+                                        .range((
+                                            bound(kind, &start),
+                                            Bound::Unbounded
+                                        ))
+                                        .next()
+                                        .map(|(k, v)| (*k, *v)),
+                                );
+                                assert_eq!(
+                                    archived
+                                        .upper_bound(bound(kind, key))
+                                        .map(native),
+                                    value
+                                        .range((
+                                            Bound::Unbounded,
+                                            bound(kind, &start)
+                                        ))
+                                        .next_back()
+                                        .map(|(k, v)| (*k, *v)),
+                                );
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn archived_btree_map_mut() {
+        use rancor::Panic;
+
+        use crate::{
+            access, access_mut, collections::btree_map::ArchivedBTreeMap,
+            to_bytes,
+        };
+
+        type T = BTreeMap<u32, u32>;
+        let key = Archived::<u32>::from_native;
+
+        let value = (0..100u32).map(|i| (i, 0)).collect::<T>();
+        let mut bytes = to_bytes::<Panic>(&value).unwrap();
+        let mut archived =
+            access_mut::<Archived<T>, Panic>(&mut bytes).unwrap();
+
+        // `Pin` has its own `get_mut`, so the map method must be named.
+        assert!(
+            ArchivedBTreeMap::get_mut(archived.as_mut(), &key(100)).is_none()
+        );
+        *ArchivedBTreeMap::get_mut(archived.as_mut(), &key(10)).unwrap() =
+            key(1000);
+        let (k, mut v) = archived.as_mut().get_key_value_mut(&key(20)).unwrap();
+        assert_eq!(*k, 20);
+        *v = key(2000);
+
+        for (k, mut v) in archived.as_mut().iter_mut() {
+            *v = key(v.to_native() + k.to_native());
+        }
+        let mut values = archived.as_mut().values_mut();
+        *values.next_back().unwrap() = key(0);
+        assert_eq!(values.len(), 99);
+        for mut v in values.step_by(2) {
+            *v = key(v.to_native() + 1);
+        }
+
+        let archived = access::<Archived<T>, Panic>(&bytes).unwrap();
+        for (k, v) in archived.iter() {
+            let k = k.to_native();
+            let expected = match k {
+                10 => 1011,
+                20 => 2021,
+                99 => 0,
+                _ if k % 2 == 0 => k + 1,
+                _ => k,
+            };
+            assert_eq!(v.to_native(), expected, "key {k}");
+        }
+    }
 }
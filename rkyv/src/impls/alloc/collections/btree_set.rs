@@ -90,8 +90,15 @@ impl<K, AK: PartialEq<K>> PartialEq<BTreeSet<K>> for ArchivedBTreeSet<AK> {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+    use core::ops::Bound;
+
     use super::BTreeSet;
-    use crate::test::roundtrip;
+    use crate::{
+        test::{roundtrip, to_archived},
+        Archived,
+    };
 
     #[test]
     fn roundtrip_btree_set() {
@@ -111,4 +118,45 @@ mod tests {
 
         roundtrip(&value);
     }
+
+    #[test]
+    fn archived_btree_set_iter() {
+        let value = (0..50u32).map(|i| 3 * i).collect::<BTreeSet<_>>();
+
+        to_archived(&value, |archived| {
+            let key = Archived::<u32>::from_native;
+
+            assert!(archived
+                .iter()
+                .map(|k| k.to_native())
+                .eq(value.iter().copied()));
+            assert!(archived
+                .iter()
+                .rev()
+                .map(|k| k.to_native())
+                .eq(value.iter().rev().copied()));
+            assert_eq!(archived.first().map(|k| k.to_native()), Some(0));
+            assert_eq!(archived.last().map(|k| k.to_native()), Some(147));
+            assert_eq!(
+                archived
+                    .range(key(10)..=key(30))
+                    .map(|k| k.to_native())
+                    .collect::<Vec<_>>(),
+                [12, 15, 18, 21, 24, 27, 30],
+            );
+            assert_eq!(archived.range(key(30)..key(10)).next(), None);
+            assert_eq!(
+                archived
+                    .lower_bound(Bound::Excluded(&key(30)))
+                    .map(|k| k.to_native()),
+                Some(33),
+            );
+            assert_eq!(
+                archived
+                    .upper_bound(Bound::Included(&key(31)))
+                    .map(|k| k.to_native()),
+                Some(30),
+            );
+        });
+    }
 }
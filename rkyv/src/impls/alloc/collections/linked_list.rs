@@ -0,0 +1,77 @@
+#[cfg(not(feature = "std"))]
+use alloc::collections::LinkedList;
+#[cfg(feature = "std")]
+use std::collections::LinkedList;
+
+use rancor::Fallible;
+
+use crate::{
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Deserialize, Place, Serialize,
+};
+
+impl<T: Archive> Archive for LinkedList<T> {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedVec::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for LinkedList<T>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::<T::Archived>::serialize_from_iter::<T, _, _>(
+            self.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D> Deserialize<LinkedList<T>, D> for ArchivedVec<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<LinkedList<T>, D::Error> {
+        let mut result = LinkedList::new();
+        for item in self.iter() {
+            result.push_back(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<LinkedList<U>> for ArchivedVec<T> {
+    fn eq(&self, other: &LinkedList<U>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::collections::LinkedList;
+    #[cfg(feature = "std")]
+    use std::collections::LinkedList;
+
+    use crate::test::roundtrip;
+
+    #[test]
+    fn roundtrip_linked_list() {
+        roundtrip(&LinkedList::<u32>::new());
+        roundtrip(&(0..10u32).collect::<LinkedList<_>>());
+    }
+}
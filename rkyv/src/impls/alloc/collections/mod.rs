@@ -1,2 +1,4 @@
+mod binary_heap;
 mod btree_map;
 mod btree_set;
+mod linked_list;
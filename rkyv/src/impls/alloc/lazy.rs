@@ -0,0 +1,166 @@
+use core::fmt;
+
+use rancor::{fail, Fallible, Source, Strategy};
+
+use crate::{
+    de::Pool,
+    lazy::{BorrowArchive, Lazy, LazyResolver},
+    Archive, Deserialize, Place, Serialize,
+};
+
+impl<T: Archive> Archive for Lazy<'_, T> {
+    type Archived = T::Archived;
+    type Resolver = LazyResolver<T>;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        match resolver.value {
+            Some(value) => value.resolve(resolver.resolver, out),
+            // The resolver only omits the value if this `Lazy` was already
+            // forced when it was serialized, and forced values are never
+            // removed.
+            None => self.get().unwrap().resolve(resolver.resolver, out),
+        }
+    }
+}
+
+impl<T, S> Serialize<S> for Lazy<'_, T>
+where
+    T: Serialize<S>,
+    T::Archived: Deserialize<T, Strategy<Pool, S::Error>>,
+    S: Fallible + ?Sized,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        if let Some(value) = self.get() {
+            return Ok(LazyResolver {
+                value: None,
+                resolver: value.serialize(serializer)?,
+            });
+        }
+
+        // Serializing doesn't force the value, so a temporary value is
+        // deserialized and kept in the resolver instead. A `Lazy` always has
+        // either an archived or a deserialized value.
+        let archived = self.archived().unwrap();
+        let value =
+            crate::deserialize::<T, _, S::Error>(archived, &mut Pool::new())?;
+        let resolver = value.serialize(serializer)?;
+        Ok(LazyResolver {
+            value: Some(value),
+            resolver,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct NotInArchive {
+    address: usize,
+}
+
+impl fmt::Display for NotInArchive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "lazy value at address {:#x} is not located in the archive being \
+             deserialized",
+            self.address,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotInArchive {}
+
+impl<'a, T, D> Deserialize<Lazy<'a, T>, D> for T::Archived
+where
+    T: Archive,
+    D: BorrowArchive<'a> + Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<Lazy<'a, T>, D::Error> {
+        let bytes = deserializer.archive_bytes();
+        let address = self as *const T::Archived as usize;
+        let offset = address.wrapping_sub(bytes.as_ptr() as usize);
+        if offset > bytes.len()
+            || bytes.len() - offset < core::mem::size_of::<T::Archived>()
+        {
+            fail!(NotInArchive { address });
+        }
+
+        // SAFETY: `self` lies entirely within `bytes`, which are borrowed for
+        // `'a`. `BorrowArchive` guarantees that `bytes` contain the entire
+        // archive, so any data reachable from `self` also lives for `'a`.
+        let archived = unsafe { &*bytes.as_ptr().add(offset).cast() };
+        Ok(Lazy::from_archived(archived))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{string::String, vec::Vec};
+
+    use rancor::Panic;
+
+    use crate::{
+        lazy::Lazy, test::to_archived, Archive, Deserialize, Serialize,
+    };
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[archive(crate)]
+    #[cfg_attr(
+        feature = "bytecheck",
+        archive_attr(derive(bytecheck::CheckBytes))
+    )]
+    struct Record<'a> {
+        name: String,
+        values: Lazy<'a, Vec<u32>>,
+    }
+
+    impl PartialEq for Lazy<'_, Vec<u32>> {
+        fn eq(&self, other: &Self) -> bool {
+            self.force::<Panic>().unwrap() == other.force::<Panic>().unwrap()
+        }
+    }
+
+    #[test]
+    fn lazy_field() {
+        let value = Record {
+            name: String::from("record"),
+            values: Lazy::new((0..64).collect()),
+        };
+
+        to_archived(&value, |archived| {
+            assert_eq!(archived.name, "record");
+            assert_eq!(archived.values.len(), 64);
+        });
+
+        #[cfg(feature = "bytecheck")]
+        {
+            use crate::{lazy::from_bytes_lazy, to_bytes};
+
+            let bytes = to_bytes::<Panic>(&value).unwrap();
+            let deserialized =
+                from_bytes_lazy::<Record<'_>, Panic>(&bytes).unwrap();
+            assert_eq!(deserialized.name, "record");
+            assert!(deserialized.values.get().is_none());
+            assert_eq!(deserialized.values.archived().unwrap().len(), 64);
+
+            // Serializing a lazy value doesn't force it.
+            let reserialized = to_bytes::<Panic>(&deserialized).unwrap();
+            assert!(deserialized.values.get().is_none());
+            assert_eq!(
+                from_bytes_lazy::<Record<'_>, Panic>(&reserialized).unwrap(),
+                value,
+            );
+
+            let values = deserialized.values.into_inner::<Panic>().unwrap();
+            assert_eq!(values, (0..64).collect::<Vec<_>>());
+        }
+    }
+}
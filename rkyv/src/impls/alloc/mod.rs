@@ -1,5 +1,6 @@
 mod boxed;
 mod collections;
+mod lazy;
 mod rc;
 mod string;
 mod vec;
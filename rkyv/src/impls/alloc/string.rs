@@ -39,6 +39,16 @@ where
     fn deserialize(&self, _: &mut D) -> Result<String, D::Error> {
         Ok(self.as_str().to_string())
     }
+
+    fn deserialize_into(
+        &self,
+        target: &mut String,
+        _: &mut D,
+    ) -> Result<(), D::Error> {
+        target.clear();
+        target.push_str(self.as_str());
+        Ok(())
+    }
 }
 
 impl PartialEq<String> for ArchivedString {
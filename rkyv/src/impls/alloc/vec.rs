@@ -38,6 +38,7 @@ impl<T: Serialize<S>, S: Fallible + Allocator + Writer + ?Sized> Serialize<S>
 impl<T, D> Deserialize<Vec<T>, D> for ArchivedVec<T::Archived>
 where
     T: Archive,
+    T::Archived: Deserialize<T, D>,
     [T::Archived]: DeserializeUnsized<[T], D>,
     D: Fallible + ?Sized,
     D::Error: Source,
@@ -56,6 +57,22 @@ where
         }
         unsafe { Ok(Box::<[T]>::from_raw(out).into()) }
     }
+
+    fn deserialize_into(
+        &self,
+        target: &mut Vec<T>,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        target.truncate(self.len());
+        for (item, target) in self.iter().zip(target.iter_mut()) {
+            item.deserialize_into(target, deserializer)?;
+        }
+        target.reserve(self.len() - target.len());
+        for item in self[target.len()..].iter() {
+            target.push(item.deserialize(deserializer)?);
+        }
+        Ok(())
+    }
 }
 
 impl<T: PartialEq<U>, U> PartialEq<Vec<U>> for ArchivedVec<T> {
@@ -79,7 +96,22 @@ impl<T: PartialOrd<U>, U> PartialOrd<Vec<U>> for ArchivedVec<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::test::roundtrip;
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        boxed::Box,
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use core::{cmp::Reverse, pin::Pin};
+
+    use rancor::Panic;
+
+    use crate::{
+        test::{roundtrip, roundtrip_into},
+        to_bytes, Archive, Archived, Serialize,
+    };
 
     #[test]
     fn roundtrip_vec() {
@@ -107,4 +139,135 @@ mod tests {
         roundtrip(&Err::<(), _>(Vec::<i32>::new()));
         roundtrip(&Err::<(), _>(vec![1, 2, 3, 4]));
     }
+
+    #[test]
+    fn deserialize_into_vec() {
+        let mut target = Vec::with_capacity(8);
+        target.push(String::with_capacity(16));
+        let capacity = target.capacity();
+        let string_ptr = target[0].as_ptr();
+
+        roundtrip_into(
+            &vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            &mut target,
+        );
+        assert_eq!(target.capacity(), capacity);
+        assert_eq!(target[0].as_ptr(), string_ptr);
+
+        roundtrip_into(&vec!["d".to_string()], &mut target);
+        assert_eq!(target.capacity(), capacity);
+        assert_eq!(target[0].as_ptr(), string_ptr);
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn archived_vec_reorder() {
+        type T = Vec<String>;
+
+        // Mixes inline and out-of-line strings in a scrambled order.
+        let mut expected = (0..20)
+            .map(|i| (i * 7) % 20)
+            .map(|i| {
+                if i % 3 == 0 {
+                    i.to_string()
+                } else {
+                    format!("{i:02} is too long to be inlined")
+                }
+            })
+            .collect::<T>();
+        let number = |s: &str| s.split(' ').next().unwrap().parse::<u32>();
+        let mut bytes = to_bytes::<Panic>(&expected).unwrap();
+
+        // The buffer is validated after every reorder to check that the
+        // out-of-line data of the strings was moved along with them.
+        fn access_mut(bytes: &mut [u8]) -> Pin<&mut Archived<T>> {
+            crate::access_mut::<Archived<T>, Panic>(bytes).unwrap()
+        }
+        fn access(bytes: &[u8]) -> &Archived<T> {
+            crate::access::<Archived<T>, Panic>(bytes).unwrap()
+        }
+
+        let mut archived = access_mut(&mut bytes);
+        archived.as_mut().swap(0, 19);
+        archived.as_mut().swap(3, 3);
+        expected.swap(0, 19);
+        assert_eq!(access(&bytes).as_slice(), expected.as_slice());
+
+        access_mut(&mut bytes).reverse();
+        expected.reverse();
+        assert_eq!(access(&bytes).as_slice(), expected.as_slice());
+
+        access_mut(&mut bytes).sort_unstable_by(|a, b| a.cmp(b));
+        expected.sort_unstable();
+        assert_eq!(access(&bytes).as_slice(), expected.as_slice());
+
+        access_mut(&mut bytes).sort_by(|a, b| b.cmp(a));
+        expected.sort_by(|a, b| b.cmp(a));
+        assert_eq!(access(&bytes).as_slice(), expected.as_slice());
+
+        access_mut(&mut bytes).sort_by_key(|s| s.len());
+        expected.sort_by_key(|s| s.len());
+        assert_eq!(access(&bytes).as_slice(), expected.as_slice());
+
+        access_mut(&mut bytes)
+            .sort_unstable_by_key(|s| number(s.as_str()).unwrap());
+        expected.sort_unstable_by_key(|s| number(s).unwrap());
+        assert_eq!(access(&bytes).as_slice(), expected.as_slice());
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn archived_vec_reorder_derived() {
+        #[derive(Archive, Serialize, Debug, PartialEq)]
+        #[archive(crate, relocate, compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Node {
+            name: String,
+            children: Vec<u32>,
+            weight: Option<Box<u32>>,
+            kind: Kind,
+        }
+
+        #[derive(Archive, Serialize, Debug, PartialEq)]
+        #[archive(crate, relocate, compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        enum Kind {
+            Leaf,
+            Named { label: String },
+            Pair(Box<u32>, String),
+        }
+
+        type T = Vec<Node>;
+
+        // Keeps the length of every string a multiple of four so that the
+        // out-of-line data of each node stays aligned in any order.
+        let pad = |mut s: String| {
+            while s.len() % 4 != 0 {
+                s.push('.');
+            }
+            s
+        };
+        let mut expected = (0..10u32)
+            .map(|i| Node {
+                name: pad(format!("node {i} is out-of-line")),
+                children: (0..i).collect(),
+                weight: (i % 2 == 0).then(|| Box::new(i * 10)),
+                kind: match i % 3 {
+                    0 => Kind::Leaf,
+                    1 => Kind::Named {
+                        label: pad(format!("label {i} is also out-of-line")),
+                    },
+                    _ => Kind::Pair(Box::new(i), pad(format!("pair {i}"))),
+                },
+            })
+            .collect::<T>();
+        let mut bytes = to_bytes::<Panic>(&expected).unwrap();
+
+        crate::access_mut::<Archived<T>, Panic>(&mut bytes)
+            .unwrap()
+            .sort_unstable_by_key(|node| Reverse(node.children.len()));
+        expected.reverse();
+        let archived = crate::access::<Archived<T>, Panic>(&bytes).unwrap();
+        assert_eq!(archived.as_slice(), expected.as_slice());
+    }
 }
@@ -4,10 +4,11 @@ use alloc::{
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
     rc::Rc,
+    string::String,
     sync::Arc,
     vec::Vec,
 };
-use core::marker::PhantomData;
+use core::{hash::Hash, marker::PhantomData};
 #[cfg(feature = "std")]
 use std::{
     borrow::Cow,
@@ -21,14 +22,22 @@ use rancor::{Fallible, Source};
 
 use crate::{
     boxed::{ArchivedBox, BoxResolver},
-    collections::util::{Entry, EntryAdapter},
+    collections::{
+        bitmap::{ArchivedBitmap, BitmapResolver},
+        phf::{ArchivedPhfMap, ArchivedPhfSet, PhfMapResolver, PhfSetResolver},
+        sorted_vec_map::{ArchivedSortedVecMap, SortedVecMapResolver},
+        sorted_vec_set::{ArchivedSortedVecSet, SortedVecSetResolver},
+        trie::{ArchivedTrieMap, TrieMapResolver},
+        util::{Entry, EntryAdapter},
+    },
     niche::option_box::{ArchivedOptionBox, OptionBoxResolver},
     ser::{Allocator, Writer},
     string::{ArchivedString, StringResolver},
     vec::{ArchivedVec, VecResolver},
     with::{
-        ArchiveWith, AsBox, AsOwned, AsVec, DeserializeWith, InlineAsBox, Map,
-        Niche, SerializeWith, Unshare,
+        ArchiveWith, AsBitmap, AsBox, AsOwned, AsPerfectHash, AsSortedVec,
+        AsTrie, AsVec, DeserializeWith, InlineAsBox, Map, Niche, SerializeWith,
+        Unshare,
     },
     Archive, ArchiveUnsized, ArchivedMetadata, Deserialize, DeserializeUnsized,
     LayoutRaw, Place, Serialize, SerializeUnsized,
@@ -411,6 +420,312 @@ where
     }
 }
 
+// AsSortedVec
+
+impl<K: Archive, V: Archive> ArchiveWith<BTreeMap<K, V>> for AsSortedVec {
+    type Archived = ArchivedSortedVecMap<K::Archived, V::Archived>;
+    type Resolver = SortedVecMapResolver;
+
+    fn resolve_with(
+        field: &BTreeMap<K, V>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSortedVecMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, V, S> SerializeWith<BTreeMap<K, V>, S> for AsSortedVec
+where
+    K: Serialize<S>,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &BTreeMap<K, V>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedVecMap::serialize_from_ordered_iter(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, D>
+    DeserializeWith<
+        ArchivedSortedVecMap<K::Archived, V::Archived>,
+        BTreeMap<K, V>,
+        D,
+    > for AsSortedVec
+where
+    K: Archive + Ord,
+    K::Archived: Deserialize<K, D>,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSortedVecMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeMap<K, V>, D::Error> {
+        let mut result = BTreeMap::new();
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl<K: Archive> ArchiveWith<BTreeSet<K>> for AsSortedVec {
+    type Archived = ArchivedSortedVecSet<K::Archived>;
+    type Resolver = SortedVecSetResolver;
+
+    fn resolve_with(
+        field: &BTreeSet<K>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSortedVecSet::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, S> SerializeWith<BTreeSet<K>, S> for AsSortedVec
+where
+    K: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &BTreeSet<K>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedVecSet::serialize_from_ordered_iter(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<K, D> DeserializeWith<ArchivedSortedVecSet<K::Archived>, BTreeSet<K>, D>
+    for AsSortedVec
+where
+    K: Archive + Ord,
+    K::Archived: Deserialize<K, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSortedVecSet<K::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeSet<K>, D::Error> {
+        let mut result = BTreeSet::new();
+        for key in field.iter() {
+            result.insert(key.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// AsTrie
+
+impl<V: Archive> ArchiveWith<BTreeMap<String, V>> for AsTrie {
+    type Archived = ArchivedTrieMap<V::Archived>;
+    type Resolver = TrieMapResolver;
+
+    fn resolve_with(
+        field: &BTreeMap<String, V>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedTrieMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<V, S> SerializeWith<BTreeMap<String, V>, S> for AsTrie
+where
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &BTreeMap<String, V>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedTrieMap::serialize_from_ordered_iter(field.iter(), serializer)
+    }
+}
+
+impl<V, D> DeserializeWith<ArchivedTrieMap<V::Archived>, BTreeMap<String, V>, D>
+    for AsTrie
+where
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedTrieMap<V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeMap<String, V>, D::Error> {
+        let mut result = BTreeMap::new();
+        for (key, value) in field.iter() {
+            result.insert(key, value.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// AsBitmap
+
+impl ArchiveWith<BTreeSet<u32>> for AsBitmap {
+    type Archived = ArchivedBitmap;
+    type Resolver = BitmapResolver;
+
+    fn resolve_with(
+        field: &BTreeSet<u32>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedBitmap::resolve_from_len(field.len() as u64, resolver, out);
+    }
+}
+
+impl<S> SerializeWith<BTreeSet<u32>, S> for AsBitmap
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &BTreeSet<u32>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBitmap::serialize_from_ordered_iter(
+            field.iter().copied(),
+            serializer,
+        )
+    }
+}
+
+impl<D> DeserializeWith<ArchivedBitmap, BTreeSet<u32>, D> for AsBitmap
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedBitmap,
+        _: &mut D,
+    ) -> Result<BTreeSet<u32>, D::Error> {
+        Ok(field.iter().collect())
+    }
+}
+
+// AsPerfectHash
+
+impl<K: Archive, V: Archive> ArchiveWith<BTreeMap<K, V>> for AsPerfectHash {
+    type Archived = ArchivedPhfMap<K::Archived, V::Archived>;
+    type Resolver = PhfMapResolver;
+
+    fn resolve_with(
+        field: &BTreeMap<K, V>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPhfMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, V, S> SerializeWith<BTreeMap<K, V>, S> for AsPerfectHash
+where
+    K: Serialize<S> + Hash + Eq,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &BTreeMap<K, V>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPhfMap::<K::Archived, V::Archived>::serialize_from_iter(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, D>
+    DeserializeWith<ArchivedPhfMap<K::Archived, V::Archived>, BTreeMap<K, V>, D>
+    for AsPerfectHash
+where
+    K: Archive + Ord,
+    K::Archived: Deserialize<K, D>,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPhfMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeMap<K, V>, D::Error> {
+        let mut result = BTreeMap::new();
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl<K: Archive> ArchiveWith<BTreeSet<K>> for AsPerfectHash {
+    type Archived = ArchivedPhfSet<K::Archived>;
+    type Resolver = PhfSetResolver;
+
+    fn resolve_with(
+        field: &BTreeSet<K>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPhfSet::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, S> SerializeWith<BTreeSet<K>, S> for AsPerfectHash
+where
+    K: Serialize<S> + Hash + Eq,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &BTreeSet<K>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPhfSet::<K::Archived>::serialize_from_iter(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<K, D> DeserializeWith<ArchivedPhfSet<K::Archived>, BTreeSet<K>, D>
+    for AsPerfectHash
+where
+    K: Archive + Ord,
+    K::Archived: Deserialize<K, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPhfSet<K::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeSet<K>, D::Error> {
+        let mut result = BTreeSet::new();
+        for key in field.iter() {
+            result.insert(key.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
 // Niche
 
 impl<T> ArchiveWith<Option<Box<T>>> for Niche
@@ -538,8 +853,21 @@ where
 
 #[cfg(test)]
 mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        collections::{BTreeMap, BTreeSet},
+        string::{String, ToString},
+        vec::Vec,
+    };
+    use core::ops::Bound;
+    #[cfg(feature = "std")]
+    use std::collections::{BTreeMap, BTreeSet};
+
     use crate::{
-        test::roundtrip, with::Niche, Archive, Deserialize, Serialize,
+        primitive::ArchivedU32,
+        test::{roundtrip, roundtrip_with},
+        with::{AsBitmap, AsTrie, Niche},
+        Archive, Deserialize, Serialize,
     };
 
     #[derive(Debug, Archive, Deserialize, Serialize, PartialEq)]
@@ -576,4 +904,218 @@ mod tests {
         });
         roundtrip(&HasNiche { inner: None });
     }
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[archive(crate)]
+    #[archive_attr(derive(Debug))]
+    #[cfg_attr(
+        feature = "bytecheck",
+        archive_attr(derive(bytecheck::CheckBytes))
+    )]
+    struct Words {
+        #[with(AsTrie)]
+        words: BTreeMap<String, u32>,
+    }
+
+    fn words() -> Words {
+        let mut words = BTreeMap::new();
+        for (i, word) in [
+            "",
+            "a",
+            "app",
+            "apple",
+            "application",
+            "apply",
+            "band",
+            "bandana",
+            "banana",
+            "can",
+            "über",
+        ]
+        .iter()
+        .enumerate()
+        {
+            words.insert(word.to_string(), i as u32);
+        }
+        for i in 0..300 {
+            words.insert(i.to_string(), i);
+        }
+        Words { words }
+    }
+
+    #[test]
+    fn roundtrip_trie() {
+        roundtrip_with(&words(), |value, archived| {
+            let expected = |iter: &mut dyn Iterator<Item = (&String, &u32)>| {
+                iter.map(|(k, v)| (k.clone(), *v)).collect::<Vec<_>>()
+            };
+            let actual = |iter: &mut dyn Iterator<Item = (String, &_)>| {
+                iter.map(|(k, v): (_, &ArchivedU32)| (k, v.to_native()))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(archived.words.len(), value.words.len());
+            for (k, v) in value.words.iter() {
+                assert_eq!(archived.words.get(k).unwrap(), v);
+            }
+            for missing in ["ap", "appl", "applications", "bandanas", "ü"] {
+                assert!(archived.words.get(missing).is_none());
+            }
+            assert_eq!(
+                actual(&mut archived.words.iter()),
+                expected(&mut value.words.iter()),
+            );
+
+            for prefix in ["", "a", "ap", "appl", "ban", "1", "29", "x", "ü"] {
+                assert_eq!(
+                    actual(&mut archived.words.starts_with(prefix)),
+                    expected(
+                        &mut value
+                            .words
+                            .iter()
+                            .filter(|(k, _)| k.starts_with(prefix))
+                    ),
+                    "prefix {prefix:?}",
+                );
+            }
+
+            let keys =
+                ["", "0", "1", "15", "150", "a", "ap", "apple", "c", "z"];
+            let bounds = keys
+                .iter()
+                .flat_map(|k| [Bound::Included(*k), Bound::Excluded(*k)]);
+            for start in bounds.clone().chain([Bound::Unbounded]) {
+                for end in bounds.clone().chain([Bound::Unbounded]) {
+                    let range = (start, end);
+                    let expected = match (start, end) {
+                        (
+                            Bound::Included(s),
+                            Bound::Included(e) | Bound::Excluded(e),
+                        ) if s > e => Vec::new(),
+                        (
+                            Bound::Excluded(s),
+                            Bound::Included(e) | Bound::Excluded(e),
+                        ) if s >= e => Vec::new(),
+                        _ => expected(&mut value.words.range::<str, _>(range)),
+                    };
+                    assert_eq!(
+                        actual(&mut archived.words.range::<str, _>(range)),
+                        expected,
+                        "range {range:?}",
+                    );
+                }
+            }
+        });
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn validate_trie() {
+        use rancor::Panic;
+
+        use crate::{access, Archived};
+
+        let value = words();
+        let bytes = crate::to_bytes::<Panic>(&value).unwrap();
+        let archived = access::<Archived<Words>, Panic>(bytes.as_ref())
+            .expect("failed to validate archived trie");
+        assert_eq!(archived.words.get("application").unwrap(), &4);
+
+        let empty = Words {
+            words: BTreeMap::new(),
+        };
+        let bytes = crate::to_bytes::<Panic>(&empty).unwrap();
+        let archived = access::<Archived<Words>, Panic>(bytes.as_ref())
+            .expect("failed to validate empty archived trie");
+        assert!(archived.words.is_empty());
+        assert!(archived.words.get("").is_none());
+    }
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[archive(crate)]
+    #[archive_attr(derive(Debug))]
+    #[cfg_attr(
+        feature = "bytecheck",
+        archive_attr(derive(bytecheck::CheckBytes))
+    )]
+    struct Ids {
+        #[with(AsBitmap)]
+        ids: BTreeSet<u32>,
+    }
+
+    fn ids(seed: u32) -> Ids {
+        let mut ids = BTreeSet::new();
+        // Sparse values become array containers.
+        ids.extend((0..1000).map(|i| i * 37 + seed));
+        // Dense, scattered values become bitmap containers.
+        ids.extend((0..20_000).map(|i| (1 << 16) + i * 3 + seed));
+        // Long runs become run containers.
+        ids.extend((5 << 16) + seed..(7 << 16) + 100);
+        ids.extend((9 << 16..10 << 16).step_by(2 + seed as usize));
+        ids.insert(u32::MAX - seed);
+        Ids { ids }
+    }
+
+    #[test]
+    fn roundtrip_bitmap() {
+        roundtrip_with(&ids(0), |value, archived| {
+            let archived = &archived.ids;
+            assert_eq!(archived.len(), value.ids.len() as u64);
+            assert!(archived.iter().eq(value.ids.iter().copied()));
+
+            let probes = [0, 37, 38, 1 << 16, (1 << 16) + 4, 6 << 16, u32::MAX];
+            let sampled = value.ids.iter().copied().step_by(97);
+            for probe in probes.into_iter().chain(sampled) {
+                assert_eq!(
+                    archived.contains(probe),
+                    value.ids.contains(&probe),
+                    "contains {probe}",
+                );
+                assert_eq!(
+                    archived.rank(probe),
+                    value.ids.range(..=probe).count() as u64,
+                    "rank {probe}",
+                );
+            }
+        });
+
+        let left = ids(0);
+        let right = ids(1);
+        crate::test::to_archived(&left, |archived_left| {
+            crate::test::to_archived(&right, |archived_right| {
+                let and: Vec<u32> = archived_left.ids.and(&archived_right.ids);
+                let expected = left.ids.intersection(&right.ids).copied();
+                assert!(and.into_iter().eq(expected));
+
+                let or: Vec<u32> = archived_left.ids.or(&archived_right.ids);
+                let expected = left.ids.union(&right.ids).copied();
+                assert!(or.into_iter().eq(expected));
+            });
+        });
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn validate_bitmap() {
+        use rancor::Panic;
+
+        use crate::{access, Archived};
+
+        for value in [ids(0), ids(3)] {
+            let bytes = crate::to_bytes::<Panic>(&value).unwrap();
+            let archived = access::<Archived<Ids>, Panic>(bytes.as_ref())
+                .expect("failed to validate archived bitmap");
+            assert_eq!(archived.ids.len(), value.ids.len() as u64);
+        }
+
+        let empty = Ids {
+            ids: BTreeSet::new(),
+        };
+        let bytes = crate::to_bytes::<Panic>(&empty).unwrap();
+        let archived = access::<Archived<Ids>, Panic>(bytes.as_ref())
+            .expect("failed to validate empty archived bitmap");
+        assert!(archived.ids.is_empty());
+        assert!(!archived.ids.contains(0));
+        assert_eq!(archived.ids.rank(u32::MAX), 0);
+    }
 }
@@ -0,0 +1,146 @@
+use core::{alloc::Layout, fmt, ptr::NonNull};
+
+use bumpalo::{
+    boxed::Box as BumpBox,
+    collections::{String as BumpString, Vec as BumpVec},
+    Bump,
+};
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    boxed::ArchivedBox,
+    de::{ArenaAllocator, BumpArena},
+    string::ArchivedString,
+    vec::ArchivedVec,
+    ArchivePointee, Deserialize,
+};
+
+#[derive(Debug)]
+struct ArenaExhausted {
+    layout: Layout,
+}
+
+impl fmt::Display for ArenaExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to allocate {} bytes with alignment {} from bump arena",
+            self.layout.size(),
+            self.layout.align(),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArenaExhausted {}
+
+unsafe impl<'a, E: Source> ArenaAllocator<'a, E> for &'a Bump {
+    unsafe fn alloc_arena(&mut self, layout: Layout) -> Result<NonNull<u8>, E> {
+        match self.try_alloc_layout(layout) {
+            Ok(ptr) => Ok(ptr),
+            Err(_) => fail!(ArenaExhausted { layout }),
+        }
+    }
+}
+
+impl<'a> BumpArena<'a> for &'a Bump {
+    fn bump(&self) -> &'a Bump {
+        self
+    }
+}
+
+impl<'a, T, U, D> Deserialize<BumpBox<'a, U>, D> for ArchivedBox<T>
+where
+    T: ArchivePointee + Deserialize<U, D>,
+    D: BumpArena<'a> + Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<BumpBox<'a, U>, D::Error> {
+        let value = self.get().deserialize(deserializer)?;
+        Ok(BumpBox::new_in(value, deserializer.bump()))
+    }
+}
+
+impl<'a, T, U, D> Deserialize<BumpVec<'a, U>, D> for ArchivedVec<T>
+where
+    T: Deserialize<U, D>,
+    D: BumpArena<'a> + Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<BumpVec<'a, U>, D::Error> {
+        let mut result =
+            BumpVec::with_capacity_in(self.len(), deserializer.bump());
+        for item in self.as_slice() {
+            result.push(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+impl<'a, D> Deserialize<BumpString<'a>, D> for ArchivedString
+where
+    D: BumpArena<'a> + Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<BumpString<'a>, D::Error> {
+        Ok(BumpString::from_str_in(self.as_str(), deserializer.bump()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{boxed::Box, string::ToString, vec};
+
+    use bumpalo::{
+        boxed::Box as BumpBox,
+        collections::{String as BumpString, Vec as BumpVec},
+        Bump,
+    };
+    use rancor::{Panic, Strategy};
+
+    use crate::{test::to_archived, Deserialize};
+
+    /// Returns whether `ptr` was allocated from `bump`.
+    fn in_bump(bump: &Bump, ptr: *const u8) -> bool {
+        // SAFETY: The chunks are only used for their address ranges.
+        unsafe { bump.iter_allocated_chunks_raw() }.any(|(start, len)| {
+            (start as usize..start as usize + len).contains(&(ptr as usize))
+        })
+    }
+
+    #[test]
+    fn deserialize_bumpalo() {
+        let bump = Bump::new();
+        let mut arena = &bump;
+        let deserializer = Strategy::<_, Panic>::wrap(&mut arena);
+
+        let value = vec!["hello".to_string(), "world".to_string()];
+        to_archived(&value, |archived| {
+            let deserialized: BumpVec<'_, BumpString<'_>> =
+                archived.deserialize(deserializer).unwrap();
+            assert_eq!(deserialized, ["hello", "world"]);
+            assert!(in_bump(&bump, deserialized.as_ptr().cast()));
+            assert!(deserialized.iter().all(|s| in_bump(&bump, s.as_ptr())));
+
+            let deserialized: &[&str] =
+                archived.deserialize(deserializer).unwrap();
+            assert_eq!(deserialized, ["hello", "world"]);
+            assert!(in_bump(&bump, deserialized.as_ptr().cast()));
+            assert!(deserialized.iter().all(|s| in_bump(&bump, s.as_ptr())));
+        });
+
+        to_archived(&Box::new(42u32), |archived| {
+            let deserialized: BumpBox<'_, u32> =
+                archived.deserialize(deserializer).unwrap();
+            assert_eq!(*deserialized, 42);
+            assert!(in_bump(&bump, (&*deserialized as *const u32).cast()));
+        });
+    }
+}
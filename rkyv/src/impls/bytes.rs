@@ -43,6 +43,14 @@ where
     }
 }
 
+// SAFETY: `Bytes` is immutable, and clones of it share the same buffer.
+#[cfg(feature = "alloc")]
+unsafe impl crate::util::StableBytes for Bytes {
+    fn stable_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(not(feature = "std"))]
@@ -0,0 +1,276 @@
+use ptr_meta::Pointee;
+use rancor::{Fallible, ResultExt as _, Source};
+
+use crate::{
+    boxed::ArchivedBox,
+    de::{ArenaAllocator, ArenaSharing, ErasedPtr, Metadata},
+    rc::{ArchivedRc, ArchivedRcWeak},
+    string::ArchivedString,
+    vec::ArchivedVec,
+    ArchivePointee, Deserialize, DeserializeUnsized, LayoutRaw,
+};
+
+/// Allocates space in the arena for a value with the given metadata.
+fn alloc_in_arena<'a, T, D>(
+    deserializer: &mut D,
+    metadata: T::Metadata,
+) -> Result<*mut T, D::Error>
+where
+    T: LayoutRaw + Pointee + ?Sized,
+    D: ArenaAllocator<'a> + Fallible + ?Sized,
+    D::Error: Source,
+{
+    let layout = T::layout_raw(metadata).into_error()?;
+    let data_address = if layout.size() > 0 {
+        // SAFETY: We just checked that `layout` has non-zero size.
+        unsafe { deserializer.alloc_arena(layout)?.as_ptr() }
+    } else {
+        crate::polyfill::dangling(&layout).as_ptr()
+    };
+    Ok(ptr_meta::from_raw_parts_mut(data_address.cast(), metadata))
+}
+
+/// Deserializes an unsized value into memory allocated from the arena.
+fn deserialize_in_arena<'a, T, U, D>(
+    value: &T,
+    deserializer: &mut D,
+) -> Result<&'a U, D::Error>
+where
+    T: DeserializeUnsized<U, D> + ?Sized,
+    U: LayoutRaw + Pointee + ?Sized + 'a,
+    D: ArenaAllocator<'a> + Fallible + ?Sized,
+    D::Error: Source,
+{
+    let out =
+        alloc_in_arena::<U, D>(deserializer, value.deserialize_metadata())?;
+    // SAFETY: `out` is non-null, properly aligned, valid for writes, and was
+    // allocated according to the layout of the deserialized metadata.
+    unsafe {
+        value.deserialize_unsized(deserializer, out)?;
+    }
+    // SAFETY: `out` was allocated from the arena so it is valid for `'a`, and
+    // it was just initialized by `deserialize_unsized`.
+    Ok(unsafe { &*out })
+}
+
+impl<'a, T, U, D> Deserialize<&'a U, D> for ArchivedBox<T>
+where
+    T: DeserializeUnsized<U, D> + ?Sized,
+    U: LayoutRaw + Pointee + ?Sized + 'a,
+    D: ArenaAllocator<'a> + Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<&'a U, D::Error> {
+        deserialize_in_arena(self.get(), deserializer)
+    }
+}
+
+impl<'a, T, U, D> Deserialize<&'a [U], D> for ArchivedVec<T>
+where
+    [T]: DeserializeUnsized<[U], D>,
+    U: 'a,
+    D: ArenaAllocator<'a> + Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<&'a [U], D::Error> {
+        deserialize_in_arena(self.as_slice(), deserializer)
+    }
+}
+
+impl<'a, D> Deserialize<&'a str, D> for ArchivedString
+where
+    str: DeserializeUnsized<str, D>,
+    D: ArenaAllocator<'a> + Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<&'a str, D::Error> {
+        deserialize_in_arena(self.as_str(), deserializer)
+    }
+}
+
+impl<'a, T, U, F, D> Deserialize<&'a U, D> for ArchivedRc<T, F>
+where
+    T: ArchivePointee + DeserializeUnsized<U, D> + ?Sized,
+    U: LayoutRaw + Pointee + ?Sized + 'a,
+    U::Metadata: Into<Metadata>,
+    Metadata: Into<U::Metadata>,
+    D: ArenaSharing<'a> + Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<&'a U, D::Error> {
+        let address = self.get() as *const T as *const () as usize;
+        if let Some(ptr) = deserializer.get_arena_shared(address) {
+            // SAFETY: The shared pointer for `address` was added below after
+            // deserializing the same value, so it points to an initialized `U`
+            // which was allocated from the arena.
+            return Ok(unsafe { &*ptr.downcast_unchecked::<U>() });
+        }
+
+        let value = deserialize_in_arena(self.get(), deserializer)?;
+        // SAFETY: `value` was allocated from the arena and was just
+        // initialized.
+        unsafe {
+            deserializer.add_arena_shared(
+                address,
+                ErasedPtr::new(value as *const U as *mut U),
+            )?;
+        }
+        Ok(value)
+    }
+}
+
+impl<'a, T, U, F, D> Deserialize<Option<&'a U>, D> for ArchivedRcWeak<T, F>
+where
+    ArchivedRc<T, F>: Deserialize<&'a U, D>,
+    T: ArchivePointee + ?Sized,
+    U: ?Sized + 'a,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<Option<&'a U>, D::Error> {
+        match self {
+            ArchivedRcWeak::None => Ok(None),
+            ArchivedRcWeak::Some(r) => Ok(Some(r.deserialize(deserializer)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        alloc::Layout, marker::PhantomData, mem::MaybeUninit, ptr::NonNull,
+    };
+
+    use rancor::{Panic, Strategy};
+
+    use crate::{de::ArenaAllocator, test::to_archived, Archived, Deserialize};
+
+    /// A minimal bump arena over a fixed buffer.
+    struct BufferArena<'a> {
+        ptr: *mut u8,
+        len: usize,
+        pos: usize,
+        _phantom: PhantomData<&'a mut [MaybeUninit<u8>]>,
+    }
+
+    impl<'a> BufferArena<'a> {
+        fn new(buffer: &'a mut [MaybeUninit<u8>]) -> Self {
+            Self {
+                ptr: buffer.as_mut_ptr().cast(),
+                len: buffer.len(),
+                pos: 0,
+                _phantom: PhantomData,
+            }
+        }
+    }
+
+    unsafe impl<'a, E> ArenaAllocator<'a, E> for BufferArena<'a> {
+        unsafe fn alloc_arena(
+            &mut self,
+            layout: Layout,
+        ) -> Result<NonNull<u8>, E> {
+            let start = (self.ptr as usize + self.pos)
+                .next_multiple_of(layout.align())
+                - self.ptr as usize;
+            assert!(start + layout.size() <= self.len, "arena exhausted");
+            self.pos = start + layout.size();
+            Ok(unsafe { NonNull::new_unchecked(self.ptr.add(start)) })
+        }
+    }
+
+    #[test]
+    fn deserialize_into_arena() {
+        #[cfg(not(feature = "std"))]
+        use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+        let mut buffer = [MaybeUninit::uninit(); 512];
+        let start = buffer.as_ptr() as usize;
+        let end = start + buffer.len();
+        let in_arena = |ptr: *const u8| (start..end).contains(&(ptr as usize));
+        let mut arena = BufferArena::new(&mut buffer);
+        let arena = Strategy::<_, Panic>::wrap(&mut arena);
+
+        to_archived(&vec![1u32, 2, 3], |archived| {
+            let deserialized: &[u32] =
+                Deserialize::<&[u32], _>::deserialize(archived, arena).unwrap();
+            assert_eq!(deserialized, [1, 2, 3]);
+            assert!(in_arena(deserialized.as_ptr().cast()));
+        });
+
+        to_archived(&vec!["a".to_string(), "b".to_string()], |archived| {
+            let deserialized: &[&str] =
+                Deserialize::<&[&str], _>::deserialize(archived, arena)
+                    .unwrap();
+            assert_eq!(deserialized, ["a", "b"]);
+            assert!(in_arena(deserialized.as_ptr().cast()));
+            assert!(deserialized.iter().all(|s| in_arena(s.as_ptr())));
+        });
+
+        to_archived(&String::from("hello arena"), |archived| {
+            let deserialized: &str =
+                Deserialize::<&str, _>::deserialize(archived, arena).unwrap();
+            assert_eq!(deserialized, "hello arena");
+            assert!(in_arena(deserialized.as_ptr()));
+        });
+
+        to_archived(&Box::new(42u64), |archived| {
+            let deserialized: &u64 =
+                Deserialize::<&u64, _>::deserialize(archived, arena).unwrap();
+            assert_eq!(*deserialized, 42);
+            assert!(in_arena((deserialized as *const u64).cast()));
+        });
+
+        to_archived(&vec![(); 4], |archived: &Archived<Vec<()>>| {
+            let deserialized: &[()] =
+                Deserialize::<&[()], _>::deserialize(archived, arena).unwrap();
+            assert_eq!(deserialized.len(), 4);
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn deserialize_shared_into_arena() {
+        #[cfg(not(feature = "std"))]
+        use alloc::{rc::Rc, sync::Arc, vec};
+        #[cfg(feature = "std")]
+        use std::{rc::Rc, sync::Arc};
+
+        use crate::de::WithArena;
+
+        let mut buffer = [MaybeUninit::uninit(); 512];
+        let start = buffer.as_ptr() as usize;
+        let end = start + buffer.len();
+        let in_arena = |ptr: *const u8| (start..end).contains(&(ptr as usize));
+        let mut deserializer =
+            WithArena::new(BufferArena::new(&mut buffer), ());
+        let deserializer = Strategy::<_, Panic>::wrap(&mut deserializer);
+
+        let shared = Rc::<str>::from("shared");
+        let value = vec![shared.clone(), Rc::from("other"), shared];
+        to_archived(&value, |archived| {
+            let deserialized: &[&str] =
+                Deserialize::<&[&str], _>::deserialize(archived, deserializer)
+                    .unwrap();
+            assert_eq!(deserialized, ["shared", "other", "shared"]);
+            assert!(deserialized.iter().all(|s| in_arena(s.as_ptr())));
+            assert!(core::ptr::eq(deserialized[0], deserialized[2]));
+            assert!(!core::ptr::eq(deserialized[0], deserialized[1]));
+        });
+
+        let shared = Arc::<[u32]>::from(vec![1, 2, 3]);
+        let value = vec![shared.clone(), shared];
+        to_archived(&value, |archived| {
+            let deserialized: &[&[u32]] =
+                Deserialize::<&[&[u32]], _>::deserialize(
+                    archived,
+                    deserializer,
+                )
+                .unwrap();
+            assert_eq!(deserialized, [[1, 2, 3], [1, 2, 3]]);
+            assert!(in_arena(deserialized[0].as_ptr().cast()));
+            assert!(core::ptr::eq(deserialized[0], deserialized[1]));
+        });
+    }
+}
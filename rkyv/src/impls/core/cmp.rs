@@ -0,0 +1,63 @@
+use core::cmp::Reverse;
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{cmp::ArchivedReverse, Archive, Deserialize, Place, Serialize};
+
+impl<T: Archive> Archive for Reverse<T> {
+    type Archived = ArchivedReverse<T::Archived>;
+    type Resolver = T::Resolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedReverse(value) = out);
+        self.0.resolve(resolver, value);
+    }
+}
+
+impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Reverse<T> {
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Reverse<T>, D> for ArchivedReverse<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<Reverse<T>, D::Error> {
+        Ok(Reverse(self.0.deserialize(deserializer)?))
+    }
+}
+
+impl<T, U: PartialEq<T>> PartialEq<Reverse<T>> for ArchivedReverse<U> {
+    fn eq(&self, other: &Reverse<T>) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Reverse;
+
+    use crate::{cmp::ArchivedReverse, test::roundtrip};
+
+    #[test]
+    fn roundtrip_reverse() {
+        roundtrip(&Reverse(42i32));
+    }
+
+    #[test]
+    fn archived_reverse_order() {
+        assert!(ArchivedReverse(1) > ArchivedReverse(2));
+        assert_eq!(ArchivedReverse(3).max(ArchivedReverse(4)).0, 3);
+    }
+}
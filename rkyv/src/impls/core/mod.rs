@@ -2,6 +2,7 @@ use core::{
     alloc::{Layout, LayoutError},
     cell::{Cell, UnsafeCell},
     mem::ManuallyDrop,
+    pin::Pin,
     ptr::{self, addr_of_mut},
     str,
 };
@@ -14,11 +15,14 @@ use crate::{
     ser::{Allocator, Writer, WriterExt as _},
     tuple::*,
     Archive, ArchivePointee, ArchiveUnsized, ArchivedMetadata,
-    CopyOptimization, Deserialize, DeserializeUnsized, LayoutRaw, Place,
-    Portable, Serialize, SerializeUnsized,
+    CopyOptimization, Deserialize, DeserializeUnsized, Extent, LayoutRaw,
+    Place, Portable, Relocate, Serialize, SerializeUnsized,
 };
 
+mod arena;
+mod cmp;
 mod net;
+mod num;
 mod ops;
 mod option;
 mod primitive;
@@ -99,6 +103,15 @@ where
     }
 
     fn deserialize_metadata(&self) -> <T as Pointee>::Metadata {}
+
+    fn deserialize_unsized_into(
+        &self,
+        target: &mut T,
+        deserializer: &mut D,
+    ) -> Result<bool, D::Error> {
+        self.deserialize_into(target, deserializer)?;
+        Ok(true)
+    }
 }
 
 macro_rules! impl_tuple {
@@ -343,12 +356,39 @@ where
     fn deserialize_metadata(&self) -> <[U] as Pointee>::Metadata {
         ptr_meta::metadata(self)
     }
+
+    fn deserialize_unsized_into(
+        &self,
+        target: &mut [U],
+        deserializer: &mut D,
+    ) -> Result<bool, D::Error> {
+        if self.len() != target.len() {
+            return Ok(false);
+        }
+        for (item, target) in self.iter().zip(target.iter_mut()) {
+            item.deserialize_into(target, deserializer)?;
+        }
+        Ok(true)
+    }
 }
 
 /// `str`
 
 unsafe impl Portable for str {}
 
+// SAFETY: `str` doesn't contain any relative pointers.
+unsafe impl Relocate for str {
+    fn extent(&self) -> Option<Extent> {
+        None
+    }
+
+    fn can_relocate(&self, _: isize) -> bool {
+        true
+    }
+
+    unsafe fn relocate(self: Pin<&mut Self>, _: isize) {}
+}
+
 impl ArchiveUnsized for str {
     type Archived = str;
 
@@ -402,6 +442,22 @@ impl<D: Fallible + ?Sized> DeserializeUnsized<str, D> for str {
     fn deserialize_metadata(&self) -> <str as Pointee>::Metadata {
         ptr_meta::metadata(self)
     }
+
+    fn deserialize_unsized_into(
+        &self,
+        target: &mut str,
+        _: &mut D,
+    ) -> Result<bool, D::Error> {
+        if self.len() != target.len() {
+            return Ok(false);
+        }
+        // SAFETY: `self` is a valid `str` with the same length as `target`, so
+        // `target` remains valid UTF-8 after the bytes are copied.
+        unsafe {
+            target.as_bytes_mut().copy_from_slice(self.as_bytes());
+        }
+        Ok(true)
+    }
 }
 
 // `ManuallyDrop`
@@ -0,0 +1,110 @@
+use core::num::{Saturating, Wrapping};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    num::{ArchivedSaturating, ArchivedWrapping},
+    Archive, Deserialize, Place, Serialize,
+};
+
+// Wrapping
+
+impl<T: Archive> Archive for Wrapping<T> {
+    type Archived = ArchivedWrapping<T::Archived>;
+    type Resolver = T::Resolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedWrapping(value) = out);
+        self.0.resolve(resolver, value);
+    }
+}
+
+impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Wrapping<T> {
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Wrapping<T>, D> for ArchivedWrapping<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<Wrapping<T>, D::Error> {
+        Ok(Wrapping(self.0.deserialize(deserializer)?))
+    }
+}
+
+impl<T, U: PartialEq<T>> PartialEq<Wrapping<T>> for ArchivedWrapping<U> {
+    fn eq(&self, other: &Wrapping<T>) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+
+// Saturating
+
+impl<T: Archive> Archive for Saturating<T> {
+    type Archived = ArchivedSaturating<T::Archived>;
+    type Resolver = T::Resolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedSaturating(value) = out);
+        self.0.resolve(resolver, value);
+    }
+}
+
+impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Saturating<T> {
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Saturating<T>, D> for ArchivedSaturating<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<Saturating<T>, D::Error> {
+        Ok(Saturating(self.0.deserialize(deserializer)?))
+    }
+}
+
+impl<T, U: PartialEq<T>> PartialEq<Saturating<T>> for ArchivedSaturating<U> {
+    fn eq(&self, other: &Saturating<T>) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::{Saturating, Wrapping};
+
+    use crate::test::roundtrip;
+
+    #[test]
+    fn roundtrip_wrapping() {
+        roundtrip(&Wrapping(12345u32));
+        roundtrip(&Wrapping(-1i64));
+    }
+
+    #[test]
+    fn roundtrip_saturating() {
+        roundtrip(&Saturating(255u8));
+        roundtrip(&Saturating(i16::MIN));
+    }
+}
@@ -433,6 +433,14 @@ mod tests {
         roundtrip(&(..=100u8));
     }
 
+    #[test]
+    fn roundtrip_char_ranges() {
+        roundtrip(&('a'..'k'));
+        roundtrip(&('\0'..=char::MAX));
+        roundtrip(&('α'..));
+        roundtrip(&(..='🦀'));
+    }
+
     #[test]
     fn roundtrip_bound() {
         roundtrip(&Bound::Included(100u8));
@@ -84,6 +84,26 @@ where
             ArchivedOption::None => None,
         })
     }
+
+    fn deserialize_into(
+        &self,
+        target: &mut Option<T>,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        match (self, target) {
+            (ArchivedOption::Some(value), Some(target)) => {
+                value.deserialize_into(target, deserializer)
+            }
+            (ArchivedOption::Some(value), target) => {
+                *target = Some(value.deserialize(deserializer)?);
+                Ok(())
+            }
+            (ArchivedOption::None, target) => {
+                *target = None;
+                Ok(())
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -95,4 +115,76 @@ mod tests {
         roundtrip(&Option::<()>::None);
         roundtrip(&Some(42));
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn deserialize_into_option() {
+        #[cfg(not(feature = "std"))]
+        use alloc::{string::String, vec, vec::Vec};
+
+        use crate::{test::roundtrip_into, Archive, Deserialize, Serialize};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate)]
+        struct Example {
+            name: String,
+            values: Vec<u32>,
+            shape: Shape,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate)]
+        enum Shape {
+            Point,
+            Line(Vec<u32>),
+            Named { name: String },
+        }
+
+        let mut target = None;
+        roundtrip_into(
+            &Some(Example {
+                name: "first".into(),
+                values: vec![1, 2, 3],
+                shape: Shape::Line(vec![4, 5]),
+            }),
+            &mut target,
+        );
+
+        let example = target.as_ref().unwrap();
+        let name_ptr = example.name.as_ptr();
+        let values_ptr = example.values.as_ptr();
+        let Shape::Line(ref line) = example.shape else {
+            unreachable!()
+        };
+        let line_ptr = line.as_ptr();
+
+        roundtrip_into(
+            &Some(Example {
+                name: "second".into(),
+                values: vec![6, 7],
+                shape: Shape::Line(vec![8]),
+            }),
+            &mut target,
+        );
+
+        let example = target.as_ref().unwrap();
+        assert_eq!(example.name.as_ptr(), name_ptr);
+        assert_eq!(example.values.as_ptr(), values_ptr);
+        let Shape::Line(ref line) = example.shape else {
+            unreachable!()
+        };
+        assert_eq!(line.as_ptr(), line_ptr);
+
+        roundtrip_into(
+            &Some(Example {
+                name: "third".into(),
+                values: Vec::new(),
+                shape: Shape::Named {
+                    name: "shape".into(),
+                },
+            }),
+            &mut target,
+        );
+        roundtrip_into(&None, &mut target);
+    }
 }
@@ -5,6 +5,7 @@ use core::{
         NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64,
         NonZeroU8, NonZeroUsize,
     },
+    pin::Pin,
 };
 
 use rancor::Fallible;
@@ -19,19 +20,33 @@ use crate::{
         ArchivedNonZeroU32, ArchivedNonZeroU64, ArchivedNonZeroUsize,
         ArchivedU128, ArchivedU16, ArchivedU32, ArchivedU64, ArchivedUsize,
     },
-    Archive, CopyOptimization, Deserialize, Place, Portable, Serialize,
+    Archive, CopyOptimization, Deserialize, Extent, Place, Portable, Relocate,
+    Serialize,
 };
 
-macro_rules! unsafe_impl_initialized_and_portable {
+// Primitives don't contain any relative pointers, so relocating them is a
+// no-op.
+macro_rules! unsafe_impl_primitive {
     ($($ty:ty),* $(,)?) => {
         $(
             unsafe impl Initialized for $ty {}
             unsafe impl Portable for $ty {}
+            unsafe impl Relocate for $ty {
+                fn extent(&self) -> Option<Extent> {
+                    None
+                }
+
+                fn can_relocate(&self, _: isize) -> bool {
+                    true
+                }
+
+                unsafe fn relocate(self: Pin<&mut Self>, _: isize) {}
+            }
         )*
     };
 }
 
-unsafe_impl_initialized_and_portable! {
+unsafe_impl_primitive! {
     (),
     bool,
     i8,
@@ -119,6 +134,46 @@ unsafe_impl_initialized_and_portable! {
 unsafe impl<T: Portable, const N: usize> Portable for [T; N] {}
 unsafe impl<T: Portable> Portable for [T] {}
 
+unsafe impl<T: Relocate, const N: usize> Relocate for [T; N] {
+    fn extent(&self) -> Option<Extent> {
+        self.iter().map(T::extent).fold(None, Extent::merge)
+    }
+
+    fn can_relocate(&self, delta: isize) -> bool {
+        self.iter().all(|value| value.can_relocate(delta))
+    }
+
+    unsafe fn relocate(self: Pin<&mut Self>, delta: isize) {
+        // SAFETY: The elements of the array are never moved out of their
+        // pinned locations, and they were all moved along with the array.
+        unsafe {
+            for value in self.get_unchecked_mut() {
+                Pin::new_unchecked(value).relocate(delta);
+            }
+        }
+    }
+}
+
+unsafe impl<T: Relocate> Relocate for [T] {
+    fn extent(&self) -> Option<Extent> {
+        self.iter().map(T::extent).fold(None, Extent::merge)
+    }
+
+    fn can_relocate(&self, delta: isize) -> bool {
+        self.iter().all(|value| value.can_relocate(delta))
+    }
+
+    unsafe fn relocate(self: Pin<&mut Self>, delta: isize) {
+        // SAFETY: The elements of the slice are never moved out of their
+        // pinned locations, and they were all moved along with the slice.
+        unsafe {
+            for value in self.get_unchecked_mut() {
+                Pin::new_unchecked(value).relocate(delta);
+            }
+        }
+    }
+}
+
 macro_rules! impl_serialize_noop {
     ($type:ty) => {
         impl<S: Fallible + ?Sized> Serialize<S> for $type {
@@ -244,6 +299,18 @@ impl_multibyte_primitives! {
 
 unsafe impl<T: ?Sized> Portable for PhantomData<T> {}
 
+unsafe impl<T: ?Sized> Relocate for PhantomData<T> {
+    fn extent(&self) -> Option<Extent> {
+        None
+    }
+
+    fn can_relocate(&self, _: isize) -> bool {
+        true
+    }
+
+    unsafe fn relocate(self: Pin<&mut Self>, _: isize) {}
+}
+
 impl<T: ?Sized> Archive for PhantomData<T> {
     const COPY_OPTIMIZATION: CopyOptimization<Self> =
         unsafe { CopyOptimization::enable() };
@@ -270,7 +337,11 @@ impl<T: ?Sized, D: Fallible + ?Sized> Deserialize<PhantomData<T>, D>
 
 // PhantomPinned
 
-unsafe_impl_initialized_and_portable!(PhantomPinned);
+// `PhantomPinned` does not implement `Relocate`. It marks types like relative
+// pointers whose validity depends on their position, so types which contain it
+// have to implement `Relocate` themselves and fix up their own offsets.
+unsafe impl Initialized for PhantomPinned {}
+unsafe impl Portable for PhantomPinned {}
 
 impl Archive for PhantomPinned {
     const COPY_OPTIMIZATION: CopyOptimization<Self> =
@@ -451,14 +522,14 @@ where
 // Atomics
 
 #[cfg(target_has_atomic = "8")]
-unsafe_impl_initialized_and_portable!(
+unsafe_impl_primitive!(
     core::sync::atomic::AtomicBool,
     core::sync::atomic::AtomicI8,
     core::sync::atomic::AtomicU8,
 );
 
 #[cfg(target_has_atomic = "16")]
-unsafe_impl_initialized_and_portable!(
+unsafe_impl_primitive!(
     rend::AtomicI16_be,
     rend::AtomicI16_le,
     rend::AtomicU16_be,
@@ -466,7 +537,7 @@ unsafe_impl_initialized_and_portable!(
 );
 
 #[cfg(target_has_atomic = "32")]
-unsafe_impl_initialized_and_portable!(
+unsafe_impl_primitive!(
     rend::AtomicI32_be,
     rend::AtomicI32_le,
     rend::AtomicU32_be,
@@ -474,7 +545,7 @@ unsafe_impl_initialized_and_portable!(
 );
 
 #[cfg(target_has_atomic = "64")]
-unsafe_impl_initialized_and_portable!(
+unsafe_impl_primitive!(
     rend::AtomicI64_be,
     rend::AtomicI64_le,
     rend::AtomicU64_be,
@@ -1,7 +1,8 @@
 mod atomic;
 
 use core::{
-    cell::{Cell, UnsafeCell},
+    cell::{Cell, OnceCell, RefCell, UnsafeCell},
+    fmt,
     hint::unreachable_unchecked,
     num::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8,
@@ -11,7 +12,7 @@ use core::{
 };
 
 use munge::munge;
-use rancor::Fallible;
+use rancor::{Fallible, OptionExt, Source};
 
 use crate::{
     niche::option_nonzero::{
@@ -26,8 +27,8 @@ use crate::{
     place::Initialized,
     primitive::{FixedNonZeroIsize, FixedNonZeroUsize},
     with::{
-        ArchiveWith, DeserializeWith, Inline, Map, Niche, SerializeWith, Skip,
-        Unsafe,
+        ArchiveWith, DeserializeWith, Get, Inline, Lock, Map, Niche,
+        SerializeWith, Skip, Unsafe,
     },
     Archive, Deserialize, Place, Serialize,
 };
@@ -385,6 +386,182 @@ where
     }
 }
 
+// Get
+
+impl<F: Archive + Copy> ArchiveWith<Cell<F>> for Get {
+    type Archived = F::Archived;
+    type Resolver = F::Resolver;
+
+    fn resolve_with(
+        field: &Cell<F>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        field.get().resolve(resolver, out);
+    }
+}
+
+impl<F, S> SerializeWith<Cell<F>, S> for Get
+where
+    F: Serialize<S> + Copy,
+    S: Fallible + ?Sized,
+{
+    fn serialize_with(
+        field: &Cell<F>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        field.get().serialize(serializer)
+    }
+}
+
+impl<F, T, D> DeserializeWith<F, Cell<T>, D> for Get
+where
+    F: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &F,
+        deserializer: &mut D,
+    ) -> Result<Cell<T>, D::Error> {
+        Ok(Cell::new(field.deserialize(deserializer)?))
+    }
+}
+
+impl<F: Archive> ArchiveWith<OnceCell<F>> for Get {
+    type Archived = ArchivedOption<F::Archived>;
+    type Resolver = Option<F::Resolver>;
+
+    fn resolve_with(
+        field: &OnceCell<F>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        match resolver {
+            None => {
+                let out = unsafe {
+                    out.cast_unchecked::<ArchivedOptionVariantNone>()
+                };
+                munge!(let ArchivedOptionVariantNone(tag) = out);
+                tag.write(ArchivedOptionTag::None);
+            }
+            Some(resolver) => {
+                let out = unsafe {
+                    out.cast_unchecked::<ArchivedOptionVariantSome<
+                        F::Archived,
+                    >>()
+                };
+                munge!(let ArchivedOptionVariantSome(tag, out_value) = out);
+                tag.write(ArchivedOptionTag::Some);
+
+                let value = if let Some(value) = field.get() {
+                    value
+                } else {
+                    unsafe {
+                        unreachable_unchecked();
+                    }
+                };
+
+                value.resolve(resolver, out_value);
+            }
+        }
+    }
+}
+
+impl<F, S> SerializeWith<OnceCell<F>, S> for Get
+where
+    F: Serialize<S>,
+    S: Fallible + ?Sized,
+{
+    fn serialize_with(
+        field: &OnceCell<F>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        field
+            .get()
+            .map(|value| value.serialize(serializer))
+            .transpose()
+    }
+}
+
+impl<F, D> DeserializeWith<ArchivedOption<F::Archived>, OnceCell<F>, D> for Get
+where
+    F: Archive,
+    F::Archived: Deserialize<F, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedOption<F::Archived>,
+        deserializer: &mut D,
+    ) -> Result<OnceCell<F>, D::Error> {
+        match field {
+            ArchivedOption::Some(value) => {
+                Ok(OnceCell::from(value.deserialize(deserializer)?))
+            }
+            ArchivedOption::None => Ok(OnceCell::new()),
+        }
+    }
+}
+
+// Lock
+
+#[derive(Debug)]
+struct AlreadyBorrowed;
+
+impl fmt::Display for AlreadyBorrowed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cell already mutably borrowed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AlreadyBorrowed {}
+
+impl<F: Archive> ArchiveWith<RefCell<F>> for Lock<Unsafe> {
+    type Archived = F::Archived;
+    type Resolver = F::Resolver;
+
+    fn resolve_with(
+        field: &RefCell<F>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        // The cell was borrowed successfully during serialization, so this
+        // only panics if it was mutably borrowed again before resolving.
+        field.borrow().resolve(resolver, out);
+    }
+}
+
+impl<F, S> SerializeWith<RefCell<F>, S> for Lock<Unsafe>
+where
+    F: Serialize<S>,
+    S: Fallible + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &RefCell<F>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        field
+            .try_borrow()
+            .ok()
+            .into_trace(AlreadyBorrowed)?
+            .serialize(serializer)
+    }
+}
+
+impl<F, T, D> DeserializeWith<F, RefCell<T>, D> for Lock<Unsafe>
+where
+    F: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &F,
+        deserializer: &mut D,
+    ) -> Result<RefCell<T>, D::Error> {
+        Ok(RefCell::new(field.deserialize(deserializer)?))
+    }
+}
+
 // Skip
 
 impl<F> ArchiveWith<F> for Skip {
@@ -405,3 +582,74 @@ impl<F: Default, D: Fallible + ?Sized> DeserializeWith<(), F, D> for Skip {
         Ok(Default::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::cell::{Cell, OnceCell, RefCell};
+
+    use crate::{
+        test::roundtrip_with,
+        with::{Get, Lock, Unsafe},
+        Archive, Deserialize, Serialize,
+    };
+
+    #[test]
+    fn roundtrip_cell() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(Get)]
+            value: Cell<i32>,
+        }
+
+        roundtrip_with(
+            &Test {
+                value: Cell::new(10),
+            },
+            |a, b| assert_eq!(b.value, a.value.get()),
+        );
+    }
+
+    #[test]
+    fn roundtrip_once_cell() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(Get)]
+            value: OnceCell<i32>,
+        }
+
+        roundtrip_with(
+            &Test {
+                value: OnceCell::from(10),
+            },
+            |a, b| assert_eq!(b.value, a.value.get().copied()),
+        );
+        roundtrip_with(
+            &Test {
+                value: OnceCell::new(),
+            },
+            |_, b| assert!(b.value.is_none()),
+        );
+    }
+
+    #[test]
+    fn roundtrip_ref_cell() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(Lock<Unsafe>)]
+            value: RefCell<i32>,
+        }
+
+        roundtrip_with(
+            &Test {
+                value: RefCell::new(10),
+            },
+            |a, b| assert_eq!(b.value, *a.value.borrow()),
+        );
+    }
+}
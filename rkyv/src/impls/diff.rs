@@ -0,0 +1,384 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, HashMap};
+
+use rancor::{fail, Source};
+
+use crate::diff::{
+    invalid_change, replace, replaced, unchanged, ArchivedChange, Change, Diff,
+    FieldChanges, FieldPatches,
+};
+
+// Values without any structure are replaced whenever they change.
+macro_rules! impl_leaf {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl<E: Source> Diff<E> for $ty {
+                fn diff(
+                    old: &Self::Archived,
+                    new: &Self::Archived,
+                ) -> Result<Option<Change>, E> {
+                    if old == new {
+                        Ok(None)
+                    } else {
+                        replace::<Self, E>(new).map(Some)
+                    }
+                }
+
+                fn patch(
+                    _: &Self::Archived,
+                    change: &ArchivedChange,
+                ) -> Result<Self, E> {
+                    match change {
+                        ArchivedChange::Replace(bytes) => {
+                            replaced::<Self, E>(bytes)
+                        }
+                        _ => invalid_change(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_leaf!(
+    (),
+    bool,
+    char,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    String,
+);
+
+macro_rules! impl_tuple {
+    ($($type:ident $index:tt),*) => {
+        impl<$($type: Diff<E>,)* E: Source> Diff<E> for ($($type,)*) {
+            fn diff(
+                old: &Self::Archived,
+                new: &Self::Archived,
+            ) -> Result<Option<Change>, E> {
+                let mut changes = FieldChanges::new();
+                $(changes.push::<$type, E>($index, &old.$index, &new.$index)?;)*
+                Ok(changes.finish())
+            }
+
+            fn patch(
+                old: &Self::Archived,
+                change: &ArchivedChange,
+            ) -> Result<Self, E> {
+                match change {
+                    ArchivedChange::Replace(bytes) => {
+                        replaced::<Self, E>(bytes)
+                    }
+                    ArchivedChange::Fields(changes) => {
+                        let mut patches = FieldPatches::new(changes);
+                        let value = ($(
+                            patches.patch::<$type, E>($index, &old.$index)?,
+                        )*);
+                        patches.finish()?;
+                        Ok(value)
+                    }
+                    _ => invalid_change(),
+                }
+            }
+        }
+    };
+}
+
+impl_tuple!(T0 0);
+impl_tuple!(T0 0, T1 1);
+impl_tuple!(T0 0, T1 1, T2 2);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3);
+
+impl<T: Diff<E>, E: Source> Diff<E> for Option<T> {
+    fn diff(
+        old: &Self::Archived,
+        new: &Self::Archived,
+    ) -> Result<Option<Change>, E> {
+        match (old.as_ref(), new.as_ref()) {
+            (None, None) => Ok(None),
+            (Some(old), Some(new)) => {
+                let mut changes = FieldChanges::new();
+                changes.push::<T, E>(0, old, new)?;
+                Ok(changes.finish())
+            }
+            _ => replace::<Self, E>(new).map(Some),
+        }
+    }
+
+    fn patch(old: &Self::Archived, change: &ArchivedChange) -> Result<Self, E> {
+        match (old.as_ref(), change) {
+            (_, ArchivedChange::Replace(bytes)) => replaced::<Self, E>(bytes),
+            (Some(old), ArchivedChange::Fields(changes)) => {
+                let mut patches = FieldPatches::new(changes);
+                let value = patches.patch::<T, E>(0, old)?;
+                patches.finish()?;
+                Ok(Some(value))
+            }
+            _ => invalid_change(),
+        }
+    }
+}
+
+impl<T: Diff<E>, E: Source> Diff<E> for Box<T> {
+    fn diff(
+        old: &Self::Archived,
+        new: &Self::Archived,
+    ) -> Result<Option<Change>, E> {
+        T::diff(old.get(), new.get())
+    }
+
+    fn patch(old: &Self::Archived, change: &ArchivedChange) -> Result<Self, E> {
+        T::patch(old.get(), change).map(Box::new)
+    }
+}
+
+impl<T: Diff<E>, E: Source> Diff<E> for Vec<T> {
+    fn diff(
+        old: &Self::Archived,
+        new: &Self::Archived,
+    ) -> Result<Option<Change>, E> {
+        let mut changes = FieldChanges::new();
+        for (i, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+            changes.push::<T, E>(i, old, new)?;
+        }
+        for (i, new) in new.iter().enumerate().skip(old.len()) {
+            changes.push_change(i, replace::<T, E>(new)?);
+        }
+
+        if old.len() == new.len() && changes.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Change::Elements {
+                len: new.len(),
+                changes: changes.into_vec(),
+            }))
+        }
+    }
+
+    fn patch(old: &Self::Archived, change: &ArchivedChange) -> Result<Self, E> {
+        match change {
+            ArchivedChange::Replace(bytes) => replaced::<Self, E>(bytes),
+            ArchivedChange::Elements { len, changes } => {
+                let len = len.to_native() as usize;
+                // Every element past the end of the old vec must be replaced,
+                // so the length is bounded by the number of changes.
+                if len > old.len() + changes.len() {
+                    return invalid_change();
+                }
+
+                let mut patches = FieldPatches::new(changes);
+                let mut result = Vec::with_capacity(len);
+                for i in 0..len {
+                    let value = match old.get(i) {
+                        Some(old) => patches.patch::<T, E>(i, old)?,
+                        None => match patches.take(i) {
+                            Some(ArchivedChange::Replace(bytes)) => {
+                                replaced::<T, E>(bytes)?
+                            }
+                            _ => return invalid_change(),
+                        },
+                    };
+                    result.push(value);
+                }
+                patches.finish()?;
+                Ok(result)
+            }
+            _ => invalid_change(),
+        }
+    }
+}
+
+impl<K, V, E> Diff<E> for BTreeMap<K, V>
+where
+    K: Diff<E> + Ord,
+    K::Archived: Ord,
+    V: Diff<E>,
+    E: Source,
+{
+    fn diff(
+        old: &Self::Archived,
+        new: &Self::Archived,
+    ) -> Result<Option<Change>, E> {
+        let mut removed = Vec::new();
+        let mut changed = FieldChanges::new();
+        for (i, (key, old_value)) in old.iter().enumerate() {
+            match new.get(key) {
+                None => removed.push(i),
+                Some(new_value) => {
+                    changed.push::<V, E>(i, old_value, new_value)?
+                }
+            }
+        }
+        let mut inserted = Vec::new();
+        for (key, value) in new.iter() {
+            if !old.contains_key(key) {
+                inserted
+                    .push((unchanged::<K, E>(key)?, unchanged::<V, E>(value)?));
+            }
+        }
+
+        diff_entries(removed, changed, inserted)
+    }
+
+    fn patch(old: &Self::Archived, change: &ArchivedChange) -> Result<Self, E> {
+        match change {
+            ArchivedChange::Replace(bytes) => replaced::<Self, E>(bytes),
+            ArchivedChange::Entries {
+                removed,
+                changed,
+                inserted,
+            } => {
+                let mut result = BTreeMap::new();
+                patch_entries::<K, V, E>(
+                    old.iter(),
+                    removed.as_slice(),
+                    FieldPatches::new(changed),
+                    inserted,
+                    |key, value| {
+                        result.insert(key, value);
+                    },
+                )?;
+                Ok(result)
+            }
+            _ => invalid_change(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S, E> Diff<E> for HashMap<K, V, S>
+where
+    K: Diff<E> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Diff<E>,
+    S: Default + BuildHasher,
+    E: Source,
+{
+    fn diff(
+        old: &Self::Archived,
+        new: &Self::Archived,
+    ) -> Result<Option<Change>, E> {
+        let mut removed = Vec::new();
+        let mut changed = FieldChanges::new();
+        for (i, (key, old_value)) in old.iter().enumerate() {
+            match new.get(key) {
+                None => removed.push(i),
+                Some(new_value) => {
+                    changed.push::<V, E>(i, old_value, new_value)?
+                }
+            }
+        }
+        let mut inserted = Vec::new();
+        for (key, value) in new.iter() {
+            if !old.contains_key(key) {
+                inserted
+                    .push((unchanged::<K, E>(key)?, unchanged::<V, E>(value)?));
+            }
+        }
+
+        diff_entries(removed, changed, inserted)
+    }
+
+    fn patch(old: &Self::Archived, change: &ArchivedChange) -> Result<Self, E> {
+        match change {
+            ArchivedChange::Replace(bytes) => replaced::<Self, E>(bytes),
+            ArchivedChange::Entries {
+                removed,
+                changed,
+                inserted,
+            } => {
+                let mut result = HashMap::with_hasher(S::default());
+                patch_entries::<K, V, E>(
+                    old.iter(),
+                    removed.as_slice(),
+                    FieldPatches::new(changed),
+                    inserted,
+                    |key, value| {
+                        result.insert(key, value);
+                    },
+                )?;
+                Ok(result)
+            }
+            _ => invalid_change(),
+        }
+    }
+}
+
+fn diff_entries<K, V, E>(
+    removed: Vec<usize>,
+    changed: FieldChanges,
+    inserted: Vec<(K, V)>,
+) -> Result<Option<Change>, E>
+where
+    K: Diff<E>,
+    V: Diff<E>,
+    E: Source,
+{
+    if removed.is_empty() && changed.is_empty() && inserted.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(Change::Entries {
+        removed,
+        changed: changed.into_vec(),
+        inserted: crate::to_bytes::<E>(&inserted)?.to_vec(),
+    }))
+}
+
+fn patch_entries<'a, K, V, E>(
+    old: impl Iterator<Item = (&'a K::Archived, &'a V::Archived)>,
+    removed: &[crate::Archived<usize>],
+    mut changed: FieldPatches<'_>,
+    inserted: &[u8],
+    mut insert: impl FnMut(K, V),
+) -> Result<(), E>
+where
+    K: Diff<E>,
+    V: Diff<E>,
+    E: Source,
+{
+    let mut removed = removed.iter().map(|i| i.to_native() as usize).peekable();
+    for (i, (key, value)) in old.enumerate() {
+        if removed.next_if_eq(&i).is_some() {
+            continue;
+        }
+        insert(unchanged::<K, E>(key)?, changed.patch::<V, E>(i, value)?);
+    }
+    if removed.next().is_some() {
+        fail!(UnusedRemoval);
+    }
+    changed.finish()?;
+
+    for (key, value) in replaced::<Vec<(K, V)>, E>(inserted)? {
+        insert(key, value);
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct UnusedRemoval;
+
+impl core::fmt::Display for UnusedRemoval {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "removed entry was out of order or out of bounds")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnusedRemoval {}
@@ -8,6 +8,7 @@ use rancor::{Fallible, Source};
 
 use crate::{
     collections::swiss_table::map::{ArchivedHashMap, HashMapResolver},
+    hash::SeededHasher,
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -45,8 +46,8 @@ where
     }
 }
 
-impl<K, V, D, S> Deserialize<HashMap<K, V, S>, D>
-    for ArchivedHashMap<K::Archived, V::Archived>
+impl<K, V, D, S, H> Deserialize<HashMap<K, V, S>, D>
+    for ArchivedHashMap<K::Archived, V::Archived, H>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D> + Hash + Eq,
@@ -54,6 +55,7 @@ where
     V::Archived: Deserialize<V, D>,
     D: Fallible + ?Sized,
     S: Default + BuildHasher,
+    H: SeededHasher,
 {
     fn deserialize(
         &self,
@@ -8,6 +8,7 @@ use rancor::{Fallible, Source};
 
 use crate::{
     collections::swiss_table::set::{ArchivedHashSet, HashSetResolver},
+    hash::SeededHasher,
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -49,12 +50,14 @@ where
     }
 }
 
-impl<K, D, S> Deserialize<HashSet<K, S>, D> for ArchivedHashSet<K::Archived>
+impl<K, D, S, H> Deserialize<HashSet<K, S>, D>
+    for ArchivedHashSet<K::Archived, H>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D> + Hash + Eq,
     D: Fallible + ?Sized,
     S: Default + BuildHasher,
+    H: SeededHasher,
 {
     fn deserialize(
         &self,
@@ -0,0 +1,137 @@
+use core::hash::{BuildHasher, Hash};
+
+use hashbrown::{HashMap, HashSet};
+use rancor::{Fallible, Source};
+
+use crate::{
+    collections::swiss_table::{
+        ArchivedHashMap, ArchivedHashSet, HashMapResolver, HashSetResolver,
+    },
+    hash::SeededHasher,
+    ser::{Allocator, SeedProvider, Writer},
+    with::{ArchiveWith, DeserializeWith, Seeded, SerializeWith},
+    Archive, Deserialize, Place, Serialize,
+};
+
+// Seeded
+
+impl<K, V, S, H> ArchiveWith<HashMap<K, V, S>> for Seeded<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Archive,
+    H: SeededHasher,
+{
+    type Archived = ArchivedHashMap<K::Archived, V::Archived, H>;
+    type Resolver = HashMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), (7, 8), resolver, out);
+    }
+}
+
+impl<K, V, S, H, RS> SerializeWith<HashMap<K, V, RS>, S> for Seeded<H>
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + SeedProvider + ?Sized,
+    S::Error: Source,
+    H: SeededHasher,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedHashMap::<_, _, H>::serialize_from_iter_with_key(
+            field.iter(),
+            serializer.hasher_key(),
+            (7, 8),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, S, H, D>
+    DeserializeWith<
+        ArchivedHashMap<K::Archived, V::Archived, H>,
+        HashMap<K, V, S>,
+        D,
+    > for Seeded<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    S: Default + BuildHasher,
+    H: SeededHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, V::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, S>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+impl<K, S, H> ArchiveWith<HashSet<K, S>> for Seeded<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+    H: SeededHasher,
+{
+    type Archived = ArchivedHashSet<K::Archived, H>;
+    type Resolver = HashSetResolver;
+
+    fn resolve_with(
+        field: &HashSet<K, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashSet::resolve_from_len(field.len(), (7, 8), resolver, out);
+    }
+}
+
+impl<K, S, H, RS> SerializeWith<HashSet<K, RS>, S> for Seeded<H>
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    S: Fallible + Allocator + Writer + SeedProvider + ?Sized,
+    S::Error: Source,
+    H: SeededHasher,
+{
+    fn serialize_with(
+        field: &HashSet<K, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedHashSet::<K::Archived, H>::serialize_from_iter_with_key(
+            field.iter(),
+            serializer.hasher_key(),
+            (7, 8),
+            serializer,
+        )
+    }
+}
+
+impl<K, S, H, D>
+    DeserializeWith<ArchivedHashSet<K::Archived, H>, HashSet<K, S>, D>
+    for Seeded<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    S: Default + BuildHasher,
+    H: SeededHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashSet<K::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<K, S>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
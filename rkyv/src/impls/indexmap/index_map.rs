@@ -5,6 +5,7 @@ use rancor::{Fallible, Source};
 
 use crate::{
     collections::swiss_table::{ArchivedIndexMap, IndexMapResolver},
+    hash::SeededHasher,
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -37,8 +38,8 @@ where
     }
 }
 
-impl<K, V, D, S> Deserialize<IndexMap<K, V, S>, D>
-    for ArchivedIndexMap<K::Archived, V::Archived>
+impl<K, V, D, S, H> Deserialize<IndexMap<K, V, S>, D>
+    for ArchivedIndexMap<K::Archived, V::Archived, H>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D>,
@@ -46,6 +47,7 @@ where
     V::Archived: Deserialize<V, D>,
     D: Fallible + ?Sized,
     S: Default + BuildHasher,
+    H: SeededHasher,
 {
     fn deserialize(
         &self,
@@ -63,11 +65,13 @@ where
     }
 }
 
-impl<UK, K, UV, V, S> PartialEq<IndexMap<UK, UV, S>> for ArchivedIndexMap<K, V>
+impl<UK, K, UV, V, S, H> PartialEq<IndexMap<UK, UV, S>>
+    for ArchivedIndexMap<K, V, H>
 where
     K: PartialEq<UK>,
     V: PartialEq<UV>,
     S: BuildHasher,
+    H: SeededHasher,
 {
     fn eq(&self, other: &IndexMap<UK, UV, S>) -> bool {
         self.iter()
@@ -76,6 +80,88 @@ where
     }
 }
 
+mod seeded {
+    use core::hash::{BuildHasher, Hash};
+
+    use indexmap::IndexMap;
+    use rancor::{Fallible, Source};
+
+    use crate::{
+        collections::swiss_table::{ArchivedIndexMap, IndexMapResolver},
+        hash::SeededHasher,
+        ser::{Allocator, SeedProvider, Writer},
+        with::{ArchiveWith, DeserializeWith, Seeded, SerializeWith},
+        Archive, Deserialize, Place, Serialize,
+    };
+
+    impl<K, V, S, H> ArchiveWith<IndexMap<K, V, S>> for Seeded<H>
+    where
+        K: Archive,
+        V: Archive,
+        H: SeededHasher,
+    {
+        type Archived = ArchivedIndexMap<K::Archived, V::Archived, H>;
+        type Resolver = IndexMapResolver;
+
+        fn resolve_with(
+            field: &IndexMap<K, V, S>,
+            resolver: Self::Resolver,
+            out: Place<Self::Archived>,
+        ) {
+            ArchivedIndexMap::resolve_from_len(
+                field.len(),
+                (7, 8),
+                resolver,
+                out,
+            );
+        }
+    }
+
+    impl<K, V, S, H, RS> SerializeWith<IndexMap<K, V, RS>, S> for Seeded<H>
+    where
+        K: Hash + Eq + Serialize<S>,
+        V: Serialize<S>,
+        S: Fallible + Allocator + Writer + SeedProvider + ?Sized,
+        S::Error: Source,
+        H: SeededHasher,
+    {
+        fn serialize_with(
+            field: &IndexMap<K, V, RS>,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            ArchivedIndexMap::<_, _, H>::serialize_from_iter_with_key(
+                field.iter(),
+                serializer.hasher_key(),
+                (7, 8),
+                serializer,
+            )
+        }
+    }
+
+    impl<K, V, S, H, D>
+        DeserializeWith<
+            ArchivedIndexMap<K::Archived, V::Archived, H>,
+            IndexMap<K, V, S>,
+            D,
+        > for Seeded<H>
+    where
+        K: Archive + Hash + Eq,
+        K::Archived: Deserialize<K, D>,
+        V: Archive,
+        V::Archived: Deserialize<V, D>,
+        S: Default + BuildHasher,
+        H: SeededHasher,
+        D: Fallible + ?Sized,
+    {
+        fn deserialize_with(
+            field: &ArchivedIndexMap<K::Archived, V::Archived, H>,
+            deserializer: &mut D,
+        ) -> Result<IndexMap<K, V, S>, D::Error> {
+            field.deserialize(deserializer)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(not(feature = "std"))]
@@ -128,4 +214,36 @@ mod tests {
         )
         .expect("failed to validate archived index map");
     }
+
+    #[cfg(all(feature = "std", feature = "bytecheck"))]
+    #[test]
+    fn validate_seeded_index_map() {
+        use rancor::Panic;
+
+        use crate::{
+            access, hash::SipHasher13, with::Seeded, Archive, Archived,
+            Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive(crate)]
+        #[archive_attr(derive(bytecheck::CheckBytes))]
+        struct Example {
+            #[with(Seeded<SipHasher13>)]
+            map: IndexMap<String, i32>,
+        }
+
+        let value = Example {
+            map: (0..50).map(|i| (i.to_string(), i)).collect(),
+        };
+
+        let result = crate::to_bytes::<Panic>(&value).unwrap();
+        let archived = access::<Archived<Example>, Panic>(result.as_ref())
+            .expect("failed to validate archived index map");
+        assert!(archived.map.keys().eq(value.map.keys()));
+        for (k, v) in value.map.iter() {
+            let archived_value = archived.map.get(k.as_str()).unwrap();
+            assert_eq!(archived_value, v);
+        }
+    }
 }
@@ -5,6 +5,7 @@ use rancor::{Fallible, Source};
 
 use crate::{
     collections::swiss_table::{ArchivedIndexSet, IndexSetResolver},
+    hash::SeededHasher,
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -36,12 +37,14 @@ where
     }
 }
 
-impl<K, D, S> Deserialize<IndexSet<K, S>, D> for ArchivedIndexSet<K::Archived>
+impl<K, D, S, H> Deserialize<IndexSet<K, S>, D>
+    for ArchivedIndexSet<K::Archived, H>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D>,
     D: Fallible + ?Sized,
     S: Default + BuildHasher,
+    H: SeededHasher,
 {
     fn deserialize(
         &self,
@@ -56,14 +59,92 @@ where
     }
 }
 
-impl<UK, K: PartialEq<UK>, S: BuildHasher> PartialEq<IndexSet<UK, S>>
-    for ArchivedIndexSet<K>
+impl<UK, K, S, H> PartialEq<IndexSet<UK, S>> for ArchivedIndexSet<K, H>
+where
+    K: PartialEq<UK>,
+    S: BuildHasher,
+    H: SeededHasher,
 {
     fn eq(&self, other: &IndexSet<UK, S>) -> bool {
         self.iter().eq(other.iter())
     }
 }
 
+mod seeded {
+    use core::hash::{BuildHasher, Hash};
+
+    use indexmap::IndexSet;
+    use rancor::{Fallible, Source};
+
+    use crate::{
+        collections::swiss_table::{ArchivedIndexSet, IndexSetResolver},
+        hash::SeededHasher,
+        ser::{Allocator, SeedProvider, Writer},
+        with::{ArchiveWith, DeserializeWith, Seeded, SerializeWith},
+        Archive, Deserialize, Place, Serialize,
+    };
+
+    impl<K, S, H> ArchiveWith<IndexSet<K, S>> for Seeded<H>
+    where
+        K: Archive,
+        H: SeededHasher,
+    {
+        type Archived = ArchivedIndexSet<K::Archived, H>;
+        type Resolver = IndexSetResolver;
+
+        fn resolve_with(
+            field: &IndexSet<K, S>,
+            resolver: Self::Resolver,
+            out: Place<Self::Archived>,
+        ) {
+            ArchivedIndexSet::resolve_from_len(
+                field.len(),
+                (7, 8),
+                resolver,
+                out,
+            );
+        }
+    }
+
+    impl<K, S, H, RS> SerializeWith<IndexSet<K, RS>, S> for Seeded<H>
+    where
+        K: Hash + Eq + Serialize<S>,
+        S: Fallible + Allocator + Writer + SeedProvider + ?Sized,
+        S::Error: Source,
+        H: SeededHasher,
+    {
+        fn serialize_with(
+            field: &IndexSet<K, RS>,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            ArchivedIndexSet::<_, H>::serialize_from_iter_with_key(
+                field.iter(),
+                serializer.hasher_key(),
+                (7, 8),
+                serializer,
+            )
+        }
+    }
+
+    impl<K, S, H, D>
+        DeserializeWith<ArchivedIndexSet<K::Archived, H>, IndexSet<K, S>, D>
+        for Seeded<H>
+    where
+        K: Archive + Hash + Eq,
+        K::Archived: Deserialize<K, D>,
+        S: Default + BuildHasher,
+        H: SeededHasher,
+        D: Fallible + ?Sized,
+    {
+        fn deserialize_with(
+            field: &ArchivedIndexSet<K::Archived, H>,
+            deserializer: &mut D,
+        ) -> Result<IndexSet<K, S>, D::Error> {
+            field.deserialize(deserializer)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(not(feature = "std"))]
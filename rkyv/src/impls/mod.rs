@@ -1,7 +1,11 @@
 #[cfg(feature = "alloc")]
 mod alloc;
 mod core;
+#[cfg(all(feature = "alloc", feature = "bytecheck"))]
+mod diff;
 mod rend;
+#[cfg(feature = "alloc")]
+mod schema;
 #[cfg(feature = "std")]
 mod std;
 
@@ -17,12 +21,16 @@ mod std;
 mod arrayvec;
 #[cfg(feature = "bitvec")]
 mod bitvec;
+#[cfg(feature = "bumpalo")]
+mod bumpalo;
 #[cfg(feature = "bytes")]
 mod bytes;
 #[cfg(feature = "hashbrown")]
 mod hashbrown;
 #[cfg(feature = "indexmap")]
 mod indexmap;
+#[cfg(feature = "roaring")]
+mod roaring;
 #[cfg(feature = "smallvec")]
 mod smallvec;
 #[cfg(feature = "smol_str")]
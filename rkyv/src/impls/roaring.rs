@@ -0,0 +1,100 @@
+use rancor::{Fallible, Source};
+use roaring::RoaringBitmap;
+
+use crate::{
+    collections::bitmap::{ArchivedBitmap, BitmapResolver},
+    ser::{Allocator, Writer},
+    Archive, Deserialize, Place, Serialize,
+};
+
+impl Archive for RoaringBitmap {
+    type Archived = ArchivedBitmap;
+    type Resolver = BitmapResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedBitmap::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for RoaringBitmap
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBitmap::serialize_from_ordered_iter(self.iter(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<RoaringBitmap, D> for ArchivedBitmap {
+    fn deserialize(&self, _: &mut D) -> Result<RoaringBitmap, D::Error> {
+        Ok(self.iter().collect())
+    }
+}
+
+impl PartialEq<RoaringBitmap> for ArchivedBitmap {
+    fn eq(&self, other: &RoaringBitmap) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roaring::RoaringBitmap;
+
+    use crate::test::{roundtrip, to_archived};
+
+    fn sample(seed: u32) -> RoaringBitmap {
+        let mut bitmap = RoaringBitmap::new();
+        // Sparse values become array containers.
+        bitmap.extend((0..1000).map(|i| i * 37 + seed));
+        // Dense, scattered values become bitmap containers.
+        bitmap.extend((0..20_000).map(|i| (1 << 16) + i * 3 + seed));
+        // Long runs become run containers.
+        bitmap.insert_range((5 << 16) + seed..(7 << 16) + 100);
+        bitmap.insert(u32::MAX - seed);
+        bitmap
+    }
+
+    #[test]
+    fn roundtrip_roaring() {
+        roundtrip(&RoaringBitmap::new());
+        roundtrip(&sample(0));
+        roundtrip(&sample(1));
+    }
+
+    #[test]
+    fn roaring_queries() {
+        let bitmap = sample(0);
+        to_archived(&bitmap, |archived| {
+            assert_eq!(archived.len(), bitmap.len());
+            for value in [0, 37, 38, 1 << 16, (1 << 16) + 4, 6 << 16, u32::MAX]
+            {
+                assert_eq!(archived.contains(value), bitmap.contains(value));
+                assert_eq!(archived.rank(value), bitmap.rank(value));
+            }
+            for value in bitmap.iter().step_by(97) {
+                assert!(archived.contains(value));
+                assert_eq!(archived.rank(value), bitmap.rank(value));
+            }
+            assert!(archived.iter().eq(bitmap.iter()));
+        });
+    }
+
+    #[test]
+    fn roaring_set_operations() {
+        let left = sample(0);
+        let right = sample(1);
+        to_archived(&left, |archived_left| {
+            to_archived(&right, |archived_right| {
+                let and: RoaringBitmap = archived_left.and(archived_right);
+                assert_eq!(and, &left & &right);
+                let or: RoaringBitmap = archived_left.or(archived_right);
+                assert_eq!(or, &left | &right);
+            });
+        });
+    }
+}
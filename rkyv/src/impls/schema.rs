@@ -0,0 +1,424 @@
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{
+    any::type_name,
+    cell::{Cell, UnsafeCell},
+    marker::PhantomData,
+    mem::{align_of, ManuallyDrop},
+    num::{NonZeroI8, NonZeroU8},
+};
+#[cfg(feature = "std")]
+use std::ffi::CStr;
+
+#[cfg(feature = "bitvec")]
+use bitvec::order::BitOrder;
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
+
+#[cfg(feature = "bitvec")]
+use crate::bitvec::{ArchivedBitArray, ArchivedBitVec};
+#[cfg(feature = "std")]
+use crate::ffi::ArchivedCString;
+use crate::{
+    boxed::ArchivedBox,
+    collections::{
+        binary_heap::ArchivedBinaryHeap,
+        bitmap::ArchivedBitmap,
+        btree_map::ArchivedBTreeMap,
+        btree_set::ArchivedBTreeSet,
+        phf::{map::ArchivedPhfMap, set::ArchivedPhfSet},
+        sorted_vec_map::ArchivedSortedVecMap,
+        sorted_vec_set::ArchivedSortedVecSet,
+        swiss_table::{
+            index_map::ArchivedIndexMap, index_set::ArchivedIndexSet,
+            map::ArchivedHashMap, set::ArchivedHashSet,
+        },
+        trie::ArchivedTrieMap,
+    },
+    extensible::ArchivedExtensible,
+    hash::SeededHasher,
+    niche::option_box::ArchivedOptionBox,
+    rc::ArchivedRc,
+    schema::{
+        ArchiveSchema, Endian, Field, Kind, Primitive, Registry, TypeSchema,
+    },
+    string::ArchivedString,
+    vec::ArchivedVec,
+    ArchivePointee,
+};
+
+const fn int(signed: bool, nonzero: bool, endian: Endian) -> Primitive {
+    Primitive::Int {
+        signed,
+        nonzero,
+        endian,
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty => $primitive:expr),* $(,)?) => {
+        $(
+            impl ArchiveSchema for $ty {
+                fn describe(_: &mut Registry) -> TypeSchema {
+                    TypeSchema::new::<Self>(Kind::Primitive($primitive))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_ints {
+    ($endian:expr; $($ty:ty => $signed:expr, $nonzero:expr),* $(,)?) => {
+        impl_primitive! {
+            $($ty => int($signed, $nonzero, $endian),)*
+        }
+    };
+}
+
+impl_primitive! {
+    () => Primitive::Unit,
+    bool => Primitive::Bool,
+    i8 => int(true, false, Endian::Little),
+    u8 => int(false, false, Endian::Little),
+    NonZeroI8 => int(true, true, Endian::Little),
+    NonZeroU8 => int(false, true, Endian::Little),
+    rend::char_le => Primitive::Char { endian: Endian::Little },
+    rend::char_be => Primitive::Char { endian: Endian::Big },
+    rend::f32_le => Primitive::Float { endian: Endian::Little },
+    rend::f32_be => Primitive::Float { endian: Endian::Big },
+    rend::f64_le => Primitive::Float { endian: Endian::Little },
+    rend::f64_be => Primitive::Float { endian: Endian::Big },
+    rend::unaligned::char_ule => Primitive::Char { endian: Endian::Little },
+    rend::unaligned::char_ube => Primitive::Char { endian: Endian::Big },
+    rend::unaligned::f32_ule => Primitive::Float { endian: Endian::Little },
+    rend::unaligned::f32_ube => Primitive::Float { endian: Endian::Big },
+    rend::unaligned::f64_ule => Primitive::Float { endian: Endian::Little },
+    rend::unaligned::f64_ube => Primitive::Float { endian: Endian::Big },
+}
+
+impl_ints! {
+    Endian::Little;
+    rend::i16_le => true, false,
+    rend::i32_le => true, false,
+    rend::i64_le => true, false,
+    rend::i128_le => true, false,
+    rend::u16_le => false, false,
+    rend::u32_le => false, false,
+    rend::u64_le => false, false,
+    rend::u128_le => false, false,
+    rend::NonZeroI16_le => true, true,
+    rend::NonZeroI32_le => true, true,
+    rend::NonZeroI64_le => true, true,
+    rend::NonZeroI128_le => true, true,
+    rend::NonZeroU16_le => false, true,
+    rend::NonZeroU32_le => false, true,
+    rend::NonZeroU64_le => false, true,
+    rend::NonZeroU128_le => false, true,
+    rend::unaligned::i16_ule => true, false,
+    rend::unaligned::i32_ule => true, false,
+    rend::unaligned::i64_ule => true, false,
+    rend::unaligned::i128_ule => true, false,
+    rend::unaligned::u16_ule => false, false,
+    rend::unaligned::u32_ule => false, false,
+    rend::unaligned::u64_ule => false, false,
+    rend::unaligned::u128_ule => false, false,
+    rend::unaligned::NonZeroI16_ule => true, true,
+    rend::unaligned::NonZeroI32_ule => true, true,
+    rend::unaligned::NonZeroI64_ule => true, true,
+    rend::unaligned::NonZeroI128_ule => true, true,
+    rend::unaligned::NonZeroU16_ule => false, true,
+    rend::unaligned::NonZeroU32_ule => false, true,
+    rend::unaligned::NonZeroU64_ule => false, true,
+    rend::unaligned::NonZeroU128_ule => false, true,
+}
+
+impl_ints! {
+    Endian::Big;
+    rend::i16_be => true, false,
+    rend::i32_be => true, false,
+    rend::i64_be => true, false,
+    rend::i128_be => true, false,
+    rend::u16_be => false, false,
+    rend::u32_be => false, false,
+    rend::u64_be => false, false,
+    rend::u128_be => false, false,
+    rend::NonZeroI16_be => true, true,
+    rend::NonZeroI32_be => true, true,
+    rend::NonZeroI64_be => true, true,
+    rend::NonZeroI128_be => true, true,
+    rend::NonZeroU16_be => false, true,
+    rend::NonZeroU32_be => false, true,
+    rend::NonZeroU64_be => false, true,
+    rend::NonZeroU128_be => false, true,
+    rend::unaligned::i16_ube => true, false,
+    rend::unaligned::i32_ube => true, false,
+    rend::unaligned::i64_ube => true, false,
+    rend::unaligned::i128_ube => true, false,
+    rend::unaligned::u16_ube => false, false,
+    rend::unaligned::u32_ube => false, false,
+    rend::unaligned::u64_ube => false, false,
+    rend::unaligned::u128_ube => false, false,
+    rend::unaligned::NonZeroI16_ube => true, true,
+    rend::unaligned::NonZeroI32_ube => true, true,
+    rend::unaligned::NonZeroI64_ube => true, true,
+    rend::unaligned::NonZeroI128_ube => true, true,
+    rend::unaligned::NonZeroU16_ube => false, true,
+    rend::unaligned::NonZeroU32_ube => false, true,
+    rend::unaligned::NonZeroU64_ube => false, true,
+    rend::unaligned::NonZeroU128_ube => false, true,
+}
+
+// Atomics have the same layout as their non-atomic counterparts.
+
+#[cfg(target_has_atomic = "8")]
+impl_primitive! {
+    core::sync::atomic::AtomicBool => Primitive::Bool,
+    core::sync::atomic::AtomicI8 => int(true, false, Endian::Little),
+    core::sync::atomic::AtomicU8 => int(false, false, Endian::Little),
+}
+
+#[cfg(target_has_atomic = "16")]
+impl_ints! {
+    Endian::Little;
+    rend::AtomicI16_le => true, false,
+    rend::AtomicU16_le => false, false,
+}
+
+#[cfg(target_has_atomic = "16")]
+impl_ints! {
+    Endian::Big;
+    rend::AtomicI16_be => true, false,
+    rend::AtomicU16_be => false, false,
+}
+
+#[cfg(target_has_atomic = "32")]
+impl_ints! {
+    Endian::Little;
+    rend::AtomicI32_le => true, false,
+    rend::AtomicU32_le => false, false,
+}
+
+#[cfg(target_has_atomic = "32")]
+impl_ints! {
+    Endian::Big;
+    rend::AtomicI32_be => true, false,
+    rend::AtomicU32_be => false, false,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl_ints! {
+    Endian::Little;
+    rend::AtomicI64_le => true, false,
+    rend::AtomicU64_le => false, false,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl_ints! {
+    Endian::Big;
+    rend::AtomicI64_be => true, false,
+    rend::AtomicU64_be => false, false,
+}
+
+impl<T: ?Sized + 'static> ArchiveSchema for PhantomData<T> {
+    fn describe(_: &mut Registry) -> TypeSchema {
+        TypeSchema::new::<Self>(Kind::Primitive(Primitive::Unit))
+    }
+}
+
+// Arrays, slices, and `str`
+
+impl<T: ArchiveSchema, const N: usize> ArchiveSchema for [T; N] {
+    fn describe(registry: &mut Registry) -> TypeSchema {
+        TypeSchema::new::<Self>(Kind::Array {
+            element: registry.register::<T>(),
+            len: N,
+        })
+    }
+}
+
+impl<T: ArchiveSchema> ArchiveSchema for [T] {
+    fn describe(registry: &mut Registry) -> TypeSchema {
+        TypeSchema::new_unsized::<Self>(
+            align_of::<T>(),
+            Kind::Slice {
+                element: registry.register::<T>(),
+            },
+        )
+    }
+}
+
+impl ArchiveSchema for str {
+    fn describe(_: &mut Registry) -> TypeSchema {
+        TypeSchema::new_unsized::<Self>(1, Kind::Str)
+    }
+}
+
+// Transparent wrappers
+
+macro_rules! impl_transparent {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<T: ArchiveSchema> ArchiveSchema for $ty<T> {
+                fn describe(registry: &mut Registry) -> TypeSchema {
+                    TypeSchema::new::<Self>(Kind::Struct(vec![Field::new::<
+                        T,
+                    >(
+                        registry, "value", 0
+                    )]))
+                }
+            }
+        )*
+    };
+}
+
+impl_transparent!(ManuallyDrop, Cell, UnsafeCell);
+
+// Builtin layouts
+
+fn builtin(layout: impl Into<String>, arguments: Vec<String>) -> Kind {
+    Kind::Builtin {
+        layout: layout.into(),
+        arguments,
+    }
+}
+
+impl ArchiveSchema for ArchivedString {
+    fn describe(_: &mut Registry) -> TypeSchema {
+        TypeSchema::new::<Self>(builtin("ArchivedString", Vec::new()))
+    }
+}
+
+impl<T: ArchiveSchema + ArchivePointee + ?Sized> ArchiveSchema
+    for ArchivedBox<T>
+{
+    fn describe(registry: &mut Registry) -> TypeSchema {
+        let arguments = vec![registry.register::<T>()];
+        TypeSchema::new::<Self>(builtin("ArchivedBox", arguments))
+    }
+}
+
+impl<T: ArchiveSchema + ArchivePointee + ?Sized> ArchiveSchema
+    for ArchivedOptionBox<T>
+{
+    fn describe(registry: &mut Registry) -> TypeSchema {
+        let arguments = vec![registry.register::<T>()];
+        TypeSchema::new::<Self>(builtin("ArchivedOptionBox", arguments))
+    }
+}
+
+impl<T: ArchiveSchema + ArchivePointee + ?Sized, F: 'static> ArchiveSchema
+    for ArchivedRc<T, F>
+{
+    fn describe(registry: &mut Registry) -> TypeSchema {
+        let arguments = vec![registry.register::<T>()];
+        TypeSchema::new::<Self>(builtin("ArchivedRc", arguments))
+    }
+}
+
+macro_rules! impl_builtin {
+    ($name:ident<$($param:ident),*>) => {
+        impl<$($param: ArchiveSchema),*> ArchiveSchema for $name<$($param),*> {
+            fn describe(registry: &mut Registry) -> TypeSchema {
+                let arguments = vec![$(registry.register::<$param>()),*];
+                TypeSchema::new::<Self>(builtin(stringify!($name), arguments))
+            }
+        }
+    };
+}
+
+impl_builtin!(ArchivedVec<T>);
+impl_builtin!(ArchivedBinaryHeap<T>);
+impl_builtin!(ArchivedSortedVecMap<K, V>);
+impl_builtin!(ArchivedSortedVecSet<K>);
+impl_builtin!(ArchivedTrieMap<V>);
+impl_builtin!(ArchivedExtensible<T>);
+
+impl ArchiveSchema for ArchivedBitmap {
+    fn describe(_: &mut Registry) -> TypeSchema {
+        TypeSchema::new::<Self>(builtin("ArchivedBitmap", Vec::new()))
+    }
+}
+
+// The hasher of a hashed collection doesn't affect its layout, but readers
+// need to know it in order to look up entries.
+macro_rules! impl_hashed_builtin {
+    ($name:ident<$($param:ident),*>) => {
+        impl<$($param: ArchiveSchema,)* H: SeededHasher + 'static> ArchiveSchema
+            for $name<$($param,)* H>
+        {
+            fn describe(registry: &mut Registry) -> TypeSchema {
+                let arguments = vec![$(registry.register::<$param>()),*];
+                let layout =
+                    format!("{}<{}>", stringify!($name), type_name::<H>());
+                TypeSchema::new::<Self>(builtin(layout, arguments))
+            }
+        }
+    };
+}
+
+impl_hashed_builtin!(ArchivedHashMap<K, V>);
+impl_hashed_builtin!(ArchivedHashSet<K>);
+impl_hashed_builtin!(ArchivedIndexMap<K, V>);
+impl_hashed_builtin!(ArchivedIndexSet<K>);
+impl_hashed_builtin!(ArchivedPhfMap<K, V>);
+impl_hashed_builtin!(ArchivedPhfSet<K>);
+
+impl<K: ArchiveSchema, V: ArchiveSchema, const E: usize> ArchiveSchema
+    for ArchivedBTreeMap<K, V, E>
+{
+    fn describe(registry: &mut Registry) -> TypeSchema {
+        let arguments =
+            vec![registry.register::<K>(), registry.register::<V>()];
+        let layout = format!("ArchivedBTreeMap<{}>", E);
+        TypeSchema::new::<Self>(builtin(layout, arguments))
+    }
+}
+
+impl<K: ArchiveSchema, const E: usize> ArchiveSchema
+    for ArchivedBTreeSet<K, E>
+{
+    fn describe(registry: &mut Registry) -> TypeSchema {
+        let arguments = vec![registry.register::<K>()];
+        let layout = format!("ArchivedBTreeSet<{}>", E);
+        TypeSchema::new::<Self>(builtin(layout, arguments))
+    }
+}
+
+#[cfg(feature = "std")]
+impl ArchiveSchema for CStr {
+    fn describe(_: &mut Registry) -> TypeSchema {
+        TypeSchema::new_unsized::<Self>(1, builtin("CStr", Vec::new()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl ArchiveSchema for ArchivedCString {
+    fn describe(_: &mut Registry) -> TypeSchema {
+        TypeSchema::new::<Self>(builtin("ArchivedCString", Vec::new()))
+    }
+}
+
+#[cfg(feature = "bitvec")]
+impl<T: ArchiveSchema, O: BitOrder> ArchiveSchema for ArchivedBitVec<T, O> {
+    fn describe(registry: &mut Registry) -> TypeSchema {
+        let arguments = vec![registry.register::<T>()];
+        let layout = format!("ArchivedBitVec<{}>", type_name::<O>());
+        TypeSchema::new::<Self>(builtin(layout, arguments))
+    }
+}
+
+#[cfg(feature = "bitvec")]
+impl<A: ArchiveSchema, O: BitOrder> ArchiveSchema for ArchivedBitArray<A, O> {
+    fn describe(registry: &mut Registry) -> TypeSchema {
+        let arguments = vec![registry.register::<A>()];
+        let layout = format!("ArchivedBitArray<{}>", type_name::<O>());
+        TypeSchema::new::<Self>(builtin(layout, arguments))
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl ArchiveSchema for Uuid {
+    fn describe(_: &mut Registry) -> TypeSchema {
+        TypeSchema::new::<Self>(builtin("Uuid", Vec::new()))
+    }
+}
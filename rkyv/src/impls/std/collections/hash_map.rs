@@ -8,6 +8,7 @@ use rancor::{Fallible, Source};
 
 use crate::{
     collections::swiss_table::map::{ArchivedHashMap, HashMapResolver},
+    hash::SeededHasher,
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -45,8 +46,8 @@ where
     }
 }
 
-impl<K, V, D, S> Deserialize<HashMap<K, V, S>, D>
-    for ArchivedHashMap<K::Archived, V::Archived>
+impl<K, V, D, S, H> Deserialize<HashMap<K, V, S>, D>
+    for ArchivedHashMap<K::Archived, V::Archived, H>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D> + Hash + Eq,
@@ -54,6 +55,7 @@ where
     V::Archived: Deserialize<V, D>,
     D: Fallible + ?Sized,
     S: Default + BuildHasher,
+    H: SeededHasher,
 {
     fn deserialize(
         &self,
@@ -69,14 +71,40 @@ where
         }
         Ok(result)
     }
+
+    fn deserialize_into(
+        &self,
+        target: &mut HashMap<K, V, S>,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        // Each key is deserialized once. Values whose keys are already in the
+        // map are moved out of it and deserialized in place.
+        let mut result =
+            HashMap::with_capacity_and_hasher(self.len(), S::default());
+        for (k, v) in self.iter() {
+            let key = k.deserialize(deserializer)?;
+            let value = match target.remove(&key) {
+                Some(mut value) => {
+                    v.deserialize_into(&mut value, deserializer)?;
+                    value
+                }
+                None => v.deserialize(deserializer)?,
+            };
+            result.insert(key, value);
+        }
+        *target = result;
+        Ok(())
+    }
 }
 
-impl<K, V, AK, AV, S> PartialEq<HashMap<K, V, S>> for ArchivedHashMap<AK, AV>
+impl<K, V, AK, AV, S, H> PartialEq<HashMap<K, V, S>>
+    for ArchivedHashMap<AK, AV, H>
 where
     K: Hash + Eq + Borrow<AK>,
     AK: Hash + Eq,
     AV: PartialEq<V>,
     S: BuildHasher,
+    H: SeededHasher,
 {
     fn eq(&self, other: &HashMap<K, V, S>) -> bool {
         if self.len() != other.len() {
@@ -89,13 +117,14 @@ where
     }
 }
 
-impl<K, V, AK, AV> PartialEq<ArchivedHashMap<AK, AV>> for HashMap<K, V>
+impl<K, V, AK, AV, H> PartialEq<ArchivedHashMap<AK, AV, H>> for HashMap<K, V>
 where
     K: Hash + Eq + Borrow<AK>,
     AK: Hash + Eq,
     AV: PartialEq<V>,
+    H: SeededHasher,
 {
-    fn eq(&self, other: &ArchivedHashMap<AK, AV>) -> bool {
+    fn eq(&self, other: &ArchivedHashMap<AK, AV, H>) -> bool {
         other.eq(self)
     }
 }
@@ -108,7 +137,7 @@ mod tests {
     use ahash::RandomState;
 
     use crate::{
-        test::{roundtrip, roundtrip_with, to_archived},
+        test::{roundtrip, roundtrip_into, roundtrip_with, to_archived},
         Archive, Archived, Deserialize, Serialize,
     };
 
@@ -200,4 +229,125 @@ mod tests {
             assert_eq!(get_with.as_str(), "value");
         });
     }
+
+    #[test]
+    fn deserialize_into_hash_map() {
+        let mut value = HashMap::new();
+        value.insert("hello".to_string(), "world".to_string());
+        value.insert("foo".to_string(), "bar".to_string());
+
+        let mut target = HashMap::new();
+        roundtrip_into(&value, &mut target);
+        let value_ptr = target["hello"].as_ptr();
+
+        // Same keys, so the values are deserialized in place
+        value.insert("hello".to_string(), "there".to_string());
+        roundtrip_into(&value, &mut target);
+        assert_eq!(target["hello"].as_ptr(), value_ptr);
+
+        // Keys which are still in the map keep their values
+        value.remove("foo");
+        value.insert("baz".to_string(), "bat".to_string());
+        roundtrip_into(&value, &mut target);
+        assert_eq!(target["hello"].as_ptr(), value_ptr);
+    }
+
+    #[test]
+    fn roundtrip_seeded_hash_map() {
+        use std::collections::HashSet;
+
+        use rancor::Panic;
+
+        use crate::{
+            access_unchecked,
+            hash::{SeededHasher as _, SipHasher13},
+            ser::{
+                seed::{WithSeed, DEFAULT_HASHER_KEY},
+                sharing::Share,
+                Serializer,
+            },
+            util::{serialize_into, with_arena, AlignedVec},
+            with::Seeded,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate)]
+        #[archive_attr(derive(Debug))]
+        struct Example {
+            #[with(Seeded<SipHasher13>)]
+            map: HashMap<String, u32>,
+            #[with(Seeded<SipHasher13>)]
+            set: HashSet<u32>,
+        }
+
+        let value = Example {
+            map: (0..100).map(|i| (i.to_string(), i)).collect(),
+            set: (0..100).map(|i| i * 3).collect(),
+        };
+
+        roundtrip_with(&value, |a, b| {
+            // Serializers use the default key unless they are given one.
+            let seed = SipHasher13::seed_from_key(DEFAULT_HASHER_KEY);
+            assert_eq!(b.map.seed(), &seed);
+            assert_eq!(b.set.seed(), &seed);
+
+            assert_eq!(b.map.len(), a.map.len());
+            for (key, value) in a.map.iter() {
+                assert_eq!(b.map[key.as_str()], *value);
+            }
+            assert_eq!(b.set.len(), a.set.len());
+            for i in 0..300 {
+                let key = Archived::<u32>::from_native(i);
+                assert_eq!(b.set.contains(&key), a.set.contains(&i));
+            }
+        });
+
+        let key = [0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210];
+        let to_bytes = |value: &Example| {
+            with_arena(|arena| {
+                let serializer = Serializer::new(
+                    AlignedVec::<16>::new(),
+                    arena.acquire(),
+                    Share::new(),
+                );
+                serialize_into::<_, Panic>(
+                    value,
+                    WithSeed::new(key, serializer),
+                )
+                .unwrap()
+                .into_inner()
+                .into_writer()
+            })
+        };
+
+        let bytes = to_bytes(&value);
+        // Serializing with the same key produces the same bytes.
+        assert_eq!(bytes.as_slice(), to_bytes(&value).as_slice());
+
+        let archived = unsafe { access_unchecked::<ArchivedExample>(&bytes) };
+        let seed = SipHasher13::seed_from_key(key);
+        assert_eq!(archived.map.seed(), &seed);
+        assert_eq!(archived.set.seed(), &seed);
+        for (key, value) in value.map.iter() {
+            assert_eq!(archived.map[key.as_str()], *value);
+        }
+    }
+
+    #[test]
+    fn large_hash_map_lookups() {
+        // Large tables need more than one probe window to find some keys.
+        let value = (0..2000u32).map(|i| (i * 7, i)).collect::<HashMap<_, _>>();
+
+        to_archived(&value, |archived| {
+            assert_eq!(archived.len(), value.len());
+            for i in 0..14_000u32 {
+                let key = Archived::<u32>::from_native(i);
+                assert_eq!(
+                    archived.get(&key).map(|v| v.to_native()),
+                    value.get(&i).copied(),
+                );
+            }
+            assert_eq!(archived.iter().count(), value.len());
+        });
+    }
 }
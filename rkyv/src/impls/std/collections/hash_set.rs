@@ -8,6 +8,7 @@ use rancor::{Fallible, Source};
 
 use crate::{
     collections::swiss_table::set::{ArchivedHashSet, HashSetResolver},
+    hash::SeededHasher,
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -49,12 +50,14 @@ where
     }
 }
 
-impl<K, D, S> Deserialize<HashSet<K, S>, D> for ArchivedHashSet<K::Archived>
+impl<K, D, S, H> Deserialize<HashSet<K, S>, D>
+    for ArchivedHashSet<K::Archived, H>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D> + Hash + Eq,
     D: Fallible + ?Sized,
     S: Default + BuildHasher,
+    H: SeededHasher,
 {
     fn deserialize(
         &self,
@@ -68,8 +71,12 @@ where
     }
 }
 
-impl<K: Hash + Eq + Borrow<AK>, AK: Hash + Eq, S: BuildHasher>
-    PartialEq<HashSet<K, S>> for ArchivedHashSet<AK>
+impl<K, AK, S, H> PartialEq<HashSet<K, S>> for ArchivedHashSet<AK, H>
+where
+    K: Hash + Eq + Borrow<AK>,
+    AK: Hash + Eq,
+    S: BuildHasher,
+    H: SeededHasher,
 {
     fn eq(&self, other: &HashSet<K, S>) -> bool {
         if self.len() != other.len() {
@@ -80,10 +87,14 @@ impl<K: Hash + Eq + Borrow<AK>, AK: Hash + Eq, S: BuildHasher>
     }
 }
 
-impl<K: Hash + Eq + Borrow<AK>, AK: Hash + Eq, S: BuildHasher>
-    PartialEq<ArchivedHashSet<AK>> for HashSet<K, S>
+impl<K, AK, S, H> PartialEq<ArchivedHashSet<AK, H>> for HashSet<K, S>
+where
+    K: Hash + Eq + Borrow<AK>,
+    AK: Hash + Eq,
+    S: BuildHasher,
+    H: SeededHasher,
 {
-    fn eq(&self, other: &ArchivedHashSet<AK>) -> bool {
+    fn eq(&self, other: &ArchivedHashSet<AK, H>) -> bool {
         other.eq(self)
     }
 }
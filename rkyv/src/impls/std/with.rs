@@ -3,7 +3,7 @@ use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
     ffi::{CStr, OsString},
-    hash::Hash,
+    hash::{BuildHasher, Hash},
     path::{Path, PathBuf},
     str::FromStr,
     sync::{Mutex, RwLock},
@@ -13,15 +13,24 @@ use std::{
 use rancor::{Fallible, OptionExt, ResultExt, Source};
 
 use crate::{
-    collections::util::{Entry, EntryAdapter},
+    collections::{
+        phf::{ArchivedPhfMap, ArchivedPhfSet, PhfMapResolver, PhfSetResolver},
+        sorted_vec_map::{ArchivedSortedVecMap, SortedVecMapResolver},
+        sorted_vec_set::{ArchivedSortedVecSet, SortedVecSetResolver},
+        swiss_table::{
+            ArchivedHashMap, ArchivedHashSet, HashMapResolver, HashSetResolver,
+        },
+        util::{Entry, EntryAdapter},
+    },
     ffi::{ArchivedCString, CStringResolver},
-    ser::{Allocator, Writer},
+    hash::SeededHasher,
+    ser::{Allocator, SeedProvider, Writer},
     string::{ArchivedString, StringResolver},
     time::ArchivedDuration,
     vec::{ArchivedVec, VecResolver},
     with::{
-        ArchiveWith, AsOwned, AsString, AsUnixTime, AsVec, DeserializeWith,
-        Lock, SerializeWith, Unsafe,
+        ArchiveWith, AsOwned, AsPerfectHash, AsSortedVec, AsString, AsUnixTime,
+        AsVec, DeserializeWith, Lock, Seeded, SerializeWith, Unsafe,
     },
     Archive, Deserialize, Place, Serialize, SerializeUnsized,
 };
@@ -369,6 +378,360 @@ where
     }
 }
 
+// AsSortedVec
+
+impl<K, V, S> ArchiveWith<HashMap<K, V, S>> for AsSortedVec
+where
+    K: Archive,
+    V: Archive,
+{
+    type Archived = ArchivedSortedVecMap<K::Archived, V::Archived>;
+    type Resolver = SortedVecMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSortedVecMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, V, S, RS> SerializeWith<HashMap<K, V, RS>, S> for AsSortedVec
+where
+    K: Serialize<S> + Ord,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedVecMap::serialize_from_iter(field.iter(), serializer)
+    }
+}
+
+impl<K, V, S, D>
+    DeserializeWith<
+        ArchivedSortedVecMap<K::Archived, V::Archived>,
+        HashMap<K, V, S>,
+        D,
+    > for AsSortedVec
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D>,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    S: Default + BuildHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSortedVecMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, S>, D::Error> {
+        let mut result =
+            HashMap::with_capacity_and_hasher(field.len(), S::default());
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl<K: Archive, S> ArchiveWith<HashSet<K, S>> for AsSortedVec {
+    type Archived = ArchivedSortedVecSet<K::Archived>;
+    type Resolver = SortedVecSetResolver;
+
+    fn resolve_with(
+        field: &HashSet<K, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSortedVecSet::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, S, RS> SerializeWith<HashSet<K, RS>, S> for AsSortedVec
+where
+    K: Serialize<S> + Ord,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashSet<K, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedVecSet::serialize_from_iter(field.iter(), serializer)
+    }
+}
+
+impl<K, S, D>
+    DeserializeWith<ArchivedSortedVecSet<K::Archived>, HashSet<K, S>, D>
+    for AsSortedVec
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D>,
+    S: Default + BuildHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSortedVecSet<K::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<K, S>, D::Error> {
+        let mut result =
+            HashSet::with_capacity_and_hasher(field.len(), S::default());
+        for key in field.iter() {
+            result.insert(key.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// AsPerfectHash
+
+impl<K, V, S> ArchiveWith<HashMap<K, V, S>> for AsPerfectHash
+where
+    K: Archive,
+    V: Archive,
+{
+    type Archived = ArchivedPhfMap<K::Archived, V::Archived>;
+    type Resolver = PhfMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPhfMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, V, S, RS> SerializeWith<HashMap<K, V, RS>, S> for AsPerfectHash
+where
+    K: Serialize<S> + Hash + Eq,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPhfMap::<K::Archived, V::Archived>::serialize_from_iter(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, S, D>
+    DeserializeWith<
+        ArchivedPhfMap<K::Archived, V::Archived>,
+        HashMap<K, V, S>,
+        D,
+    > for AsPerfectHash
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D>,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    S: Default + BuildHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPhfMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, S>, D::Error> {
+        let mut result =
+            HashMap::with_capacity_and_hasher(field.len(), S::default());
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl<K: Archive, S> ArchiveWith<HashSet<K, S>> for AsPerfectHash {
+    type Archived = ArchivedPhfSet<K::Archived>;
+    type Resolver = PhfSetResolver;
+
+    fn resolve_with(
+        field: &HashSet<K, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPhfSet::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, S, RS> SerializeWith<HashSet<K, RS>, S> for AsPerfectHash
+where
+    K: Serialize<S> + Hash + Eq,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashSet<K, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPhfSet::<K::Archived>::serialize_from_iter(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<K, S, D> DeserializeWith<ArchivedPhfSet<K::Archived>, HashSet<K, S>, D>
+    for AsPerfectHash
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D>,
+    S: Default + BuildHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPhfSet<K::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<K, S>, D::Error> {
+        let mut result =
+            HashSet::with_capacity_and_hasher(field.len(), S::default());
+        for key in field.iter() {
+            result.insert(key.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// Seeded
+
+impl<K, V, S, H> ArchiveWith<HashMap<K, V, S>> for Seeded<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Archive,
+    H: SeededHasher,
+{
+    type Archived = ArchivedHashMap<K::Archived, V::Archived, H>;
+    type Resolver = HashMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), (7, 8), resolver, out);
+    }
+}
+
+impl<K, V, S, H, RS> SerializeWith<HashMap<K, V, RS>, S> for Seeded<H>
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + SeedProvider + ?Sized,
+    S::Error: Source,
+    H: SeededHasher,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedHashMap::<_, _, H>::serialize_from_iter_with_key(
+            field.iter(),
+            serializer.hasher_key(),
+            (7, 8),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, S, H, D>
+    DeserializeWith<
+        ArchivedHashMap<K::Archived, V::Archived, H>,
+        HashMap<K, V, S>,
+        D,
+    > for Seeded<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    S: Default + BuildHasher,
+    H: SeededHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, V::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, S>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+impl<K, S, H> ArchiveWith<HashSet<K, S>> for Seeded<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+    H: SeededHasher,
+{
+    type Archived = ArchivedHashSet<K::Archived, H>;
+    type Resolver = HashSetResolver;
+
+    fn resolve_with(
+        field: &HashSet<K, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashSet::resolve_from_len(field.len(), (7, 8), resolver, out);
+    }
+}
+
+impl<K, S, H, RS> SerializeWith<HashSet<K, RS>, S> for Seeded<H>
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    S: Fallible + Allocator + Writer + SeedProvider + ?Sized,
+    S::Error: Source,
+    H: SeededHasher,
+{
+    fn serialize_with(
+        field: &HashSet<K, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedHashSet::<K::Archived, H>::serialize_from_iter_with_key(
+            field.iter(),
+            serializer.hasher_key(),
+            (7, 8),
+            serializer,
+        )
+    }
+}
+
+impl<K, S, H, D>
+    DeserializeWith<ArchivedHashSet<K::Archived, H>, HashSet<K, S>, D>
+    for Seeded<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    S: Default + BuildHasher,
+    H: SeededHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashSet<K::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<K, S>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
 // UnixTimestamp
 
 impl ArchiveWith<SystemTime> for AsUnixTime {
@@ -457,14 +820,16 @@ where
 #[cfg(test)]
 mod tests {
     use std::{
+        collections::{HashMap, HashSet},
         ffi::OsString,
         path::PathBuf,
         sync::{Mutex, RwLock},
     };
 
     use crate::{
+        primitive::ArchivedU32,
         test::roundtrip_with,
-        with::{AsString, Lock, Unsafe},
+        with::{AsPerfectHash, AsSortedVec, AsString, Lock, Unsafe},
         Archive, Deserialize, Serialize,
     };
 
@@ -565,4 +930,113 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn roundtrip_perfect_hash() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(AsPerfectHash)]
+            map: HashMap<String, u32>,
+            #[with(AsPerfectHash)]
+            set: HashSet<u32>,
+        }
+
+        let value = Test {
+            map: (0..200).map(|i| (i.to_string(), i)).collect(),
+            set: (0..200).map(|i| i * 3).collect(),
+        };
+
+        roundtrip_with(&value, |value, archived| {
+            assert_eq!(archived.map.len(), value.map.len());
+            for (k, v) in value.map.iter() {
+                assert_eq!(archived.map.get(k.as_str()).unwrap(), v);
+            }
+            assert!(archived.map.get("200").is_none());
+
+            assert_eq!(archived.set.len(), value.set.len());
+            for i in 0..600 {
+                let key = ArchivedU32::from_native(i);
+                assert_eq!(archived.set.contains(&key), i % 3 == 0);
+            }
+        });
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn validate_perfect_hash() {
+        use rancor::Panic;
+
+        use crate::{access, Archived};
+
+        #[derive(Archive, Serialize)]
+        #[archive(crate)]
+        #[archive_attr(derive(bytecheck::CheckBytes))]
+        struct Test {
+            #[with(AsPerfectHash)]
+            map: HashMap<String, u32>,
+            #[with(AsPerfectHash)]
+            empty: HashSet<u32>,
+        }
+
+        let value = Test {
+            map: (0..100).map(|i| (i.to_string(), i)).collect(),
+            empty: HashSet::new(),
+        };
+
+        let bytes = crate::to_bytes::<Panic>(&value).unwrap();
+        let archived = access::<Archived<Test>, Panic>(bytes.as_ref())
+            .expect("failed to validate archived perfect hash map");
+        for (k, v) in value.map.iter() {
+            assert_eq!(archived.map.get(k.as_str()).unwrap(), v);
+        }
+        assert!(archived.empty.is_empty());
+        assert!(!archived.empty.contains(&ArchivedU32::from_native(0)));
+    }
+
+    #[test]
+    fn roundtrip_sorted_vec() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(crate)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(AsSortedVec)]
+            map: HashMap<u32, String>,
+            #[with(AsSortedVec)]
+            set: HashSet<u32>,
+        }
+
+        let value = Test {
+            map: (0..100).map(|i| (i * 2, i.to_string())).collect(),
+            set: (0..100).map(|i| i * 3).collect(),
+        };
+
+        roundtrip_with(&value, |value, archived| {
+            assert_eq!(archived.map.len(), value.map.len());
+            for (k, v) in value.map.iter() {
+                let key = ArchivedU32::from_native(*k);
+                assert_eq!(archived.map.get(&key).unwrap(), v);
+            }
+            assert!(archived.map.get(&ArchivedU32::from_native(1)).is_none());
+            assert!(archived.map.keys().is_sorted());
+
+            let start = ArchivedU32::from_native(10);
+            let end = ArchivedU32::from_native(20);
+            let keys = archived.map.range(start..end).map(|(k, _)| *k);
+            assert!(keys.eq([10, 12, 14, 16, 18]));
+            let keys = archived.map.range(start..=end).rev().map(|(k, _)| *k);
+            assert!(keys.eq([20, 18, 16, 14, 12, 10]));
+            assert_eq!(archived.map.range(end..start).count(), 0);
+
+            assert_eq!(archived.set.first().unwrap().to_native(), 0);
+            assert_eq!(archived.set.last().unwrap().to_native(), 297);
+            for i in 0..300 {
+                let key = ArchivedU32::from_native(i);
+                assert_eq!(archived.set.contains_key(&key), i % 3 == 0);
+            }
+            let keys = archived.set.range(..end).map(|k| k.to_native());
+            assert!(keys.eq([0, 3, 6, 9, 12, 15, 18]));
+        });
+    }
 }
@@ -0,0 +1,246 @@
+//! Lazy, on-demand deserialization of archived values.
+//!
+//! A [`Lazy`] field archives exactly like the value it wraps, but deserializes
+//! into a handle that keeps a reference into the archive. The wrapped value is
+//! only deserialized the first time it is [forced](Lazy::force). This is useful
+//! for large records where only a few fields are needed as native types.
+//!
+//! Because lazy handles borrow from the archive, deserializing them requires a
+//! deserializer which knows the bytes of the archive. [`from_bytes_lazy`] takes
+//! care of this for validated archives, and [`WithArchive`] can be used to add
+//! the capability to other deserializers.
+
+use core::{cell::OnceCell, fmt};
+
+#[cfg(feature = "bytecheck")]
+use bytecheck::CheckBytes;
+use rancor::{Source, Strategy};
+
+#[cfg(feature = "bytecheck")]
+use crate::validation::validators::DefaultValidator;
+use crate::{
+    de::{ErasedPtr, Pool, Pooling},
+    Archive, Deserialize,
+};
+
+/// A value which is deserialized on first use.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     bytecheck::CheckBytes,
+///     lazy::{from_bytes_lazy, Lazy},
+///     rancor::Error,
+///     to_bytes, Archive, Deserialize, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize, Deserialize)]
+/// #[archive_attr(derive(CheckBytes))]
+/// struct Record<'a> {
+///     id: u32,
+///     payload: Lazy<'a, Vec<String>>,
+/// }
+///
+/// let record = Record {
+///     id: 42,
+///     payload: Lazy::new(vec!["a large".to_string(), "payload".to_string()]),
+/// };
+/// let bytes = to_bytes::<Error>(&record).unwrap();
+///
+/// let record = from_bytes_lazy::<Record<'_>, Error>(&bytes).unwrap();
+/// assert_eq!(record.id, 42);
+/// // The payload can be inspected without deserializing it...
+/// assert_eq!(record.payload.archived().unwrap().len(), 2);
+/// assert!(record.payload.get().is_none());
+/// // ... and is only deserialized when it is forced.
+/// let payload = record.payload.force::<Error>().unwrap();
+/// assert_eq!(payload[1], "payload");
+/// ```
+pub struct Lazy<'a, T: Archive> {
+    archived: Option<&'a T::Archived>,
+    value: OnceCell<T>,
+}
+
+impl<'a, T: Archive> Lazy<'a, T> {
+    /// Creates a new `Lazy` from a deserialized value.
+    pub fn new(value: T) -> Self {
+        Self {
+            archived: None,
+            value: OnceCell::from(value),
+        }
+    }
+
+    /// Creates a new `Lazy` which deserializes the given archived value when
+    /// it is forced.
+    pub fn from_archived(archived: &'a T::Archived) -> Self {
+        Self {
+            archived: Some(archived),
+            value: OnceCell::new(),
+        }
+    }
+
+    /// Returns the archived value, if this `Lazy` was created from one.
+    pub fn archived(&self) -> Option<&'a T::Archived> {
+        self.archived
+    }
+
+    /// Returns the deserialized value if it has been forced.
+    pub fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    /// Returns a mutable reference to the deserialized value if it has been
+    /// forced.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut()
+    }
+
+    /// Deserializes the value with the given deserializer if it has not been
+    /// deserialized yet, and returns a reference to it.
+    pub fn force_with<D, E>(&self, deserializer: &mut D) -> Result<&T, E>
+    where
+        T::Archived: Deserialize<T, Strategy<D, E>>,
+    {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        // A `Lazy` always has either an archived or a deserialized value.
+        let archived = self.archived.unwrap();
+        let value = archived.deserialize(Strategy::wrap(deserializer))?;
+        let _ = self.value.set(value);
+        Ok(self.value.get().unwrap())
+    }
+
+    /// Deserializes the value with a new [`Pool`] if it has not been
+    /// deserialized yet, and returns a reference to it.
+    ///
+    /// Shared pointers in the value are not shared with the rest of the
+    /// deserialized data. Use [`force_with`](Lazy::force_with) to provide a
+    /// deserializer instead.
+    pub fn force<E>(&self) -> Result<&T, E>
+    where
+        T::Archived: Deserialize<T, Strategy<Pool, E>>,
+    {
+        self.force_with(&mut Pool::new())
+    }
+
+    /// Forces the value and returns it.
+    pub fn into_inner<E>(self) -> Result<T, E>
+    where
+        T::Archived: Deserialize<T, Strategy<Pool, E>>,
+    {
+        self.force::<E>()?;
+        Ok(self.value.into_inner().unwrap())
+    }
+}
+
+impl<T: Archive + fmt::Debug> fmt::Debug for Lazy<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value.get() {
+            Some(value) => f.debug_tuple("Lazy").field(value).finish(),
+            None => f.write_str("Lazy(<archived>)"),
+        }
+    }
+}
+
+/// The resolver for a [`Lazy`].
+pub struct LazyResolver<T: Archive> {
+    // Serializing a `Lazy` which hasn't been forced deserializes a temporary
+    // value, which has to be kept until it is resolved.
+    pub(crate) value: Option<T>,
+    pub(crate) resolver: T::Resolver,
+}
+
+/// A deserializer that can borrow from the bytes of the archive being
+/// deserialized.
+///
+/// This trait is required to deserialize [`Lazy`].
+///
+/// # Safety
+///
+/// `archive_bytes` must return bytes which contain the entire archive that is
+/// being deserialized, including all of the data reachable from any value that
+/// is deserialized.
+pub unsafe trait BorrowArchive<'a> {
+    /// Returns the bytes of the archive being deserialized.
+    fn archive_bytes(&self) -> &'a [u8];
+}
+
+unsafe impl<'a, T, E> BorrowArchive<'a> for Strategy<T, E>
+where
+    T: BorrowArchive<'a> + ?Sized,
+{
+    fn archive_bytes(&self) -> &'a [u8] {
+        T::archive_bytes(self)
+    }
+}
+
+/// A deserializer adapter that adds [`BorrowArchive`] to another deserializer.
+#[derive(Debug)]
+pub struct WithArchive<'a, D> {
+    bytes: &'a [u8],
+    inner: D,
+}
+
+impl<'a, D> WithArchive<'a, D> {
+    /// Creates a new deserializer from the bytes of an archive and an inner
+    /// deserializer.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must contain the entire archive that will be deserialized,
+    /// including all of the data reachable from any value that is
+    /// deserialized.
+    pub unsafe fn new(bytes: &'a [u8], inner: D) -> Self {
+        Self { bytes, inner }
+    }
+
+    /// Consumes the adapter and returns the inner deserializer.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+unsafe impl<'a, D> BorrowArchive<'a> for WithArchive<'a, D> {
+    fn archive_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<D: Pooling<E>, E> Pooling<E> for WithArchive<'_, D> {
+    fn get_shared_ptr(&mut self, address: usize) -> Option<ErasedPtr> {
+        self.inner.get_shared_ptr(address)
+    }
+
+    unsafe fn add_shared_ptr(
+        &mut self,
+        address: usize,
+        ptr: ErasedPtr,
+        drop: unsafe fn(ErasedPtr),
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `add_shared_ptr` are the same as
+        // the requirements for calling this function.
+        unsafe { self.inner.add_shared_ptr(address, ptr, drop) }
+    }
+}
+
+/// Checks and deserializes a value which may contain [`Lazy`] fields from the
+/// given bytes.
+///
+/// This is the lazy counterpart of [`from_bytes`](crate::from_bytes). Any
+/// `Lazy` fields borrow from `bytes` and are only deserialized when forced.
+#[cfg(feature = "bytecheck")]
+pub fn from_bytes_lazy<'a, T, E>(bytes: &'a [u8]) -> Result<T, E>
+where
+    T: Archive,
+    T::Archived: for<'b> CheckBytes<Strategy<DefaultValidator<'b>, E>>
+        + Deserialize<T, Strategy<WithArchive<'a, Pool>, E>>,
+    E: Source,
+{
+    let archived = crate::access::<T::Archived, E>(bytes)?;
+    // SAFETY: `bytes` was just validated as an archive of `T`, so all of the
+    // data reachable from the root is located in `bytes`.
+    let mut deserializer = unsafe { WithArchive::new(bytes, Pool::new()) };
+    archived.deserialize(Strategy::wrap(&mut deserializer))
+}
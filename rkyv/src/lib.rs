@@ -88,7 +88,11 @@
 //!
 //! Crates supported by rkyv:
 //!
+//! - [`bumpalo`](https://docs.rs/bumpalo) *Enables deserializing archived
+//!   values into bump-allocated collections.*
 //! - [`indexmap`](https://docs.rs/indexmap)
+//! - [`memmap2`](https://docs.rs/memmap2) *Enables writing archives to and
+//!   accessing archives from memory-mapped files.*
 //! - [`rend`](https://docs.rs/rend) *Enabled automatically when using
 //!   endian-specific archive features.*
 //! - [`tinyvec`](https://docs.rs/tinyvec)
@@ -145,18 +149,25 @@ pub use ::munge;
 pub use ::ptr_meta;
 pub use ::rancor;
 pub use ::rend;
-pub use ::rkyv_derive::{Archive, Deserialize, Portable, Serialize};
+pub use ::rkyv_derive::{Archive, Deserialize, Portable, Relocate, Serialize};
 
 // Modules
 
 mod alias;
 #[macro_use]
 mod _macros;
+#[cfg(feature = "alloc")]
+pub mod append;
 #[cfg(feature = "bitvec")]
 pub mod bitvec;
 pub mod boxed;
+pub mod cmp;
 pub mod collections;
 pub mod de;
+#[cfg(feature = "alloc")]
+pub mod diff;
+pub mod envelope;
+pub mod extensible;
 mod fmt;
 // This is pretty unfortunate. CStr doesn't rely on the rest of std, but it's
 // not in core. If CStr ever gets moved into `core` then this module will no
@@ -165,8 +176,11 @@ mod fmt;
 pub mod ffi;
 pub mod hash;
 mod impls;
+#[cfg(feature = "alloc")]
+pub mod lazy;
 pub mod net;
 pub mod niche;
+pub mod num;
 pub mod ops;
 pub mod option;
 pub mod place;
@@ -175,6 +189,10 @@ pub mod primitive;
 pub mod rc;
 pub mod rel_ptr;
 pub mod result;
+#[cfg(feature = "alloc")]
+pub mod roots;
+#[cfg(feature = "alloc")]
+pub mod schema;
 pub mod ser;
 mod simd;
 pub mod string;
@@ -212,7 +230,10 @@ pub use crate::{
     alias::*,
     place::Place,
     traits::*,
-    util::{access_unchecked, access_unchecked_mut, deserialize, serialize},
+    util::{
+        access_unchecked, access_unchecked_mut, deserialize, deserialize_into,
+        serialize,
+    },
 };
 
 // Check endianness feature flag settings
@@ -15,6 +15,7 @@ use crate::{
 #[derive(Portable)]
 #[archive(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(transparent)]
 pub struct ArchivedIpv4Addr {
@@ -126,6 +127,7 @@ impl ArchivedIpv4Addr {
 #[derive(Portable)]
 #[archive(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(transparent)]
 pub struct ArchivedIpv6Addr {
@@ -219,6 +221,7 @@ impl ArchivedIpv6Addr {
 #[derive(Portable)]
 #[archive(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(u8)]
 pub enum ArchivedIpAddr {
@@ -293,6 +296,7 @@ impl ArchivedIpAddr {
 #[derive(Portable)]
 #[archive(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct ArchivedSocketAddrV4 {
@@ -332,6 +336,7 @@ impl ArchivedSocketAddrV4 {
 #[derive(Portable)]
 #[archive(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct ArchivedSocketAddrV6 {
@@ -398,6 +403,7 @@ impl ArchivedSocketAddrV6 {
 #[derive(Portable)]
 #[archive(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(u8)]
 pub enum ArchivedSocketAddr {
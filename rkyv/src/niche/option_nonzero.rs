@@ -20,6 +20,7 @@ macro_rules! impl_archived_option_nonzero {
         #[archive(crate)]
         #[repr(transparent)]
         #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+        #[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
         pub struct $ar {
             inner: Archived<$ne>,
         }
@@ -0,0 +1,49 @@
+//! Archived versions of `num` types.
+
+use core::fmt;
+
+use crate::Portable;
+
+/// An archived [`Wrapping`](core::num::Wrapping).
+#[derive(
+    Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[archive(crate)]
+#[repr(transparent)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
+pub struct ArchivedWrapping<T>(pub T);
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedWrapping<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ArchivedWrapping<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An archived [`Saturating`](core::num::Saturating).
+#[derive(
+    Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[archive(crate)]
+#[repr(transparent)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
+pub struct ArchivedSaturating<T>(pub T);
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedSaturating<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ArchivedSaturating<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -10,6 +10,7 @@ use crate::Portable;
 /// An archived [`RangeFull`](::core::ops::RangeFull).
 #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[repr(C)]
 #[archive(crate)]
 pub struct ArchivedRangeFull;
@@ -24,6 +25,7 @@ impl fmt::Debug for ArchivedRangeFull {
 /// An archived [`Range`](::core::ops::Range).
 #[derive(Clone, Default, PartialEq, Eq, Hash, Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[repr(C)]
 #[archive(crate)]
 pub struct ArchivedRange<T> {
@@ -76,6 +78,7 @@ impl<T> RangeBounds<T> for ArchivedRange<T> {
 /// An archived [`RangeInclusive`](::core::ops::RangeInclusive).
 #[derive(Clone, Default, PartialEq, Eq, Hash, Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[repr(C)]
 #[archive(crate)]
 pub struct ArchivedRangeInclusive<T> {
@@ -126,6 +129,7 @@ impl<T> RangeBounds<T> for ArchivedRangeInclusive<T> {
 /// An archived [`RangeFrom`](::core::ops::RangeFrom).
 #[derive(Clone, Default, PartialEq, Eq, Hash, Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[repr(C)]
 #[archive(crate)]
 pub struct ArchivedRangeFrom<T> {
@@ -165,6 +169,7 @@ impl<T> RangeBounds<T> for ArchivedRangeFrom<T> {
 /// An archived [`RangeTo`](::core::ops::RangeTo).
 #[derive(Clone, Default, PartialEq, Eq, Hash, Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[repr(C)]
 #[archive(crate)]
 pub struct ArchivedRangeTo<T> {
@@ -204,6 +209,7 @@ impl<T> RangeBounds<T> for ArchivedRangeTo<T> {
 /// An archived [`RangeToInclusive`](::core::ops::RangeToInclusive).
 #[derive(Clone, Default, PartialEq, Eq, Hash, Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[repr(C)]
 #[archive(crate)]
 pub struct ArchivedRangeToInclusive<T> {
@@ -243,6 +249,7 @@ impl<T> RangeBounds<T> for ArchivedRangeToInclusive<T> {
 /// An archived [`Bound`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[repr(u8)]
 #[archive(crate)]
 pub enum ArchivedBound<T> {
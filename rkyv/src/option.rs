@@ -6,16 +6,18 @@ use core::{
     pin::Pin,
 };
 
-use crate::Portable;
+use crate::{Portable, Relocate};
 
 /// An archived [`Option`].
 ///
 /// It functions identically to [`Option`] but has a different internal
 /// representation to allow for archiving.
-#[derive(Clone, Copy, Debug, Portable)]
+#[derive(Clone, Copy, Debug, Portable, Relocate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[repr(u8)]
 #[archive(crate)]
+#[relocate(unsafe_no_offsets)]
 pub enum ArchivedOption<T> {
     /// No value
     None,
@@ -1,6 +1,5 @@
 use core::{alloc::Layout, ptr::NonNull};
 
-#[cfg(feature = "alloc")]
 pub fn dangling(layout: &Layout) -> NonNull<u8> {
     #[cfg(miri)]
     {
@@ -7,7 +7,7 @@ use core::{
 };
 
 use munge::munge;
-use rancor::{fail, Panic, ResultExt as _, Source};
+use rancor::{fail, Failure, Panic, ResultExt as _, Source};
 
 use crate::{
     place::Initialized,
@@ -253,6 +253,37 @@ impl<O: Offset> RawRelPtr<O> {
         let off = self.offset();
         self.base_mut().wrapping_offset(off).cast()
     }
+
+    /// Returns whether the relative pointer can be moved by `delta` bytes
+    /// relative to its target.
+    ///
+    /// This returns `false` if the adjusted offset would not fit in `O`.
+    /// Invalid relative pointers can always be moved.
+    pub fn can_relocate(&self, delta: isize) -> bool {
+        self.is_invalid()
+            || self.offset().checked_sub(delta).is_some_and(|offset| {
+                offset != 1 && O::from_isize::<Failure>(offset).is_ok()
+            })
+    }
+
+    /// Adjusts the offset of a relative pointer which has been moved by
+    /// `delta` bytes relative to its target. Invalid relative pointers are
+    /// left invalid.
+    ///
+    /// # Safety
+    ///
+    /// `can_relocate` must have returned `true` for the relative pointer and
+    /// `delta`.
+    pub unsafe fn relocate(self: Pin<&mut Self>, delta: isize) {
+        if !self.is_invalid() {
+            let offset = O::from_isize::<Panic>(self.offset() - delta);
+            // SAFETY: We never move out of the pinned relative pointer, only
+            // replace its offset.
+            unsafe {
+                self.get_unchecked_mut().offset = offset.always_ok();
+            }
+        }
+    }
 }
 
 impl<O: fmt::Debug> fmt::Debug for RawRelPtr<O> {
@@ -468,6 +499,30 @@ impl<T: ArchivePointee + ?Sized, O: Offset> RelPtr<T, O> {
         let raw_ptr = unsafe { self.map_unchecked_mut(|s| &mut s.raw_ptr) };
         ptr_meta::from_raw_parts(raw_ptr.as_mut_ptr_wrapping(), metadata)
     }
+
+    /// Returns whether the relative pointer can be moved by `delta` bytes
+    /// relative to its target.
+    ///
+    /// See [`RawRelPtr::can_relocate`] for more details.
+    pub fn can_relocate(&self, delta: isize) -> bool {
+        self.raw_ptr.can_relocate(delta)
+    }
+
+    /// Adjusts the offset of a relative pointer which has been moved by
+    /// `delta` bytes relative to its target.
+    ///
+    /// # Safety
+    ///
+    /// `can_relocate` must have returned `true` for the relative pointer and
+    /// `delta`.
+    pub unsafe fn relocate(self: Pin<&mut Self>, delta: isize) {
+        // SAFETY: The metadata of a `RelPtr` does not depend on its position,
+        // so only the raw relative pointer needs to be relocated. The caller
+        // has guaranteed that it can be relocated by `delta`.
+        unsafe {
+            self.map_unchecked_mut(|s| &mut s.raw_ptr).relocate(delta);
+        }
+    }
 }
 
 impl<T: ArchivePointee + ?Sized, O: fmt::Debug> fmt::Debug for RelPtr<T, O>
@@ -13,6 +13,7 @@ use crate::Portable;
 #[derive(Debug, Portable)]
 #[archive(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 #[repr(u8)]
 pub enum ArchivedResult<T, E> {
     /// Contains the success value
@@ -0,0 +1,242 @@
+//! Archives with multiple independently-addressable roots.
+//!
+//! A multi-root archive contains several root values, possibly of different
+//! types, followed by a table of contents. The table of contents is an archived
+//! `Vec<usize>` of root positions located at the end of the buffer, so it can
+//! be found the same way as the root of a regular archive.
+//!
+//! Roots are written with a [`MultiRootSerializer`] and accessed by index with
+//! [`access_root`]. Only the requested root is validated, so accessing a single
+//! small record from a large archive is cheap. Because all roots are written
+//! with the same serializer, values that are shared between roots (e.g. through
+//! `Rc` or `Arc`) are only serialized once.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "bytecheck")]
+use bytecheck::CheckBytes;
+use rancor::{fail, Source, Strategy};
+
+#[cfg(feature = "bytecheck")]
+use crate::validation::validators::DefaultValidator;
+use crate::{
+    access_unchecked, ser::Writer, util::access_pos_unchecked, Archived,
+    Portable, Serialize, SerializeUnsized,
+};
+
+/// The table of contents of a multi-root archive.
+pub type ArchivedRoots = Archived<Vec<usize>>;
+
+/// A serializer adapter that writes multiple roots into a single archive.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     rancor::Error,
+///     roots::{access_root, MultiRootSerializer},
+///     ser::{sharing::Share, Serializer},
+///     util::{with_arena, AlignedVec},
+///     Archived,
+/// };
+///
+/// let bytes = with_arena(|arena| {
+///     let mut serializer = MultiRootSerializer::new(Serializer::new(
+///         AlignedVec::<16>::new(),
+///         arena.acquire(),
+///         Share::new(),
+///     ));
+///     serializer.serialize_root::<_, Error>(&"first".to_string())?;
+///     serializer.serialize_root::<_, Error>(&vec![1u32, 2, 3])?;
+///     Ok::<_, Error>(serializer.finish::<Error>()?.into_writer())
+/// })
+/// .unwrap();
+///
+/// let second = access_root::<Archived<Vec<u32>>, Error>(&bytes, 1).unwrap();
+/// assert_eq!(second, &[1, 2, 3]);
+/// let first = access_root::<Archived<String>, Error>(&bytes, 0).unwrap();
+/// assert_eq!(first, "first");
+/// ```
+#[derive(Debug, Default)]
+pub struct MultiRootSerializer<S> {
+    serializer: S,
+    roots: Vec<usize>,
+}
+
+impl<S> MultiRootSerializer<S> {
+    /// Creates a new multi-root serializer that writes to the given serializer.
+    pub fn new(serializer: S) -> Self {
+        Self {
+            serializer,
+            roots: Vec::new(),
+        }
+    }
+
+    /// Returns the number of roots that have been serialized.
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Returns whether no roots have been serialized.
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Returns the positions of the roots that have been serialized.
+    pub fn root_positions(&self) -> &[usize] {
+        &self.roots
+    }
+
+    /// Serializes a new root and returns its index.
+    pub fn serialize_root<T, E>(&mut self, value: &T) -> Result<usize, E>
+    where
+        T: SerializeUnsized<Strategy<S, E>> + ?Sized,
+        S: Writer<E>,
+    {
+        let pos =
+            value.serialize_unsized(Strategy::wrap(&mut self.serializer))?;
+        self.roots.push(pos);
+        Ok(self.roots.len() - 1)
+    }
+
+    /// Writes the table of contents and returns the underlying serializer.
+    pub fn finish<E>(mut self) -> Result<S, E>
+    where
+        Vec<usize>: Serialize<Strategy<S, E>>,
+        S: Writer<E>,
+    {
+        crate::util::serialize(&self.roots, &mut self.serializer)?;
+        Ok(self.serializer)
+    }
+}
+
+#[derive(Debug)]
+struct RootIndexOutOfBounds {
+    index: usize,
+    len: usize,
+}
+
+impl fmt::Display for RootIndexOutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "root index out of bounds: the archive has {} roots but the index \
+             is {}",
+            self.len, self.index,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RootIndexOutOfBounds {}
+
+fn root_pos<E: Source>(
+    roots: &ArchivedRoots,
+    index: usize,
+) -> Result<usize, E> {
+    match roots.get(index) {
+        Some(pos) => Ok(pos.to_native() as usize),
+        None => fail!(RootIndexOutOfBounds {
+            index,
+            len: roots.len(),
+        }),
+    }
+}
+
+/// Accesses the table of contents of a multi-root archive after checking its
+/// validity.
+#[cfg(feature = "bytecheck")]
+pub fn access_roots<E: Source>(bytes: &[u8]) -> Result<&ArchivedRoots, E> {
+    crate::access::<ArchivedRoots, E>(bytes)
+}
+
+/// Accesses the root at the given index of a multi-root archive after checking
+/// its validity.
+///
+/// Only the table of contents and the requested root are validated.
+#[cfg(feature = "bytecheck")]
+pub fn access_root<T, E>(bytes: &[u8], index: usize) -> Result<&T, E>
+where
+    T: Portable + for<'a> CheckBytes<Strategy<DefaultValidator<'a>, E>>,
+    E: Source,
+{
+    let pos = root_pos::<E>(access_roots::<E>(bytes)?, index)?;
+    crate::validation::util::access_pos::<T, E>(bytes, pos)
+}
+
+/// Accesses the root at the given index of a multi-root archive without
+/// checking its validity.
+///
+/// An error is still returned if `index` is out of bounds.
+///
+/// # Safety
+///
+/// The byte slice must represent a multi-root archive, and the root at the
+/// given index must be a valid `T`.
+pub unsafe fn access_root_unchecked<T, E>(
+    bytes: &[u8],
+    index: usize,
+) -> Result<&T, E>
+where
+    T: Portable,
+    E: Source,
+{
+    // SAFETY: The caller has guaranteed that the byte slice represents a
+    // multi-root archive, which always ends with its table of contents.
+    let roots = unsafe { access_unchecked::<ArchivedRoots>(bytes) };
+    let pos = root_pos::<E>(roots, index)?;
+    // SAFETY: The caller has guaranteed that the root at `index` is a valid
+    // `T`.
+    Ok(unsafe { access_pos_unchecked::<T>(bytes, pos) })
+}
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        rc::Rc,
+        string::{String, ToString},
+    };
+    #[cfg(feature = "std")]
+    use std::rc::Rc;
+
+    use rancor::{Error, Panic};
+
+    use super::{access_root, access_roots, MultiRootSerializer};
+    use crate::{
+        ser::{sharing::Share, Serializer},
+        util::{with_arena, AlignedVec},
+        Archived,
+    };
+
+    #[test]
+    fn multiple_roots() {
+        let shared = Rc::new("shared".to_string());
+        let bytes = with_arena(|arena| {
+            let mut serializer = MultiRootSerializer::new(Serializer::new(
+                AlignedVec::<16>::new(),
+                arena.acquire(),
+                Share::new(),
+            ));
+            serializer.serialize_root::<_, Panic>(&shared).unwrap();
+            serializer.serialize_root::<_, Panic>(&42u32).unwrap();
+            serializer.serialize_root::<_, Panic>(&shared).unwrap();
+            serializer.finish::<Panic>().unwrap().into_writer()
+        });
+
+        assert_eq!(access_roots::<Panic>(&bytes).unwrap().len(), 3);
+
+        let first =
+            access_root::<Archived<Rc<String>>, Panic>(&bytes, 0).unwrap();
+        let second = access_root::<Archived<u32>, Panic>(&bytes, 1).unwrap();
+        let third =
+            access_root::<Archived<Rc<String>>, Panic>(&bytes, 2).unwrap();
+        assert_eq!(first.as_str(), "shared");
+        assert_eq!(*second, 42);
+        // Both roots point to the same shared string
+        assert_eq!(first.get() as *const _, third.get() as *const _);
+
+        assert!(access_root::<Archived<u32>, Error>(&bytes, 3).is_err());
+    }
+}
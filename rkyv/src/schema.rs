@@ -0,0 +1,495 @@
+//! Runtime descriptions of archived types.
+//!
+//! An [`ArchiveSchema`] describes the layout of an archived type so that it
+//! can be read by tools and by consumers which aren't written in Rust. Types
+//! are described by [`TypeSchema`]s, which give the size and alignment of the
+//! type along with its [`Kind`]: a primitive, a struct or enum with fields at
+//! known offsets, an array or slice, or one of the collections provided by
+//! rkyv like [`ArchivedVec`](crate::vec::ArchivedVec).
+//!
+//! Types refer to each other by name, and a [`Schema`] collects every type
+//! reachable from a root type. This allows recursive types to be described.
+//! Types are identified by their [`TypeId`] and named with [`type_name`]. If
+//! two different types have the same name, the later one is suffixed with its
+//! index in the schema. Names are only meant to uniquely identify a type within
+//! a schema and may change between compiler versions.
+//!
+//! `ArchiveSchema` can be derived for archived types generated by
+//! `#[derive(Archive)]` with `#[archive(schema)]`. Schemas are themselves
+//! archivable, so they can be stored alongside the data they describe.
+//!
+//! # Examples
+//!
+//! ```
+//! use rkyv::{
+//!     schema::{schema, Kind},
+//!     Archive, Archived,
+//! };
+//!
+//! #[derive(Archive)]
+//! #[archive(schema)]
+//! struct Point {
+//!     x: f32,
+//!     y: f32,
+//!     tags: Vec<String>,
+//! }
+//!
+//! let schema = schema::<Archived<Point>>();
+//! let root = schema.root();
+//! assert_eq!(root.size, Some(core::mem::size_of::<ArchivedPoint>()));
+//!
+//! let Kind::Struct(fields) = &root.kind else {
+//!     panic!("expected a struct");
+//! };
+//! assert_eq!(fields[1].name, "y");
+//! assert_eq!(fields[1].offset, 4);
+//!
+//! let tags = schema.get(&fields[2].ty).unwrap();
+//! let Kind::Builtin { layout, arguments } = &tags.kind else {
+//!     panic!("expected a builtin collection");
+//! };
+//! assert_eq!(layout, "ArchivedVec");
+//! assert!(matches!(
+//!     schema.get(&arguments[0]).unwrap().kind,
+//!     Kind::Builtin { .. },
+//! ));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    any::{type_name, TypeId},
+    mem::{align_of, size_of},
+};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+#[doc(inline)]
+pub use rkyv_derive::ArchiveSchema;
+
+use crate::{Archive, Deserialize, Portable, Serialize};
+
+/// An archived type with a layout which can be described at runtime.
+///
+/// See the [module docs](crate::schema) for more information.
+pub trait ArchiveSchema: Portable + 'static {
+    /// Describes this type, registering any types it refers to with the given
+    /// registry.
+    fn describe(registry: &mut Registry) -> TypeSchema;
+}
+
+/// Returns the schema of the given archived type and all of the types it
+/// refers to.
+pub fn schema<T: ArchiveSchema + ?Sized>() -> Schema {
+    let mut registry = Registry::new();
+    registry.register::<T>();
+    registry.into_schema()
+}
+
+/// A set of archived types, along with the types they refer to.
+#[derive(Archive, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[archive(crate, schema)]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(feature = "bytecheck", archive_attr(derive(bytecheck::CheckBytes)))]
+pub struct Schema {
+    /// The described types, in the order they were registered. The first type
+    /// is the root type of the schema.
+    pub types: Vec<TypeSchema>,
+}
+
+impl Schema {
+    /// Returns the root type of the schema.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schema is empty.
+    pub fn root(&self) -> &TypeSchema {
+        &self.types[0]
+    }
+
+    /// Returns the type with the given name, or `None` if the schema doesn't
+    /// contain it.
+    pub fn get(&self, name: &str) -> Option<&TypeSchema> {
+        self.types.iter().find(|ty| ty.name == name)
+    }
+}
+
+/// A description of a single archived type.
+#[derive(Archive, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[archive(crate, schema)]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(feature = "bytecheck", archive_attr(derive(bytecheck::CheckBytes)))]
+pub struct TypeSchema {
+    /// The name of the type.
+    pub name: String,
+    /// The size of the type in bytes, or `None` if the type is unsized.
+    pub size: Option<usize>,
+    /// The alignment of the type in bytes.
+    pub align: usize,
+    /// What kind of type this is.
+    pub kind: Kind,
+}
+
+impl TypeSchema {
+    /// Returns a description of the sized type `T` with the given kind.
+    pub fn new<T>(kind: Kind) -> Self {
+        Self {
+            name: type_name::<T>().to_string(),
+            size: Some(size_of::<T>()),
+            align: align_of::<T>(),
+            kind,
+        }
+    }
+
+    /// Returns a description of the unsized type `T` with the given alignment
+    /// and kind.
+    pub fn new_unsized<T: ?Sized>(align: usize, kind: Kind) -> Self {
+        Self {
+            name: type_name::<T>().to_string(),
+            size: None,
+            align,
+            kind,
+        }
+    }
+}
+
+/// The kind of an archived type.
+#[derive(Archive, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[archive(crate, schema)]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(feature = "bytecheck", archive_attr(derive(bytecheck::CheckBytes)))]
+pub enum Kind {
+    /// A primitive value.
+    Primitive(Primitive),
+    /// A struct with the given fields.
+    Struct(Vec<Field>),
+    /// An enum with the given variants.
+    ///
+    /// The tag of an archived enum is always a single byte at the start of the
+    /// enum.
+    Enum(Vec<Variant>),
+    /// An array of a fixed number of elements.
+    Array {
+        /// The name of the element type.
+        element: String,
+        /// The number of elements in the array.
+        len: usize,
+    },
+    /// A slice of elements with a length determined by its pointer.
+    Slice {
+        /// The name of the element type.
+        element: String,
+    },
+    /// A UTF-8 string with a length determined by its pointer.
+    Str,
+    /// A type with a layout of its own, like `ArchivedVec` or
+    /// `ArchivedHashMap`.
+    Builtin {
+        /// The name of the layout.
+        ///
+        /// This is the name of the type without any paths or type parameters,
+        /// followed by any other parameters which are needed to read it. For
+        /// example, `ArchivedVec`, `ArchivedBTreeMap<5>`, or
+        /// `ArchivedHashMap<rkyv::hash::FxHasher64>`.
+        layout: String,
+        /// The names of the types which the layout is parameterized over, in
+        /// the order they appear in the rkyv type.
+        arguments: Vec<String>,
+    },
+}
+
+/// A primitive archived type.
+///
+/// Multi-byte primitives are stored with the given endianness. The width of a
+/// primitive is the size of its type.
+#[derive(Archive, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[archive(crate, schema)]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(feature = "bytecheck", archive_attr(derive(bytecheck::CheckBytes)))]
+pub enum Primitive {
+    /// A zero-sized type with no fields, like `()` or `PhantomData`.
+    Unit,
+    /// A `bool`.
+    Bool,
+    /// An integer.
+    Int {
+        /// Whether the integer is signed.
+        signed: bool,
+        /// Whether the integer is guaranteed to be nonzero.
+        nonzero: bool,
+        /// The endianness of the integer.
+        endian: Endian,
+    },
+    /// An IEEE 754 floating-point number.
+    Float {
+        /// The endianness of the float.
+        endian: Endian,
+    },
+    /// A Unicode scalar value stored as a `u32`.
+    Char {
+        /// The endianness of the char.
+        endian: Endian,
+    },
+}
+
+/// The byte order of a primitive.
+#[derive(
+    Archive, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq,
+)]
+#[archive(crate, schema)]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(feature = "bytecheck", archive_attr(derive(bytecheck::CheckBytes)))]
+pub enum Endian {
+    /// Little-endian byte order. Single-byte primitives are always described
+    /// as little-endian.
+    Little,
+    /// Big-endian byte order.
+    Big,
+}
+
+/// A field of a struct or enum variant.
+#[derive(Archive, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[archive(crate, schema)]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(feature = "bytecheck", archive_attr(derive(bytecheck::CheckBytes)))]
+pub struct Field {
+    /// The name of the field, or its index if it is unnamed.
+    pub name: String,
+    /// The offset of the field in bytes from the start of the struct or enum.
+    pub offset: usize,
+    /// The name of the type of the field.
+    pub ty: String,
+}
+
+impl Field {
+    /// Returns a description of a field of type `T` with the given name and
+    /// offset, registering `T` with the given registry.
+    pub fn new<T: ArchiveSchema>(
+        registry: &mut Registry,
+        name: &str,
+        offset: usize,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            offset,
+            ty: registry.register::<T>(),
+        }
+    }
+}
+
+/// A variant of an enum.
+#[derive(Archive, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[archive(crate, schema)]
+#[archive_attr(derive(Debug))]
+#[cfg_attr(feature = "bytecheck", archive_attr(derive(bytecheck::CheckBytes)))]
+pub struct Variant {
+    /// The name of the variant.
+    pub name: String,
+    /// The value of the tag for the variant.
+    pub tag: u8,
+    /// The fields of the variant.
+    pub fields: Vec<Field>,
+}
+
+/// A set of types which have been described.
+///
+/// Each type is only described once, which allows recursive types to refer to
+/// themselves.
+#[derive(Debug, Default)]
+pub struct Registry {
+    names: BTreeMap<TypeId, String>,
+    types: Vec<Option<TypeSchema>>,
+}
+
+impl Registry {
+    /// Returns a new, empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Describes the type `T` if it hasn't been described yet, and returns its
+    /// name.
+    pub fn register<T: ArchiveSchema + ?Sized>(&mut self) -> String {
+        let id = TypeId::of::<T>();
+        if let Some(name) = self.names.get(&id) {
+            return name.clone();
+        }
+
+        let index = self.types.len();
+        let mut name = type_name::<T>().to_string();
+        if self.names.values().any(|other| *other == name) {
+            name = format!("{name}#{index}");
+        }
+        self.names.insert(id, name.clone());
+        // Reserve a spot for the type so that it can refer to itself while
+        // it's being described.
+        self.types.push(None);
+        let mut ty = T::describe(self);
+        ty.name = name.clone();
+        self.types[index] = Some(ty);
+        name
+    }
+
+    /// Returns a schema containing all of the described types.
+    pub fn into_schema(self) -> Schema {
+        Schema {
+            types: self
+                .types
+                .into_iter()
+                .map(|ty| ty.expect("type was not finished being described"))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{boxed::Box, string::String, vec::Vec};
+    use core::mem::{offset_of, size_of};
+
+    use super::{schema, Kind, Primitive};
+    use crate::{
+        string::ArchivedString, test::to_archived, vec::ArchivedVec, Archive,
+        Archived, Serialize,
+    };
+
+    #[test]
+    fn derived_struct_schema() {
+        #[allow(dead_code)]
+        #[derive(Archive)]
+        #[archive(crate, schema)]
+        struct Example {
+            a: u8,
+            b: u32,
+            r#c: Option<Box<str>>,
+            d: (u16, [i64; 2]),
+        }
+
+        let schema = schema::<ArchivedExample>();
+        let root = schema.root();
+        assert_eq!(root.size, Some(size_of::<ArchivedExample>()));
+
+        let Kind::Struct(fields) = &root.kind else {
+            panic!("expected a struct");
+        };
+        let names = fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["a", "b", "c", "d"]);
+        assert_eq!(fields[1].offset, offset_of!(ArchivedExample, b));
+        assert_eq!(fields[2].offset, offset_of!(ArchivedExample, c));
+        assert_eq!(fields[3].offset, offset_of!(ArchivedExample, d));
+
+        let b = schema.get(&fields[1].ty).unwrap();
+        assert!(matches!(
+            b.kind,
+            Kind::Primitive(Primitive::Int {
+                signed: false,
+                nonzero: false,
+                ..
+            })
+        ));
+
+        let Kind::Enum(variants) = &schema.get(&fields[2].ty).unwrap().kind
+        else {
+            panic!("expected an enum");
+        };
+        assert_eq!(variants[1].name, "Some");
+        let boxed = schema.get(&variants[1].fields[0].ty).unwrap();
+        let Kind::Builtin { layout, arguments } = &boxed.kind else {
+            panic!("expected a builtin layout");
+        };
+        assert_eq!(layout, "ArchivedBox");
+        let str = schema.get(&arguments[0]).unwrap();
+        assert_eq!(str.kind, Kind::Str);
+        assert_eq!(str.size, None);
+
+        let Kind::Struct(tuple) = &schema.get(&fields[3].ty).unwrap().kind
+        else {
+            panic!("expected a struct");
+        };
+        assert_eq!(tuple[1].name, "1");
+        let array = schema.get(&tuple[1].ty).unwrap();
+        assert!(matches!(array.kind, Kind::Array { len: 2, .. }));
+    }
+
+    #[test]
+    fn derived_enum_schema() {
+        #[allow(dead_code)]
+        #[derive(Archive, Serialize)]
+        #[archive(crate, schema)]
+        #[repr(u8)]
+        enum Example {
+            A,
+            B(u32) = 5,
+            C { x: u16, y: u64 },
+        }
+
+        let schema = schema::<ArchivedExample>();
+        let Kind::Enum(variants) = &schema.root().kind else {
+            panic!("expected an enum");
+        };
+        let tags = variants.iter().map(|v| v.tag).collect::<Vec<_>>();
+        assert_eq!(tags, [0, 5, 6]);
+        assert!(variants[0].fields.is_empty());
+
+        to_archived(&Example::C { x: 1, y: 2 }, |archived| {
+            let ArchivedExample::C { x, y } = archived else {
+                panic!("expected variant C");
+            };
+            let base = archived as *const ArchivedExample as usize;
+            let fields = &variants[2].fields;
+            assert_eq!(fields[0].name, "x");
+            assert_eq!(fields[0].offset, x as *const _ as usize - base);
+            assert_eq!(fields[1].name, "y");
+            assert_eq!(fields[1].offset, y as *const _ as usize - base);
+        });
+    }
+
+    #[test]
+    fn recursive_schema() {
+        #[allow(dead_code)]
+        #[derive(Archive)]
+        #[archive(crate, schema)]
+        struct Node {
+            name: String,
+            #[omit_bounds]
+            #[archive_attr(omit_bounds)]
+            children: Vec<Node>,
+        }
+
+        let schema = schema::<ArchivedNode>();
+        assert_eq!(schema.types.len(), 3);
+        let Kind::Struct(fields) = &schema.root().kind else {
+            panic!("expected a struct");
+        };
+        assert_eq!(fields[0].ty, core::any::type_name::<ArchivedString>());
+        assert_eq!(
+            fields[1].ty,
+            core::any::type_name::<ArchivedVec<ArchivedNode>>()
+        );
+        let Kind::Builtin { arguments, .. } =
+            &schema.get(&fields[1].ty).unwrap().kind
+        else {
+            panic!("expected a builtin layout");
+        };
+        assert_eq!(arguments[0], schema.root().name);
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn archived_schema() {
+        use rancor::Error;
+
+        use super::Schema;
+
+        let schema = schema::<Archived<Schema>>();
+        let bytes = crate::to_bytes::<Error>(&schema).unwrap();
+        let deserialized = crate::from_bytes::<Schema, Error>(&bytes).unwrap();
+        assert_eq!(deserialized, schema);
+    }
+}
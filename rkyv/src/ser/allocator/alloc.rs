@@ -168,9 +168,13 @@ impl Arena {
     pub fn acquire(&mut self) -> ArenaHandle<'_> {
         self.shrink();
 
+        // After shrinking, the head is the only block in the loop.
+        let head_size = unsafe { self.head_ptr.as_ref().next_size };
         ArenaHandle {
+            head_ptr: self.head_ptr,
+            head_size,
             tail_ptr: self.head_ptr,
-            tail_size: unsafe { self.head_ptr.as_ref().next_size },
+            tail_size: head_size,
             used: size_of::<Block>(),
             _phantom: PhantomData,
         }
@@ -204,6 +208,8 @@ impl Default for Arena {
 
 /// A handle which can allocate within an arena.
 pub struct ArenaHandle<'a> {
+    head_ptr: NonNull<Block>,
+    head_size: usize,
     tail_ptr: NonNull<Block>,
     tail_size: usize,
     used: usize,
@@ -253,9 +259,99 @@ unsafe impl<E> Allocator<E> for ArenaHandle<'_> {
         ptr: NonNull<u8>,
         _: Layout,
     ) -> Result<(), E> {
-        let bytes = self.tail_ptr.as_ptr().cast::<u8>();
-        self.used = ptr.as_ptr() as usize - bytes as usize;
+        let addr = ptr.as_ptr() as usize;
+
+        // Allocations start after the block header, and zero-sized allocations
+        // may end exactly at the end of their block.
+        let contains = |block: NonNull<Block>, size: usize| {
+            let start = block.as_ptr() as usize;
+            (start + size_of::<Block>()..=start + size).contains(&addr)
+        };
+
+        if !contains(self.tail_ptr, self.tail_size) {
+            // Nested allocations may have moved on to newer blocks. In that
+            // case, walk the loop of blocks once to find the one containing
+            // `ptr`.
+            let mut current_ptr = self.head_ptr;
+            let mut current_size = self.head_size;
+            let found = loop {
+                if contains(current_ptr, current_size) {
+                    break true;
+                }
+
+                let current = unsafe { current_ptr.as_ref() };
+                current_ptr = current.next_ptr;
+                current_size = current.next_size;
+                if current_ptr == self.head_ptr {
+                    break false;
+                }
+            };
+
+            debug_assert!(
+                found,
+                "popped allocation is not in any block of the arena",
+            );
+            if !found {
+                return Ok(());
+            }
+            self.tail_ptr = current_ptr;
+            self.tail_size = current_size;
+        }
+
+        self.used = addr - self.tail_ptr.as_ptr() as usize;
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::{alloc::Layout, ptr::NonNull};
+
+    use rancor::Panic;
+
+    use super::{Arena, ArenaHandle};
+    use crate::ser::Allocator;
+
+    fn push(handle: &mut ArenaHandle<'_>, layout: Layout) -> NonNull<u8> {
+        unsafe { Allocator::<Panic>::push_alloc(handle, layout) }
+            .unwrap()
+            .cast()
+    }
+
+    fn pop(handle: &mut ArenaHandle<'_>, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { Allocator::<Panic>::pop_alloc(handle, ptr, layout) }.unwrap()
+    }
+
+    #[test]
+    fn pop_alloc_from_earlier_block() {
+        let mut arena = Arena::with_capacity(64);
+        let mut handle = arena.acquire();
+
+        let small = Layout::from_size_align(16, 8).unwrap();
+        let large = Layout::from_size_align(1024, 8).unwrap();
+        let first = push(&mut handle, small);
+        // Too large for the first block, so this moves on to a new block.
+        let second = push(&mut handle, large);
+        pop(&mut handle, second, large);
+        pop(&mut handle, first, small);
+
+        assert_eq!(push(&mut handle, small), first);
+    }
+
+    #[test]
+    fn pop_zero_sized_alloc_at_block_end() {
+        let mut arena = Arena::with_capacity(64);
+        let capacity = arena.capacity();
+        let mut handle = arena.acquire();
+
+        let full = Layout::from_size_align(capacity, 1).unwrap();
+        let empty = Layout::from_size_align(0, 1).unwrap();
+        let first = push(&mut handle, full);
+        let second = push(&mut handle, empty);
+        pop(&mut handle, second, empty);
+        pop(&mut handle, first, full);
+
+        assert_eq!(push(&mut handle, full), first);
+    }
+}
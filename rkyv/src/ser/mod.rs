@@ -1,6 +1,7 @@
 //! Serialization traits and adapters.
 
 pub mod allocator;
+pub mod seed;
 pub mod sharing;
 pub mod writer;
 
@@ -10,6 +11,7 @@ use rancor::Strategy;
 #[doc(inline)]
 pub use self::{
     allocator::Allocator,
+    seed::SeedProvider,
     sharing::{Sharing, SharingExt},
     writer::{Positional, Writer, WriterExt},
 };
@@ -0,0 +1,116 @@
+//! Keys for the hashers of archived hash tables.
+
+use core::{alloc::Layout, ptr::NonNull};
+
+use rancor::Strategy;
+
+use crate::ser::{Allocator, Positional, Serializer, Sharing, Writer};
+
+/// The key that serializers use for keyed hashers unless they are given one.
+///
+/// This key is public, so it does not protect hash tables against keys which
+/// were chosen to collide. Use [`WithSeed`] to choose a secret key instead.
+pub const DEFAULT_HASHER_KEY: [u64; 2] =
+    [0x243f_6a88_85a3_08d3, 0x1319_8a2e_0370_7344];
+
+/// A serializer that can provide a key for keyed hashers.
+///
+/// This trait is required to serialize hash maps and sets with
+/// [`Seeded`](crate::with::Seeded). Every hash table in an archive is built
+/// with the key returned by the serializer, so serializing the same value with
+/// the same key always produces the same bytes.
+pub trait SeedProvider {
+    /// Returns the key to build archived hash tables with.
+    fn hasher_key(&self) -> [u64; 2];
+}
+
+impl<T: SeedProvider + ?Sized, E> SeedProvider for Strategy<T, E> {
+    fn hasher_key(&self) -> [u64; 2] {
+        T::hasher_key(self)
+    }
+}
+
+impl<W, A, S> SeedProvider for Serializer<W, A, S> {
+    fn hasher_key(&self) -> [u64; 2] {
+        DEFAULT_HASHER_KEY
+    }
+}
+
+/// A serializer adapter that provides a chosen key for keyed hashers.
+///
+/// All other capabilities are forwarded to the inner serializer.
+#[derive(Debug, Default)]
+pub struct WithSeed<S> {
+    key: [u64; 2],
+    inner: S,
+}
+
+impl<S> WithSeed<S> {
+    /// Creates a new serializer which builds hash tables with the given key.
+    pub fn new(key: [u64; 2], inner: S) -> Self {
+        Self { key, inner }
+    }
+
+    /// Creates a new serializer which builds hash tables with a random key.
+    ///
+    /// The key is chosen once, so every hash table serialized with the
+    /// returned serializer uses the same key.
+    #[cfg(feature = "std")]
+    pub fn random(inner: S) -> Self {
+        Self::new(crate::hash::random_key(), inner)
+    }
+
+    /// Consumes the adapter and returns the inner serializer.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> SeedProvider for WithSeed<S> {
+    fn hasher_key(&self) -> [u64; 2] {
+        self.key
+    }
+}
+
+impl<S: Positional> Positional for WithSeed<S> {
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+}
+
+impl<S: Writer<E>, E> Writer<E> for WithSeed<S> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.inner.write(bytes)
+    }
+}
+
+unsafe impl<S: Allocator<E>, E> Allocator<E> for WithSeed<S> {
+    unsafe fn push_alloc(
+        &mut self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, E> {
+        // SAFETY: The safety requirements for `S::push_alloc()` are the same as
+        // the safety requirements for `push_alloc()`.
+        unsafe { self.inner.push_alloc(layout) }
+    }
+
+    unsafe fn pop_alloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `S::pop_alloc()` are the same as
+        // the safety requirements for `pop_alloc()`.
+        unsafe { self.inner.pop_alloc(ptr, layout) }
+    }
+}
+
+impl<S: Sharing<E>, E> Sharing<E> for WithSeed<S> {
+    fn get_shared_ptr(&self, address: usize) -> Option<usize> {
+        self.inner.get_shared_ptr(address)
+    }
+
+    fn add_shared_ptr(&mut self, address: usize, pos: usize) -> Result<(), E> {
+        self.inner.add_shared_ptr(address, pos)
+    }
+}
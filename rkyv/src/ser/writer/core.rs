@@ -12,10 +12,10 @@ use rancor::{fail, Source};
 use crate::ser::{Positional, Writer};
 
 #[derive(Debug)]
-struct BufferOverflow {
-    write_len: usize,
-    cap: usize,
-    len: usize,
+pub(super) struct BufferOverflow {
+    pub(super) write_len: usize,
+    pub(super) cap: usize,
+    pub(super) len: usize,
 }
 
 impl fmt::Display for BufferOverflow {
@@ -167,13 +167,76 @@ impl<E: Source> Writer<E> for Buffer<'_> {
     }
 }
 
+/// A writer that counts the number of bytes written without storing them.
+///
+/// Because padding is written like any other bytes, serializing a value with a
+/// `SizeCounter` computes the exact number of bytes that serializing it with
+/// any other writer would produce. This can be used to size a [`Buffer`] or
+/// pre-allocate an [`AlignedVec`](crate::util::AlignedVec) so that
+/// serialization never overflows or reallocates.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     rancor::Error,
+///     ser::{writer::SizeCounter, Positional},
+///     to_bytes, to_bytes_in,
+/// };
+///
+/// let value = vec!["hello".to_string(), "world".to_string()];
+///
+/// let counter = to_bytes_in::<_, Error>(&value, SizeCounter::new()).unwrap();
+/// let bytes = to_bytes::<Error>(&value).unwrap();
+/// assert_eq!(counter.pos(), bytes.len());
+/// ```
+#[derive(Debug, Default)]
+pub struct SizeCounter {
+    len: usize,
+}
+
+impl SizeCounter {
+    /// Creates a new size counter starting at position 0.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_pos(0)
+    }
+
+    /// Creates a new size counter, and assumes that it is currently at the
+    /// given position.
+    #[inline]
+    pub fn with_pos(pos: usize) -> Self {
+        Self { len: pos }
+    }
+}
+
+impl Positional for SizeCounter {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.len
+    }
+}
+
+impl<E> Writer<E> for SizeCounter {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.len += bytes.len();
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::mem::MaybeUninit;
 
     use rancor::Panic;
 
-    use crate::{ser::writer::Buffer, to_bytes_in};
+    use crate::{
+        ser::{
+            writer::{Buffer, SizeCounter},
+            Positional as _,
+        },
+        to_bytes_in,
+    };
 
     #[test]
     fn zeroes_padding() {
@@ -198,4 +261,30 @@ mod tests {
             .iter()
             .all(|&b| b == 0));
     }
+
+    #[test]
+    fn size_counter_matches_buffer() {
+        use crate::{Archive, Serialize};
+
+        #[derive(Archive, Serialize)]
+        #[archive(crate)]
+        pub struct Example {
+            a: u8,
+            b: Option<u64>,
+            c: [u16; 3],
+        }
+
+        let value = Example {
+            a: 1,
+            b: Some(2),
+            c: [3, 4, 5],
+        };
+
+        let mut bytes = [MaybeUninit::uninit(); 256];
+        let buffer =
+            to_bytes_in::<_, Panic>(&value, Buffer::from(&mut bytes)).unwrap();
+        let counter =
+            to_bytes_in::<_, Panic>(&value, SizeCounter::new()).unwrap();
+        assert_eq!(counter.pos(), buffer.len());
+    }
 }
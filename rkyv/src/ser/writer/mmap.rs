@@ -0,0 +1,227 @@
+use std::{fs::File, io, path::Path};
+
+use memmap2::MmapMut;
+use rancor::{fail, ResultExt as _, Source};
+
+use super::core::BufferOverflow;
+use crate::ser::{Positional, Writer};
+
+const MIN_CAPACITY: usize = 4096;
+
+/// Wraps a [`File`] and equips it with [`Writer`] by writing through a growable
+/// memory map.
+///
+/// The file is resized and remapped as the archive grows, so large archives can
+/// be written without buffering them in memory. Once serialization is done,
+/// [`finish`](MmapWriter::finish) must be called to truncate the file to the
+/// length of the archive. Otherwise, the file may be left with trailing padding
+/// and the root will not be located at the end of the file.
+///
+/// Creating a writer is unsafe because the file is mapped into memory, and
+/// modifying or truncating it through any other handle while it is mapped is
+/// undefined behavior.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     rancor::Error, ser::writer::MmapWriter, to_bytes_in, util::access_file,
+///     Archived,
+/// };
+///
+/// let path = std::env::temp_dir()
+///     .join(format!("rkyv_mmap_writer_example_{}.bin", std::process::id()));
+///
+/// let value = vec!["hello".to_string(), "world".to_string()];
+/// // SAFETY: The file is not modified through any other handle while it is
+/// // mapped.
+/// let writer = unsafe { MmapWriter::create(&path).unwrap() };
+/// to_bytes_in::<_, Error>(&value, writer)
+///     .unwrap()
+///     .finish::<Error>()
+///     .unwrap();
+///
+/// // SAFETY: The file is not modified while it is mapped.
+/// let archived =
+///     unsafe { access_file::<Archived<Vec<String>>, Error>(&path).unwrap() };
+/// assert_eq!(archived.len(), 2);
+/// assert_eq!(archived[1], "world");
+/// # drop(archived);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MmapWriter {
+    file: File,
+    map: MmapMut,
+    pos: usize,
+}
+
+impl MmapWriter {
+    /// Creates a new writer that writes to the file at the given path.
+    ///
+    /// The file is created if it does not exist, and truncated if it does.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated by anything other than the
+    /// writer until it is finished, including by other processes.
+    pub unsafe fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        // SAFETY: The caller has guaranteed that the file will not be modified
+        // by anything other than the writer.
+        unsafe { Self::new(file) }
+    }
+
+    /// Creates a new writer that writes to the given file.
+    ///
+    /// The file must be opened for both reading and writing. Any existing
+    /// contents of the file will be overwritten.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated by anything other than the
+    /// writer until it is finished, including by other processes.
+    pub unsafe fn new(file: File) -> io::Result<Self> {
+        // SAFETY: The caller has guaranteed that the file will not be modified
+        // by anything other than the writer.
+        unsafe { Self::with_capacity(file, MIN_CAPACITY) }
+    }
+
+    /// Creates a new writer that writes to the given file, and reserves at
+    /// least `capacity` bytes up front.
+    ///
+    /// The file must be opened for both reading and writing. Any existing
+    /// contents of the file will be overwritten.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated by anything other than the
+    /// writer until it is finished, including by other processes.
+    pub unsafe fn with_capacity(
+        file: File,
+        capacity: usize,
+    ) -> io::Result<Self> {
+        let capacity = capacity.max(MIN_CAPACITY);
+        file.set_len(capacity as u64)?;
+        // SAFETY: The file is owned by the writer, and the caller has
+        // guaranteed that it will not be modified by anything else.
+        let map = unsafe { MmapMut::map_mut(&file)? };
+        Ok(Self { file, map, pos: 0 })
+    }
+
+    /// Returns the number of bytes that can be written before the file has to
+    /// be resized.
+    pub fn capacity(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns the bytes that have been written so far.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.map[..self.pos]
+    }
+
+    fn grow(&mut self, min_capacity: usize) -> io::Result<()> {
+        let new_capacity = min_capacity
+            .checked_next_power_of_two()
+            .unwrap_or(min_capacity)
+            .max(MIN_CAPACITY);
+
+        self.map.flush()?;
+        self.file.set_len(new_capacity as u64)?;
+        // SAFETY: See `with_capacity`.
+        self.map = unsafe { MmapMut::map_mut(&self.file)? };
+        Ok(())
+    }
+
+    /// Flushes the written bytes to disk, truncates the file to the length of
+    /// the archive, and returns the underlying file.
+    pub fn finish<E: Source>(self) -> Result<File, E> {
+        let Self { file, map, pos } = self;
+        map.flush().into_error()?;
+        drop(map);
+        file.set_len(pos as u64).into_error()?;
+        Ok(file)
+    }
+}
+
+impl Positional for MmapWriter {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<E: Source> Writer<E> for MmapWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        let Some(end) = self.pos.checked_add(bytes.len()) else {
+            fail!(BufferOverflow {
+                write_len: bytes.len(),
+                cap: self.map.len(),
+                len: self.pos,
+            });
+        };
+        if end > self.map.len() {
+            self.grow(end).into_error()?;
+        }
+        self.map[self.pos..end].copy_from_slice(bytes);
+        self.pos = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::{Failure, Panic};
+
+    use crate::{
+        ser::{writer::MmapWriter, Writer},
+        to_bytes, to_bytes_in,
+        util::access_file,
+        Archived,
+    };
+
+    #[test]
+    fn grows_past_initial_capacity() {
+        let path = std::env::temp_dir().join(format!(
+            "rkyv_test_mmap_writer_grows_{}.bin",
+            std::process::id(),
+        ));
+
+        let value = (0..10_000u32).collect::<Vec<_>>();
+        let writer = unsafe { MmapWriter::create(&path).unwrap() };
+        let file = to_bytes_in::<_, Panic>(&value, writer)
+            .unwrap()
+            .finish::<Panic>()
+            .unwrap();
+        assert_eq!(
+            file.metadata().unwrap().len() as usize,
+            to_bytes::<Panic>(&value).unwrap().len(),
+        );
+
+        let archived =
+            unsafe { access_file::<Archived<Vec<u32>>, Panic>(&path).unwrap() };
+        assert_eq!(archived.as_slice(), value.as_slice());
+
+        drop(archived);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn write_past_max_position() {
+        let path = std::env::temp_dir().join(format!(
+            "rkyv_test_mmap_writer_overflow_{}.bin",
+            std::process::id(),
+        ));
+
+        let mut writer = unsafe { MmapWriter::create(&path).unwrap() };
+        writer.pos = usize::MAX;
+        assert!(Writer::<Failure>::write(&mut writer, &[0]).is_err());
+
+        drop(writer);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
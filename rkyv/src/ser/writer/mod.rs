@@ -3,6 +3,8 @@
 #[cfg(feature = "alloc")]
 mod alloc;
 mod core;
+#[cfg(feature = "memmap2")]
+mod mmap;
 #[cfg(feature = "std")]
 mod std;
 
@@ -10,6 +12,8 @@ use ::core::mem;
 use rancor::{Fallible, Strategy};
 
 pub use self::core::*;
+#[cfg(feature = "memmap2")]
+pub use self::mmap::*;
 #[cfg(feature = "std")]
 pub use self::std::*;
 use crate::{Archive, ArchiveUnsized, Place, RelPtr};
@@ -0,0 +1,88 @@
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m256i, _mm256_cmpeq_epi8, _mm256_loadu2_m128i, _mm256_movemask_epi8,
+    _mm256_set1_epi8,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m256i, _mm256_cmpeq_epi8, _mm256_loadu2_m128i, _mm256_movemask_epi8,
+    _mm256_set1_epi8,
+};
+use core::num::NonZeroU32;
+
+// Archived hash tables are probed in 16-byte groups, so AVX2 uses the SSE2
+// group for single probes. Its wider registers are used to read two probes at
+// once with `GroupPair`.
+pub use super::sse2::{Bitmask, Group};
+
+#[derive(Clone, Copy)]
+pub struct PairBitmask(u32);
+
+impl PairBitmask {
+    #[inline]
+    pub fn any_bit_set(self) -> bool {
+        self.0 != 0
+    }
+
+    #[inline]
+    pub fn remove_lowest_bit(self) -> Self {
+        Self(self.0 & (self.0 - 1))
+    }
+
+    #[inline]
+    pub fn lowest_set_bit(self) -> Option<usize> {
+        let nonzero = NonZeroU32::new(self.0)?;
+        Some(nonzero.trailing_zeros() as usize)
+    }
+}
+
+impl Iterator for PairBitmask {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bit = self.lowest_set_bit()?;
+        *self = self.remove_lowest_bit();
+        Some(bit)
+    }
+}
+
+/// Two groups read into one AVX2 register.
+///
+/// The bits of the first group come before the bits of the second group in
+/// each mask, so bits below `Group::WIDTH` belong to the first group.
+#[derive(Clone, Copy)]
+pub struct GroupPair(__m256i);
+
+// The AVX2 intrinsics used below are safe to call on newer compilers because
+// AVX2 is statically enabled, but older compilers still require `unsafe`.
+#[allow(unused_unsafe)]
+impl GroupPair {
+    /// # Safety
+    ///
+    /// `first` and `second` must be valid for reads and each point to enough
+    /// bytes for a `Group`.
+    #[inline]
+    pub unsafe fn read(first: *const u8, second: *const u8) -> Self {
+        // SAFETY: The caller has guaranteed that `first` and `second` are valid
+        // for reads and point to enough bytes for a `Group`.
+        // `_mm256_loadu2_m128i` does not require either pointer to be aligned.
+        unsafe { Self(_mm256_loadu2_m128i(second.cast(), first.cast())) }
+    }
+
+    #[inline]
+    pub fn match_byte(self, byte: u8) -> PairBitmask {
+        // SAFETY: AVX2 is enabled at compile time.
+        unsafe {
+            let cmp = _mm256_cmpeq_epi8(self.0, _mm256_set1_epi8(byte as i8));
+            PairBitmask(_mm256_movemask_epi8(cmp) as u32)
+        }
+    }
+
+    #[inline]
+    pub fn match_empty(self) -> PairBitmask {
+        // Empty control bytes have their high bit set, and `movemask` collects
+        // the high bit of each byte.
+        // SAFETY: AVX2 is enabled at compile time.
+        unsafe { PairBitmask(_mm256_movemask_epi8(self.0) as u32) }
+    }
+}
@@ -1,8 +1,194 @@
-#[path = "generic.rs"]
-mod group;
-
-// TODO: add optimized SIMD implementations for sse2 and neon
-
-pub use group::*;
-
-pub const MAX_GROUP_WIDTH: usize = 16;
+// The group implementation is selected at compile time. Archived hash tables
+// are probed in groups of `MAX_GROUP_WIDTH` bytes, and implementations with
+// narrower groups read several groups per probe so that they all probe
+// archived hash tables identically. The control bytes of archived hash tables
+// are padded to `MAX_GROUP_WIDTH`, so changing it changes the archived format.
+//
+// AVX2 does not widen the groups. Instead, it provides a `GroupPair` which
+// reads two probes into one register.
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "avx2",
+))]
+#[path = "sse2.rs"]
+mod sse2;
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "avx2",
+))]
+#[path = "avx2.rs"]
+mod group;
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(target_feature = "avx2"),
+))]
+#[path = "sse2.rs"]
+mod group;
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+)))]
+#[path = "generic.rs"]
+mod group;
+
+// TODO: add optimized SIMD implementation for neon
+
+pub use group::*;
+
+pub const MAX_GROUP_WIDTH: usize = 16;
+
+// Tests compare the selected implementation against the generic one.
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "generic.rs"]
+mod generic;
+
+#[cfg(test)]
+mod tests {
+    use super::MAX_GROUP_WIDTH;
+
+    macro_rules! define_probe {
+        ($name:ident, $module:ident) => {
+            /// Returns the bits matched by `match_byte`, `match_empty`, and
+            /// `match_full` for each byte in a maximum-width probe.
+            fn $name(bytes: &[u8; MAX_GROUP_WIDTH], byte: u8) -> [u32; 3] {
+                use super::$module::Group;
+
+                let mut result = [0; 3];
+                for offset in (0..MAX_GROUP_WIDTH).step_by(Group::WIDTH) {
+                    let group =
+                        unsafe { Group::read(bytes.as_ptr().add(offset)) };
+                    let masks = [
+                        group.match_byte(byte),
+                        group.match_empty(),
+                        group.match_full(),
+                    ];
+                    for (bits, mask) in result.iter_mut().zip(masks) {
+                        for bit in mask {
+                            *bits |= 1 << (offset + bit);
+                        }
+                    }
+                }
+                result
+            }
+        };
+    }
+
+    define_probe!(probe, group);
+    define_probe!(probe_generic, generic);
+
+    /// Returns the bits matched by `match_byte` and `match_empty` for each
+    /// byte in two maximum-width probes read as a pair.
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "avx2",
+    ))]
+    fn probe_pair(
+        first: &[u8; MAX_GROUP_WIDTH],
+        second: &[u8; MAX_GROUP_WIDTH],
+        byte: u8,
+    ) -> [u32; 2] {
+        use super::group::GroupPair;
+
+        let pair = unsafe { GroupPair::read(first.as_ptr(), second.as_ptr()) };
+        [pair.match_byte(byte), pair.match_empty()]
+            .map(|mask| mask.fold(0, |bits, bit| bits | 1 << bit))
+    }
+
+    #[test]
+    fn max_group_width_is_stable() {
+        // The control bytes of archived hash tables are padded to the maximum
+        // group width, so it must not change between implementations or
+        // releases.
+        assert_eq!(MAX_GROUP_WIDTH, 16);
+    }
+
+    #[test]
+    fn groups_match_generic() {
+        // A simple xorshift generator keeps the control bytes deterministic.
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..10_000 {
+            let mut bytes = [0u8; MAX_GROUP_WIDTH];
+            for byte in bytes.iter_mut() {
+                let random = next();
+                // Mix empty control bytes with a small range of hashes so that
+                // groups contain repeated matches.
+                *byte = if random & 3 == 0 {
+                    0xff
+                } else {
+                    (random >> 8) as u8 & 0x0f
+                };
+            }
+            let byte = (next() >> 8) as u8 & 0x0f;
+
+            let bits_where = |f: &dyn Fn(u8) -> bool| {
+                (0..MAX_GROUP_WIDTH)
+                    .filter(|&i| f(bytes[i]))
+                    .fold(0u32, |bits, i| bits | 1 << i)
+            };
+            let matching = bits_where(&|b| b == byte);
+            let empty = bits_where(&|b| b & 0x80 != 0);
+            let full = bits_where(&|b| b & 0x80 == 0);
+
+            // The generic implementation of `match_byte` may return false
+            // positives next to real matches, so only the real matches are
+            // required to agree.
+            let superset = |[matched, matched_empty, matched_full]: [u32; 3]| {
+                assert_eq!(matched & matching, matching, "{bytes:?} {byte}");
+                assert_eq!(matched_empty, empty, "{bytes:?}");
+                assert_eq!(matched_full, full, "{bytes:?}");
+            };
+            // The SIMD implementations must match exactly.
+            #[allow(unused_variables)]
+            let exact = |probed: [u32; 3]| {
+                let expected = [matching, empty, full];
+                assert_eq!(probed, expected, "{bytes:?} {byte}");
+            };
+
+            superset(probe_generic(&bytes, byte));
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                target_feature = "sse2",
+            ))]
+            exact(probe(&bytes, byte));
+            #[cfg(not(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                target_feature = "sse2",
+            )))]
+            superset(probe(&bytes, byte));
+
+            // Pairs must match the two probes read separately.
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                target_feature = "avx2",
+            ))]
+            {
+                let mut other = bytes;
+                other.reverse();
+                let [matched, matched_empty, _] = probe(&bytes, byte);
+                let [other_matched, other_empty, _] = probe(&other, byte);
+                let shift = MAX_GROUP_WIDTH;
+                assert_eq!(
+                    probe_pair(&bytes, &other, byte),
+                    [
+                        matched | other_matched << shift,
+                        matched_empty | other_empty << shift,
+                    ],
+                    "{bytes:?} {byte}",
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8, _mm_set1_epi8,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8, _mm_set1_epi8,
+};
+use core::{mem::size_of, num::NonZeroU16};
+
+#[derive(Clone, Copy)]
+pub struct Bitmask(u16);
+
+impl Bitmask {
+    pub const EMPTY: Self = Bitmask(0);
+
+    #[inline]
+    pub fn any_bit_set(self) -> bool {
+        self.0 != 0
+    }
+
+    #[inline]
+    pub fn remove_lowest_bit(self) -> Self {
+        Self(self.0 & (self.0 - 1))
+    }
+
+    #[inline]
+    pub fn lowest_set_bit(self) -> Option<usize> {
+        let nonzero = NonZeroU16::new(self.0)?;
+        Some(nonzero.trailing_zeros() as usize)
+    }
+}
+
+impl Iterator for Bitmask {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bit = self.lowest_set_bit()?;
+        *self = self.remove_lowest_bit();
+        Some(bit)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Group(__m128i);
+
+// The SSE2 intrinsics used below are safe to call on newer compilers because
+// SSE2 is statically enabled, but older compilers still require `unsafe`.
+#[allow(unused_unsafe)]
+impl Group {
+    pub const WIDTH: usize = size_of::<__m128i>();
+
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads and point to enough bytes for a `__m128i`.
+    #[inline]
+    pub unsafe fn read(ptr: *const u8) -> Self {
+        // SAFETY: The caller has guaranteed that `ptr` is valid for reads and
+        // points to enough bytes for a `__m128i`. `_mm_loadu_si128` does not
+        // require `ptr` to be aligned.
+        unsafe { Self(_mm_loadu_si128(ptr.cast())) }
+    }
+
+    #[inline]
+    pub fn match_byte(self, byte: u8) -> Bitmask {
+        // SAFETY: SSE2 is enabled at compile time.
+        unsafe {
+            let cmp = _mm_cmpeq_epi8(self.0, _mm_set1_epi8(byte as i8));
+            Bitmask(_mm_movemask_epi8(cmp) as u16)
+        }
+    }
+
+    #[inline]
+    pub fn match_empty(self) -> Bitmask {
+        // Empty control bytes have their high bit set, and `movemask` collects
+        // the high bit of each byte.
+        // SAFETY: SSE2 is enabled at compile time.
+        unsafe { Bitmask(_mm_movemask_epi8(self.0) as u16) }
+    }
+
+    #[inline]
+    pub fn match_full(self) -> Bitmask {
+        Bitmask(!self.match_empty().0)
+    }
+}
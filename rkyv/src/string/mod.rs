@@ -17,7 +17,7 @@ use munge::munge;
 use rancor::Fallible;
 use repr::{ArchivedStringRepr, INLINE_CAPACITY};
 
-use crate::{Place, Portable, SerializeUnsized};
+use crate::{Place, Portable, Relocate, SerializeUnsized};
 
 /// An archived [`String`].
 ///
@@ -30,8 +30,9 @@ use crate::{Place, Portable, SerializeUnsized};
     derive(bytecheck::CheckBytes),
     check_bytes(verify)
 )]
-#[derive(Portable)]
+#[derive(Portable, Relocate)]
 #[archive(crate)]
+#[relocate(unsafe_no_offsets)]
 pub struct ArchivedString {
     repr: repr::ArchivedStringRepr,
 }
@@ -1,13 +1,13 @@
 //! An archived string representation that supports inlining short strings.
 
-use core::{marker::PhantomPinned, mem, ptr, slice, str};
+use core::{marker::PhantomPinned, mem, pin::Pin, ptr, slice, str};
 
 use munge::munge;
 use rancor::{Panic, ResultExt as _, Source};
 
 use crate::{
     primitive::{ArchivedUsize, FixedIsize},
-    Place, Portable,
+    Extent, Place, Portable, Relocate,
 };
 
 const OFFSET_BYTES: usize = mem::size_of::<FixedIsize>();
@@ -230,6 +230,50 @@ impl ArchivedStringRepr {
     }
 }
 
+// SAFETY: Inline representations don't point to anything. Out-of-line
+// representations own exactly the bytes of the string, and `relocate` adjusts
+// their offset by `delta`. Out-of-line offsets must stay negative because the
+// sign bit marks the representation as out-of-line, and `can_relocate` checks
+// for that.
+unsafe impl Relocate for ArchivedStringRepr {
+    fn extent(&self) -> Option<Extent> {
+        if self.is_inline() {
+            None
+        } else {
+            let start = self.as_ptr() as usize;
+            Some(Extent {
+                start,
+                end: start + self.len(),
+                align: 1,
+            })
+        }
+    }
+
+    fn can_relocate(&self, delta: isize) -> bool {
+        if self.is_inline() {
+            return true;
+        }
+
+        // SAFETY: We just checked that the representation is out-of-line.
+        let offset = unsafe { self.out_of_line_offset() };
+        offset.checked_sub(delta).is_some_and(|offset| {
+            offset < 0 && FixedIsize::try_from(offset).is_ok()
+        })
+    }
+
+    unsafe fn relocate(self: Pin<&mut Self>, delta: isize) {
+        if !self.is_inline() {
+            // SAFETY: We just checked that the representation is out-of-line,
+            // and we never move out of the pinned representation.
+            unsafe {
+                let offset = self.out_of_line_offset() - delta;
+                self.get_unchecked_mut().out_of_line.offset =
+                    (offset as FixedIsize).to_le_bytes();
+            }
+        }
+    }
+}
+
 #[cfg(feature = "bytecheck")]
 const _: () = {
     use core::fmt;
@@ -45,6 +45,15 @@ mod detail {
         crate::deserialize::<T, _, Panic>(value, &mut Unpool)
             .expect("failed to deserialize value")
     }
+
+    pub fn deserialize_into<T>(value: &T::Archived, target: &mut T)
+    where
+        T: Archive,
+        T::Archived: Deserialize<T, TestDeserializer>,
+    {
+        crate::deserialize_into::<T, _, Panic>(value, target, &mut Unpool)
+            .expect("failed to deserialize value")
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -76,11 +85,22 @@ mod detail {
         crate::deserialize::<T, _, Panic>(value, &mut Pool::new())
             .expect("failed to deserialize value")
     }
+
+    pub fn deserialize_into<T>(value: &T::Archived, target: &mut T)
+    where
+        T: Archive,
+        T::Archived: Deserialize<T, TestDeserializer>,
+    {
+        crate::deserialize_into::<T, _, Panic>(value, target, &mut Pool::new())
+            .expect("failed to deserialize value")
+    }
 }
 
 use core::fmt::Debug;
 
-use self::detail::{deserialize, to_bytes, TestDeserializer, TestSerializer};
+use self::detail::{
+    deserialize, deserialize_into, to_bytes, TestDeserializer, TestSerializer,
+};
 use crate::{access_unchecked, Deserialize, Serialize};
 
 pub fn to_archived<T>(value: &T, f: impl FnOnce(&T::Archived))
@@ -112,3 +132,14 @@ where
 {
     roundtrip_with(value, |a, b| assert_eq!(b, a));
 }
+
+pub fn roundtrip_into<T>(value: &T, target: &mut T)
+where
+    T: Debug + PartialEq + for<'a> Serialize<TestSerializer<'a>>,
+    T::Archived: Deserialize<T, TestDeserializer>,
+{
+    to_archived(value, |archived_value| {
+        deserialize_into::<T>(archived_value, target);
+        assert_eq!(value, target);
+    });
+}
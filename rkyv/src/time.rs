@@ -16,6 +16,7 @@ use crate::{
     derive(bytecheck::CheckBytes),
     check_bytes(verify)
 )]
+#[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
 pub struct ArchivedDuration {
     secs: ArchivedU64,
     nanos: ArchivedU32,
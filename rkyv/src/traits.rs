@@ -4,6 +4,7 @@ use core::{
     alloc::{Layout, LayoutError},
     hash::Hash,
     marker::PhantomData,
+    pin::Pin,
 };
 
 use crate::{
@@ -23,6 +24,104 @@ use crate::{
 /// pointers to must also be `Portable`.
 pub unsafe trait Portable {}
 
+/// A [`Portable`] type which can be moved within a buffer by adjusting the
+/// relative pointers it contains.
+///
+/// Relative pointers are only valid as long as the pointer and its target move
+/// together, so archived values normally can't be moved around on their own.
+/// Types which implement `Relocate` report the [`Extent`] of the out-of-line
+/// data they own and know how to fix up their relative pointers after being
+/// moved relative to it. This is what allows archived slices to be reordered in
+/// place (e.g. with [`ArchivedVec::swap`](crate::vec::ArchivedVec::swap)).
+///
+/// `Relocate` can be implemented for archived types generated by
+/// `#[derive(Archive)]` with `#[archive(relocate)]`. Other types can use
+/// `#[derive(Relocate)]`, which must be opted into with
+/// `#[relocate(unsafe_no_offsets)]` since it can't check that every relative
+/// offset in the type is stored in a field which implements `Relocate`.
+///
+/// # Safety
+///
+/// - `extent` must return an extent which covers all of the out-of-line data
+///   that the value owns, including the out-of-line data of that data and so
+///   on. Every relative pointer in the value must point into that extent.
+/// - `can_relocate` must only return `true` if every relative pointer in the
+///   value can still reach its target after the value is moved by `delta`
+///   bytes relative to its out-of-line data.
+/// - `relocate` must update every relative pointer in the value so that it
+///   points to the same target as it did before the value was moved.
+pub unsafe trait Relocate: Portable {
+    /// Returns the extent of the out-of-line data owned by this value, or
+    /// `None` if it doesn't own any.
+    fn extent(&self) -> Option<Extent>;
+
+    /// Returns whether this value can be moved by `delta` bytes relative to
+    /// its out-of-line data.
+    ///
+    /// This returns `false` if the adjusted offset of any relative pointer in
+    /// the value would not fit in its offset storage.
+    fn can_relocate(&self, delta: isize) -> bool;
+
+    /// Adjusts the relative pointers of a value which has been moved by
+    /// `delta` bytes relative to its out-of-line data.
+    ///
+    /// # Safety
+    ///
+    /// The value must have been moved `delta` bytes further away from its
+    /// out-of-line data without being otherwise modified, and `can_relocate`
+    /// must have returned `true` for the value and `delta`.
+    unsafe fn relocate(self: Pin<&mut Self>, delta: isize);
+}
+
+/// A range of memory which holds the out-of-line data of an archived value.
+///
+/// The data in an extent can be moved as a whole to any address which keeps
+/// it aligned to `align` without invalidating the relative pointers between
+/// its parts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Extent {
+    /// The address of the start of the extent.
+    pub start: usize,
+    /// The address of the end of the extent.
+    pub end: usize,
+    /// The largest alignment of any value in the extent.
+    pub align: usize,
+}
+
+impl Extent {
+    /// Returns the extent occupied by the given value.
+    pub fn of<T: ?Sized>(value: &T) -> Self {
+        let start = value as *const T as *const u8 as usize;
+        Self {
+            start,
+            end: start + core::mem::size_of_val(value),
+            align: core::mem::align_of_val(value),
+        }
+    }
+
+    /// Returns the length of the extent in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns whether the extent is empty.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the smallest extent which covers both `a` and `b`.
+    pub fn merge(a: Option<Self>, b: Option<Self>) -> Option<Self> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(Self {
+                start: a.start.min(b.start),
+                end: a.end.max(b.end),
+                align: a.align.max(b.align),
+            }),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
 /// Returns the layout of a type from its metadata.
 pub trait LayoutRaw
 where
@@ -316,6 +415,40 @@ pub trait Serialize<S: Fallible + ?Sized>: Archive {
 pub trait Deserialize<T, D: Fallible + ?Sized> {
     /// Deserializes using the given deserializer
     fn deserialize(&self, deserializer: &mut D) -> Result<T, D::Error>;
+
+    /// Deserializes into an existing value using the given deserializer.
+    ///
+    /// Implementations should overwrite `target` in place and reuse any
+    /// allocations it already owns where possible. This makes repeatedly
+    /// deserializing values of the same shape much cheaper. The default
+    /// implementation deserializes a new value and replaces `target` with it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rkyv::{
+    ///     access, de::Pool, deserialize_into, rancor::Error, to_bytes, Archived,
+    /// };
+    ///
+    /// let mut value = vec!["hello".to_string(), "world".to_string()];
+    /// let capacity = value.capacity();
+    ///
+    /// let bytes = to_bytes::<Error>(&vec!["goodbye".to_string()]).unwrap();
+    /// let archived = access::<Archived<Vec<String>>, Error>(&bytes).unwrap();
+    /// deserialize_into::<_, _, Error>(archived, &mut value, &mut Pool::new())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(value, ["goodbye"]);
+    /// assert_eq!(value.capacity(), capacity);
+    /// ```
+    fn deserialize_into(
+        &self,
+        target: &mut T,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        *target = self.deserialize(deserializer)?;
+        Ok(())
+    }
 }
 
 /// A counterpart of [`Archive`] that's suitable for unsized types.
@@ -523,4 +656,19 @@ pub trait DeserializeUnsized<T: Pointee + ?Sized, D: Fallible + ?Sized>:
 
     /// Deserializes the metadata for the given type.
     fn deserialize_metadata(&self) -> T::Metadata;
+
+    /// Deserializes into an existing value, reusing its allocations.
+    ///
+    /// Returns `false` without modifying `target` if the value cannot be
+    /// deserialized in place, for example because the metadata of `target`
+    /// differs from the deserialized metadata. The default implementation
+    /// always returns `false`.
+    fn deserialize_unsized_into(
+        &self,
+        target: &mut T,
+        deserializer: &mut D,
+    ) -> Result<bool, D::Error> {
+        let _ = (target, deserializer);
+        Ok(false)
+    }
 }
@@ -1,14 +1,16 @@
 //! Archived versions of tuple types.
 
-use crate::Portable;
+use crate::{Portable, Relocate};
 
 macro_rules! impl_tuple {
     ($name:ident, $n:tt, $($type:ident $index:tt),*) => {
         #[doc = concat!("An archived tuple with ", stringify!($n), " elements")]
-        #[derive(Debug, Portable)]
+        #[derive(Debug, Portable, Relocate)]
         #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+        #[cfg_attr(feature = "alloc", derive(crate::schema::ArchiveSchema))]
         #[repr(C)]
         #[archive(crate)]
+        #[relocate(unsafe_no_offsets)]
         pub struct $name<$($type),*>($(pub $type),*);
     };
 }
@@ -1,14 +1,16 @@
 mod aligned_vec;
+mod owned;
 
 use rancor::Strategy;
 
-pub use self::aligned_vec::*;
+pub use self::{aligned_vec::*, owned::*};
 use crate::{
     access_unchecked,
     de::pooling::Pool,
     deserialize,
     ser::{
-        allocator::Arena, sharing::Share, DefaultSerializer, Serializer, Writer,
+        allocator::Arena, sharing::Share, writer::SizeCounter,
+        DefaultSerializer, Positional as _, Serializer, Writer,
     },
     util::serialize_into,
     Archive, Deserialize, Serialize,
@@ -168,6 +170,35 @@ where
     })
 }
 
+/// Returns the exact number of bytes that serializing the given value with
+/// [`to_bytes`] would produce.
+///
+/// This serializes the value with a [`SizeCounter`], which tracks the position
+/// and padding of the archive without storing any bytes. The result can be used
+/// to allocate a buffer of exactly the right size up front.
+///
+/// # Examples
+/// ```
+/// use rkyv::{rancor::Error, to_bytes_in, util::AlignedVec};
+///
+/// let value = vec!["a".to_string(), "b".repeat(100)];
+///
+/// let size = rkyv::util::archived_size::<Error>(&value).unwrap();
+/// let bytes =
+///     to_bytes_in::<_, Error>(&value, AlignedVec::<16>::with_capacity(size))
+///         .unwrap();
+/// assert_eq!(bytes.len(), size);
+/// assert_eq!(bytes.capacity(), size);
+/// ```
+pub fn archived_size<E>(
+    value: &impl for<'a> Serialize<DefaultSerializer<'a, SizeCounter, E>>,
+) -> Result<usize, E>
+where
+    E: rancor::Source,
+{
+    Ok(to_bytes_in(value, SizeCounter::new())?.pos())
+}
+
 /// Deserializes a value from the given bytes.
 ///
 /// This function is only available with the `alloc` feature because it uses a
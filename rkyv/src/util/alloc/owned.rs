@@ -0,0 +1,332 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    fmt,
+    marker::PhantomData,
+    mem::{align_of, size_of},
+    ops::Deref,
+};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+#[cfg(feature = "bytecheck")]
+use bytecheck::CheckBytes;
+#[cfg(feature = "bytecheck")]
+use rancor::Source;
+use rancor::Strategy;
+
+#[cfg(feature = "bytecheck")]
+use crate::validation::validators::DefaultValidator;
+use crate::{
+    de::Pool, util::access_pos_unchecked, util::AlignedVec, Archive,
+    Deserialize,
+};
+
+/// Byte storage which can back an [`OwnedArchive`].
+///
+/// # Safety
+///
+/// The bytes returned by `stable_bytes` must not change while the storage is
+/// only accessed through shared references. Moving the storage must not change
+/// the address or contents of the bytes, and cloning it (if it is `Clone`) must
+/// not change their contents.
+pub unsafe trait StableBytes {
+    /// Returns the bytes of the storage.
+    fn stable_bytes(&self) -> &[u8];
+}
+
+// SAFETY: The contents of an `AlignedVec` are stored on the heap and can only
+// be changed through a mutable reference.
+unsafe impl<const A: usize> StableBytes for AlignedVec<A> {
+    fn stable_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+// SAFETY: The contents of a `Box<[u8]>` are stored on the heap and can only be
+// changed through a mutable reference.
+unsafe impl StableBytes for Box<[u8]> {
+    fn stable_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+// SAFETY: An `Arc` only provides shared access to its contents, which are
+// stable because `S` is `StableBytes`.
+unsafe impl<S: StableBytes + ?Sized> StableBytes for Arc<S> {
+    fn stable_bytes(&self) -> &[u8] {
+        S::stable_bytes(self)
+    }
+}
+
+/// An archived value which owns the bytes it is stored in.
+///
+/// `OwnedArchive` can be created from any [`StableBytes`] storage: an
+/// [`AlignedVec`], a `Box<[u8]>`, an `Arc` of another storage, a `bytes::Bytes`
+/// with the `bytes` feature, or a `memmap2::Mmap` with the `memmap2` feature.
+/// It dereferences to the archived value and can be sent between threads and
+/// cloned whenever its storage can. Use cheaply-cloneable storage like `Bytes`
+/// or `Arc<AlignedVec>` to share an archive without copying it.
+///
+/// The bytes must be sufficiently aligned for the archived type. `AlignedVec`
+/// and memory maps always are, but other storage may need to be aligned
+/// manually. Validation fails if the bytes are not sufficiently aligned.
+/// Cloning may not preserve alignment (e.g. for `Box<[u8]>`), so `clone`
+/// checks it again.
+///
+/// # Examples
+/// ```
+/// use rkyv::{rancor::Error, to_bytes, util::OwnedArchive};
+///
+/// let bytes = to_bytes::<Error>(&vec![1u32, 2, 3]).unwrap();
+/// let archive = OwnedArchive::<Vec<u32>>::new::<Error>(bytes).unwrap();
+///
+/// let handle = std::thread::spawn(move || {
+///     archive.iter().map(|x| x.to_native()).sum::<u32>()
+/// });
+/// assert_eq!(handle.join().unwrap(), 6);
+/// ```
+pub struct OwnedArchive<T: Archive, S = AlignedVec> {
+    storage: S,
+    pos: usize,
+    _phantom: PhantomData<T::Archived>,
+}
+
+// SAFETY: `OwnedArchive` only provides shared access to the archived value, so
+// it may be sent to another thread if the storage can be and the archived value
+// can be shared between threads.
+unsafe impl<T: Archive, S: Send> Send for OwnedArchive<T, S> where
+    T::Archived: Sync
+{
+}
+
+// SAFETY: `OwnedArchive` only provides shared access to the archived value, so
+// it may be shared between threads if the storage and archived value can be.
+unsafe impl<T: Archive, S: Sync> Sync for OwnedArchive<T, S> where
+    T::Archived: Sync
+{
+}
+
+impl<T: Archive, S: StableBytes> OwnedArchive<T, S> {
+    /// Creates a new `OwnedArchive` from the given storage after checking that
+    /// a valid archived `T` is located at the end of it.
+    #[cfg(feature = "bytecheck")]
+    pub fn new<E>(storage: S) -> Result<Self, E>
+    where
+        T::Archived: for<'a> CheckBytes<Strategy<DefaultValidator<'a>, E>>,
+        E: Source,
+    {
+        let pos = storage
+            .stable_bytes()
+            .len()
+            .saturating_sub(size_of::<T::Archived>());
+        Self::new_pos(storage, pos)
+    }
+
+    /// Creates a new `OwnedArchive` from the given storage after checking that
+    /// a valid archived `T` is located at the given position in it.
+    #[cfg(feature = "bytecheck")]
+    pub fn new_pos<E>(storage: S, pos: usize) -> Result<Self, E>
+    where
+        T::Archived: for<'a> CheckBytes<Strategy<DefaultValidator<'a>, E>>,
+        E: Source,
+    {
+        crate::validation::util::access_pos::<T::Archived, E>(
+            storage.stable_bytes(),
+            pos,
+        )?;
+        // SAFETY: We just validated that a valid archived `T` is located at
+        // `pos`.
+        Ok(unsafe { Self::new_unchecked(storage, pos) })
+    }
+
+    /// Creates a new `OwnedArchive` from the given storage and the position of
+    /// the root value in it.
+    ///
+    /// # Safety
+    ///
+    /// A valid archived `T` must be located at `pos` in the storage.
+    pub unsafe fn new_unchecked(storage: S, pos: usize) -> Self {
+        Self {
+            storage,
+            pos,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the position of the root value in the storage.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the bytes of the archive.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.storage.stable_bytes()
+    }
+
+    /// Returns a reference to the underlying storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Consumes the `OwnedArchive` and returns the underlying storage.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    /// Deserializes the archived value with a new [`Pool`].
+    pub fn deserialize<E>(&self) -> Result<T, E>
+    where
+        T::Archived: Deserialize<T, Strategy<Pool, E>>,
+    {
+        crate::deserialize(&**self, &mut Pool::new())
+    }
+}
+
+impl<T: Archive, S: StableBytes> Deref for OwnedArchive<T, S> {
+    type Target = T::Archived;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: A valid archived `T` is located at `pos` in the storage, and
+        // `StableBytes` guarantees that the bytes have not changed since.
+        unsafe {
+            access_pos_unchecked::<T::Archived>(
+                self.storage.stable_bytes(),
+                self.pos,
+            )
+        }
+    }
+}
+
+impl<T: Archive, S: StableBytes + Clone> Clone for OwnedArchive<T, S> {
+    /// # Panics
+    ///
+    /// Panics if the bytes of the cloned storage are not sufficiently aligned
+    /// for the archived value.
+    fn clone(&self) -> Self {
+        let storage = self.storage.clone();
+        let ptr = storage.stable_bytes().as_ptr().wrapping_add(self.pos);
+        assert!(
+            ptr as usize % align_of::<T::Archived>() == 0,
+            "cloned archive storage is not sufficiently aligned",
+        );
+        Self {
+            storage,
+            pos: self.pos,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, S> fmt::Debug for OwnedArchive<T, S>
+where
+    T: Archive,
+    T::Archived: fmt::Debug,
+    S: StableBytes,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::{boxed::Box, string::ToString, sync::Arc, vec, vec::Vec};
+    #[cfg(feature = "std")]
+    use std::sync::Arc;
+
+    use rancor::{Error, Panic};
+
+    use crate::{
+        to_bytes,
+        util::{AlignedVec, OwnedArchive, StableBytes},
+    };
+
+    #[test]
+    fn owned_archive_storage() {
+        let value = vec!["hello".to_string(), "world".to_string()];
+        let bytes = to_bytes::<Panic>(&value).unwrap();
+
+        let archive =
+            OwnedArchive::<Vec<String>>::new::<Panic>(bytes.clone()).unwrap();
+        assert_eq!(archive.len(), 2);
+        assert_eq!(archive.deserialize::<Panic>().unwrap(), value);
+
+        let shared =
+            OwnedArchive::<Vec<String>, Arc<AlignedVec>>::new::<Panic>(
+                Arc::new(bytes.clone()),
+            )
+            .unwrap();
+        let cloned = shared.clone();
+        assert_eq!(shared.as_bytes().as_ptr(), cloned.as_bytes().as_ptr());
+        assert_eq!(cloned[1], "world");
+
+        let boxed: Box<[u8]> = bytes.as_slice().into();
+        if boxed.as_ptr() as usize & 7 == 0 {
+            let archive =
+                OwnedArchive::<Vec<String>, Box<[u8]>>::new::<Panic>(boxed)
+                    .unwrap();
+            assert_eq!(archive[0], "hello");
+        }
+
+        #[cfg(feature = "bytes")]
+        {
+            // `Bytes` created from a `Vec` keeps its allocation, which is
+            // aligned like the `AlignedVec` it was copied from.
+            let shared = bytes::Bytes::from(bytes.to_vec());
+            if shared.as_ptr() as usize & 7 == 0 {
+                let archive = OwnedArchive::<Vec<String>, bytes::Bytes>::new::<
+                    Panic,
+                >(shared)
+                .unwrap();
+                let cloned = archive.clone();
+                assert_eq!(
+                    archive.as_bytes().as_ptr(),
+                    cloned.as_bytes().as_ptr()
+                );
+            }
+        }
+
+        let mut corrupted = bytes;
+        let len = corrupted.len();
+        corrupted[len - 4..].copy_from_slice(&[0xff; 4]);
+        assert!(OwnedArchive::<Vec<String>>::new::<Error>(corrupted).is_err());
+    }
+
+    /// Storage which moves its bytes one byte further into a new buffer
+    /// whenever it is cloned.
+    struct Shifting {
+        buffer: AlignedVec,
+        offset: usize,
+    }
+
+    // SAFETY: The bytes are stored on the heap and are never modified.
+    unsafe impl StableBytes for Shifting {
+        fn stable_bytes(&self) -> &[u8] {
+            &self.buffer[self.offset..]
+        }
+    }
+
+    impl Clone for Shifting {
+        fn clone(&self) -> Self {
+            let mut buffer = AlignedVec::new();
+            buffer.extend_from_slice(&[0]);
+            buffer.extend_from_slice(self.stable_bytes());
+            Self { buffer, offset: 1 }
+        }
+    }
+
+    #[test]
+    #[should_panic = "not sufficiently aligned"]
+    fn clone_checks_alignment() {
+        let bytes = to_bytes::<Panic>(&vec![1u32, 2, 3]).unwrap();
+        let archive =
+            OwnedArchive::<Vec<u32>, Shifting>::new::<Panic>(Shifting {
+                buffer: bytes,
+                offset: 0,
+            })
+            .unwrap();
+        let _ = archive.clone();
+    }
+}
@@ -0,0 +1,153 @@
+use core::{fmt, marker::PhantomData, mem::size_of, ops::Deref};
+use std::{fs::File, path::Path};
+
+#[cfg(feature = "bytecheck")]
+use bytecheck::CheckBytes;
+use memmap2::Mmap;
+#[cfg(feature = "bytecheck")]
+use rancor::{ResultExt as _, Source, Strategy};
+
+#[cfg(feature = "bytecheck")]
+use crate::validation::validators::DefaultValidator;
+use crate::{
+    util::{access_pos_unchecked, StableBytes},
+    Portable,
+};
+
+/// An archived value backed by a memory-mapped file.
+///
+/// `MmapArchive` owns the mapping and dereferences to the archived root value.
+/// It can be created with [`access_file`], or with [`access_file_unchecked`] to
+/// skip validation.
+pub struct MmapArchive<T> {
+    map: Mmap,
+    pos: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Portable> MmapArchive<T> {
+    /// Creates a new `MmapArchive` from a memory map and the position of the
+    /// root value in it.
+    ///
+    /// # Safety
+    ///
+    /// A valid `T` must be located at `pos` in the memory map.
+    pub unsafe fn new_unchecked(map: Mmap, pos: usize) -> Self {
+        Self {
+            map,
+            pos,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the position of the root value in the memory map.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the bytes of the memory map.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.map
+    }
+
+    /// Consumes the `MmapArchive` and returns the underlying memory map.
+    pub fn into_inner(self) -> Mmap {
+        self.map
+    }
+}
+
+impl<T: Portable> Deref for MmapArchive<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: A valid `T` is located at `pos` in the memory map.
+        unsafe { access_pos_unchecked::<T>(&self.map, self.pos) }
+    }
+}
+
+// SAFETY: A read-only memory map can only be modified by other processes or
+// through other mappings, which `Mmap::map` requires the caller to prevent.
+unsafe impl StableBytes for Mmap {
+    fn stable_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<T: Portable + fmt::Debug> fmt::Debug for MmapArchive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// Maps the file at the given path into memory and accesses the archived value
+/// at the end of it without checking its validity.
+///
+/// Memory maps are always page-aligned, so the archive will be sufficiently
+/// aligned for any archived type.
+///
+/// This is the unchecked counterpart of [`access_file`].
+///
+/// # Safety
+///
+/// - The file must contain an archived `T` located at the end of the file.
+/// - The file must not be modified while the returned value is alive.
+pub unsafe fn access_file_unchecked<T: Portable>(
+    path: impl AsRef<Path>,
+) -> std::io::Result<MmapArchive<T>> {
+    let file = File::open(path)?;
+    // SAFETY: The caller has guaranteed that the file will not be modified
+    // while it is mapped.
+    let map = unsafe { Mmap::map(&file)? };
+    let pos = map.len().saturating_sub(size_of::<T>());
+    // SAFETY: The caller has guaranteed that a valid `T` is located at the end
+    // of the file.
+    Ok(unsafe { MmapArchive::new_unchecked(map, pos) })
+}
+
+/// Maps the file at the given path into memory and accesses the archived value
+/// at the end of it after checking its validity.
+///
+/// Memory maps are always page-aligned, so the archive will be sufficiently
+/// aligned for any archived type. The contents of the file are validated with
+/// the [`DefaultValidator`], which checks both bounds and alignment of all
+/// nonlocal memory as well as shared pointers.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated while the returned value is
+/// alive, including by other processes. Validation only checks the contents of
+/// the file at the time it is mapped.
+///
+/// # Examples
+/// ```
+/// use rkyv::{rancor::Error, to_bytes, util::access_file, Archived};
+///
+/// let path = std::env::temp_dir()
+///     .join(format!("rkyv_access_file_example_{}.bin", std::process::id()));
+/// let bytes = to_bytes::<Error>(&vec![1u32, 2, 3]).unwrap();
+/// std::fs::write(&path, &bytes).unwrap();
+///
+/// // SAFETY: The file is not modified while it is mapped.
+/// let archived =
+///     unsafe { access_file::<Archived<Vec<u32>>, Error>(&path).unwrap() };
+/// assert_eq!(archived.as_slice(), [1, 2, 3]);
+/// # drop(archived);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[cfg(feature = "bytecheck")]
+pub unsafe fn access_file<T, E>(
+    path: impl AsRef<Path>,
+) -> Result<MmapArchive<T>, E>
+where
+    T: Portable + for<'a> CheckBytes<Strategy<DefaultValidator<'a>, E>>,
+    E: Source,
+{
+    let file = File::open(path).into_error()?;
+    // SAFETY: The caller has guaranteed that the file will not be modified
+    // while it is mapped.
+    let map = unsafe { Mmap::map(&file).into_error()? };
+    let pos = map.len().saturating_sub(size_of::<T>());
+    crate::validation::util::access_pos::<T, E>(&map, pos)?;
+    // SAFETY: We just validated that a valid `T` is located at `pos`.
+    Ok(unsafe { MmapArchive::new_unchecked(map, pos) })
+}
@@ -13,6 +13,8 @@
 #[cfg(feature = "alloc")]
 mod alloc;
 mod inline_vec;
+#[cfg(feature = "memmap2")]
+mod mmap;
 mod ser_vec;
 
 use core::{
@@ -27,6 +29,9 @@ use rancor::Strategy;
 #[cfg(feature = "alloc")]
 pub use self::alloc::*;
 #[doc(inline)]
+#[cfg(feature = "memmap2")]
+pub use self::mmap::*;
+#[doc(inline)]
 pub use self::{inline_vec::InlineVec, ser_vec::SerVec};
 use crate::{ser::Writer, Archive, Deserialize, Portable, SerializeUnsized};
 
@@ -198,3 +203,19 @@ where
 {
     value.deserialize(Strategy::wrap(deserializer))
 }
+
+/// Deserializes a value from the given archived value into an existing value
+/// using the provided deserializer.
+///
+/// See [`Deserialize::deserialize_into`] for more details.
+pub fn deserialize_into<T, D, E>(
+    value: &T::Archived,
+    target: &mut T,
+    deserializer: &mut D,
+) -> Result<(), E>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, Strategy<D, E>>,
+{
+    value.deserialize_into(target, Strategy::wrap(deserializer))
+}
@@ -1,5 +1,6 @@
 //! Validation implementations and helper types.
 
+pub mod path;
 pub mod util;
 pub mod validators;
 
@@ -53,6 +54,16 @@ pub unsafe trait ArchiveContext<E = <Self as Fallible>::Error> {
         &mut self,
         range: Range<usize>,
     ) -> Result<(), E>;
+
+    /// Checks that a collection with the given number of elements may be
+    /// validated.
+    ///
+    /// Contexts can use this to limit the resources spent validating untrusted
+    /// archives. The default implementation always succeeds.
+    fn check_collection_len(&mut self, len: usize) -> Result<(), E> {
+        let _ = len;
+        Ok(())
+    }
 }
 
 unsafe impl<T, E> ArchiveContext<E> for Strategy<T, E>
@@ -85,6 +96,10 @@ where
         // has the same safety requirements.
         unsafe { T::pop_subtree_range(self, range) }
     }
+
+    fn check_collection_len(&mut self, len: usize) -> Result<(), E> {
+        T::check_collection_len(self, len)
+    }
 }
 
 /// Helper methods for [`ArchiveContext`].
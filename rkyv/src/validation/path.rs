@@ -0,0 +1,275 @@
+//! Field and index paths for validation errors.
+//!
+//! Validation errors are traced with the location of the value that failed to
+//! validate. Derived `CheckBytes` implementations trace the fields and enum
+//! variants they check, and rkyv's collections trace the indices of their
+//! elements with [`PathSegment`]. [`PathError`] collects these traces into a
+//! path like `root.players[12].inventory.items`.
+//!
+//! Arrays and slices which are checked by bytecheck directly (e.g. `[T; N]`
+//! fields and boxed slices) don't trace typed indices, so their elements don't
+//! appear in the path.
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::{any::Any, boxed::Box, error::Error, vec::Vec};
+
+#[cfg(feature = "std")]
+use bytecheck::{
+    NamedEnumVariantCheckContext, StructCheckContext, TupleStructCheckContext,
+    UnnamedEnumVariantCheckContext,
+};
+#[cfg(feature = "std")]
+use rancor::{Source, Trace};
+
+/// A single step in the path to a value inside an archive.
+///
+/// Path segments are also used as trace information while validating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A named field of a struct or enum variant.
+    Field(&'static str),
+    /// An unnamed field of a tuple struct or enum variant.
+    TupleField(usize),
+    /// An enum variant.
+    Variant(&'static str),
+    /// An element of an array, slice, or collection.
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field(name) => write!(f, "while checking field '{name}'"),
+            Self::TupleField(index) => {
+                write!(f, "while checking field index {index}")
+            }
+            Self::Variant(name) => {
+                write!(f, "while checking variant '{name}'")
+            }
+            Self::Index(index) => write!(f, "while checking index {index}"),
+        }
+    }
+}
+
+/// Displays a sequence of path segments, starting from `root`.
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayPath<'a>(pub &'a [PathSegment]);
+
+impl fmt::Display for DisplayPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "root")?;
+        for segment in self.0 {
+            match segment {
+                PathSegment::Field(name) => write!(f, ".{name}")?,
+                PathSegment::TupleField(index) => write!(f, ".{index}")?,
+                PathSegment::Variant(name) => write!(f, "::{name}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error type which records the path to the value that failed validation.
+///
+/// Validating with `PathError` reports errors like:
+///
+/// ```text
+/// root.players[12].inventory.items -> subtree pointer overran range: ...
+/// ```
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     access, bytecheck::CheckBytes, to_bytes, validation::path::PathError,
+///     Archive, Archived, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive_attr(derive(CheckBytes, Debug))]
+/// struct Inventory {
+///     items: Vec<u32>,
+/// }
+///
+/// let value = vec![Inventory { items: vec![1, 2] }];
+/// let mut bytes = to_bytes::<PathError>(&value).unwrap();
+///
+/// // Corrupt the length of the last inventory's items.
+/// let offset = bytes.len() - 12;
+/// bytes[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+///
+/// let error = access::<Archived<Vec<Inventory>>, PathError>(&bytes)
+///     .unwrap_err();
+/// assert_eq!(error.path().to_string(), "root[0].items");
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct PathError {
+    error: Box<dyn Error + Send + Sync + 'static>,
+    path: Vec<PathSegment>,
+}
+
+#[cfg(feature = "std")]
+impl PathError {
+    /// Returns the path to the value that failed validation.
+    pub fn path(&self) -> DisplayPath<'_> {
+        DisplayPath(&self.path)
+    }
+
+    /// Returns the segments of the path to the value that failed validation,
+    /// outermost first.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// Returns the underlying error.
+    pub fn error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.error
+    }
+
+    fn push_trace(&mut self, trace: &dyn Any) {
+        // Traces are added as the error propagates outward, so each segment is
+        // the new outermost one.
+        if let Some(segment) = trace.downcast_ref::<PathSegment>() {
+            self.path.insert(0, *segment);
+        } else if let Some(context) = trace.downcast_ref::<StructCheckContext>()
+        {
+            self.path.insert(0, PathSegment::Field(context.field_name));
+        } else if let Some(context) =
+            trace.downcast_ref::<TupleStructCheckContext>()
+        {
+            self.path
+                .insert(0, PathSegment::TupleField(context.field_index));
+        } else if let Some(context) =
+            trace.downcast_ref::<NamedEnumVariantCheckContext>()
+        {
+            self.path.insert(0, PathSegment::Field(context.field_name));
+            self.path
+                .insert(0, PathSegment::Variant(context.variant_name));
+        } else if let Some(context) =
+            trace.downcast_ref::<UnnamedEnumVariantCheckContext>()
+        {
+            self.path
+                .insert(0, PathSegment::TupleField(context.field_index));
+            self.path
+                .insert(0, PathSegment::Variant(context.variant_name));
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.path(), self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Trace for PathError {
+    fn trace<R>(mut self, trace: R) -> Self
+    where
+        R: fmt::Debug + fmt::Display + Send + Sync + 'static,
+    {
+        self.push_trace(&trace);
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl Source for PathError {
+    fn new<T: Error + Send + Sync + 'static>(source: T) -> Self {
+        Self {
+            error: Box::new(source),
+            path: Vec::new(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use core::mem::size_of;
+    use std::{collections::HashMap, string::ToString};
+
+    use super::{DisplayPath, PathError, PathSegment};
+    use crate::{
+        access, primitive::ArchivedUsize, to_bytes, Archive, Archived,
+        Serialize,
+    };
+
+    #[derive(Archive, Serialize)]
+    #[archive(crate)]
+    #[archive_attr(derive(bytecheck::CheckBytes, Debug))]
+    struct Player {
+        name: String,
+        inventory: Vec<u32>,
+        scores: HashMap<u32, Vec<u32>>,
+    }
+
+    #[test]
+    fn display_path() {
+        let path = [
+            PathSegment::Field("players"),
+            PathSegment::Index(12),
+            PathSegment::Variant("Some"),
+            PathSegment::TupleField(0),
+        ];
+        assert_eq!(DisplayPath(&path).to_string(), "root.players[12]::Some.0");
+    }
+
+    /// Sets the length of the archived vec at `pos` to its maximum.
+    fn corrupt_len(bytes: &mut [u8], pos: usize) {
+        let start = pos + size_of::<ArchivedUsize>();
+        bytes[start..start + size_of::<ArchivedUsize>()].fill(0xff);
+    }
+
+    #[test]
+    fn field_and_index_path() {
+        let players = vec![
+            Player {
+                name: "a".to_string(),
+                inventory: vec![1, 2, 3],
+                scores: [(2, vec![2])].into_iter().collect(),
+            },
+            Player {
+                name: "b".to_string(),
+                inventory: vec![4, 5],
+                scores: [(1, vec![1])].into_iter().collect(),
+            },
+        ];
+        let bytes = to_bytes::<PathError>(&players).unwrap();
+        let archived =
+            access::<Archived<Vec<Player>>, PathError>(&bytes).unwrap();
+        let base = bytes.as_ptr() as usize;
+        let inventory = &archived[1].inventory as *const _ as usize - base;
+        let score = archived[1]
+            .scores
+            .get(&Archived::<u32>::from_native(1))
+            .unwrap() as *const _ as usize
+            - base;
+
+        let mut corrupted = bytes.clone();
+        corrupt_len(&mut corrupted, inventory);
+        let error =
+            access::<Archived<Vec<Player>>, PathError>(&corrupted).unwrap_err();
+        assert_eq!(error.path().to_string(), "root[1].inventory");
+        assert!(error.to_string().starts_with(
+            "root[1].inventory -> subtree pointer overran range: offset"
+        ));
+
+        let mut corrupted = bytes.clone();
+        corrupt_len(&mut corrupted, score);
+        let error =
+            access::<Archived<Vec<Player>>, PathError>(&corrupted).unwrap_err();
+        let path = error.path().to_string();
+        assert!(path.starts_with("root[1].scores.table["), "{path}");
+        assert!(path.ends_with("].value"), "{path}");
+    }
+}
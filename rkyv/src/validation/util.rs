@@ -11,7 +11,8 @@ use crate::{
     deserialize,
     util::{access_pos_unchecked, access_pos_unchecked_mut},
     validation::{
-        validators::DefaultValidator, ArchiveContext, ArchiveContextExt,
+        validators::{DefaultValidator, ValidationLimits},
+        ArchiveContext, ArchiveContextExt,
     },
     Archive, Deserialize, Portable,
 };
@@ -143,6 +144,23 @@ where
     access_with_context::<T, DefaultValidator, E>(bytes, &mut validator)
 }
 
+/// Accesses an archived value from the given byte slice by calculating the root
+/// position after checking its validity within the given limits.
+///
+/// This should be used instead of [`access`] for archives from untrusted
+/// sources. See [`ValidationLimits`] for more details.
+pub fn access_with_limits<T, E>(
+    bytes: &[u8],
+    limits: ValidationLimits,
+) -> Result<&T, E>
+where
+    T: Portable + for<'a> CheckBytes<Strategy<DefaultValidator<'a>, E>>,
+    E: Source,
+{
+    let mut validator = DefaultValidator::with_limits(bytes, limits);
+    access_with_context::<T, DefaultValidator, E>(bytes, &mut validator)
+}
+
 // TODO: `Pin` is not technically correct for the return type. `Pin` requires
 // the pinned value to be dropped before its memory can be reused, but archived
 // types explicitly do not require that. It just wants immovable types.
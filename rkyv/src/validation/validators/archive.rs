@@ -4,13 +4,14 @@ use core::{
     alloc::Layout, fmt, marker::PhantomData, num::NonZeroUsize, ops::Range,
 };
 
-use rancor::{fail, OptionExt, Source};
+use rancor::{fail, OptionExt, ResultExt as _, Source};
 
-use crate::{fmt::Pointer, validation::ArchiveContext};
+use super::limits::{Budget, Limit, LimitExceeded, ValidationLimits};
+use crate::validation::ArchiveContext;
 
 #[derive(Debug)]
 struct UnalignedPointer {
-    address: usize,
+    offset: isize,
     align: usize,
 }
 
@@ -18,9 +19,8 @@ impl fmt::Display for UnalignedPointer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "unaligned pointer: ptr {} unaligned for alignment {}",
-            Pointer(self.address),
-            self.align,
+            "unaligned pointer: offset {} unaligned for alignment {}",
+            self.offset, self.align,
         )
     }
 }
@@ -30,7 +30,7 @@ impl std::error::Error for UnalignedPointer {}
 
 #[derive(Debug)]
 struct InvalidSubtreePointer {
-    address: usize,
+    offset: isize,
     size: usize,
     subtree_range: Range<usize>,
 }
@@ -39,11 +39,11 @@ impl fmt::Display for InvalidSubtreePointer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "subtree pointer overran range: ptr {} size {} in range {}..{}",
-            Pointer(self.address),
+            "subtree pointer overran range: offset {} size {} in range {}..{}",
+            self.offset,
             self.size,
-            Pointer(self.subtree_range.start),
-            Pointer(self.subtree_range.end),
+            self.subtree_range.start,
+            self.subtree_range.end,
         )
     }
 }
@@ -91,10 +91,17 @@ impl fmt::Display for RangePoppedOutOfOrder {
 impl std::error::Error for RangePoppedOutOfOrder {}
 
 /// A validator that can verify archives with nonlocal memory.
+///
+/// Errors report locations as byte offsets from the start of the buffer being
+/// validated.
 #[derive(Debug)]
 pub struct ArchiveValidator<'a> {
+    base: usize,
     subtree_range: Range<usize>,
     max_subtree_depth: Option<NonZeroUsize>,
+    max_collection_len: Option<usize>,
+    bytes_budget: Option<Budget>,
+    work_budget: Option<Budget>,
     _phantom: PhantomData<&'a [u8]>,
 }
 
@@ -122,16 +129,44 @@ impl<'a> ArchiveValidator<'a> {
         bytes: &'a [u8],
         max_subtree_depth: Option<NonZeroUsize>,
     ) -> Self {
+        let mut limits = ValidationLimits::new();
+        limits.max_depth = max_subtree_depth;
+        Self::with_limits(bytes, limits)
+    }
+
+    /// Creates a new bounds validator for the given bytes which fails if
+    /// validation exceeds the given limits.
+    ///
+    /// The limit on shared pointers is enforced by
+    /// [`SharedValidator`](crate::validation::validators::SharedValidator)
+    /// instead.
+    #[inline]
+    pub fn with_limits(bytes: &'a [u8], limits: ValidationLimits) -> Self {
         let Range { start, end } = bytes.as_ptr_range();
         Self {
+            base: start as usize,
             subtree_range: Range {
                 start: start as usize,
                 end: end as usize,
             },
-            max_subtree_depth,
+            max_subtree_depth: limits.max_depth,
+            max_collection_len: limits.max_collection_len,
+            bytes_budget: Budget::new(limits.max_bytes),
+            work_budget: Budget::new(limits.max_work),
             _phantom: PhantomData,
         }
     }
+
+    /// Returns the offset of the given address from the start of the buffer.
+    fn offset_of(&self, address: usize) -> isize {
+        address.wrapping_sub(self.base) as isize
+    }
+
+    /// Returns the given range of addresses as offsets from the start of the
+    /// buffer.
+    fn relative_range(&self, range: &Range<usize>) -> Range<usize> {
+        range.start - self.base..range.end - self.base
+    }
 }
 
 unsafe impl<E: Source> ArchiveContext<E> for ArchiveValidator<'_> {
@@ -144,18 +179,22 @@ unsafe impl<E: Source> ArchiveContext<E> for ArchiveValidator<'_> {
         let end = ptr.wrapping_add(layout.size()) as usize;
         if start < self.subtree_range.start || end > self.subtree_range.end {
             fail!(InvalidSubtreePointer {
-                address: start,
+                offset: self.offset_of(start),
                 size: layout.size(),
-                subtree_range: self.subtree_range.clone(),
+                subtree_range: self.relative_range(&self.subtree_range),
             });
         } else if start & (layout.align() - 1) != 0 {
             fail!(UnalignedPointer {
-                address: ptr as usize,
+                offset: self.offset_of(start),
                 align: layout.align(),
             });
-        } else {
-            Ok(())
         }
+
+        Budget::spend(&mut self.bytes_budget, Limit::Bytes, layout.size())
+            .into_error()?;
+        Budget::spend(&mut self.work_budget, Limit::Work, 1).into_error()?;
+
+        Ok(())
     }
 
     unsafe fn push_subtree_range(
@@ -191,4 +230,16 @@ unsafe impl<E: Source> ArchiveContext<E> for ArchiveValidator<'_> {
         }
         Ok(())
     }
+
+    fn check_collection_len(&mut self, len: usize) -> Result<(), E> {
+        if let Some(maximum) = self.max_collection_len {
+            if len > maximum {
+                fail!(LimitExceeded {
+                    limit: Limit::CollectionLen,
+                    maximum,
+                });
+            }
+        }
+        Budget::spend(&mut self.work_budget, Limit::Work, len).into_error()
+    }
 }
@@ -0,0 +1,279 @@
+//! Resource limits for validating untrusted archives.
+
+use core::{fmt, num::NonZeroUsize};
+
+/// Limits on the resources used while validating an archive.
+///
+/// Validating an archive takes time proportional to the amount of data it
+/// claims to contain, which a malicious archive controls. These limits bound
+/// the work that validation does before failing with a [`LimitExceeded`]
+/// error. All limits are disabled by default.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     rancor::Error,
+///     to_bytes,
+///     validation::{
+///         util::access_with_limits, validators::ValidationLimits,
+///     },
+///     Archived,
+/// };
+///
+/// let bytes = to_bytes::<Error>(&vec![0u32; 100]).unwrap();
+///
+/// let limits = ValidationLimits::new().max_collection_len(1000);
+/// assert!(access_with_limits::<Archived<Vec<u32>>, Error>(&bytes, limits)
+///     .is_ok());
+///
+/// let limits = ValidationLimits::new().max_collection_len(10);
+/// assert!(access_with_limits::<Archived<Vec<u32>>, Error>(&bytes, limits)
+///     .is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ValidationLimits {
+    pub(super) max_depth: Option<NonZeroUsize>,
+    pub(super) max_bytes: Option<usize>,
+    pub(super) max_collection_len: Option<usize>,
+    pub(super) max_shared_pointers: Option<usize>,
+    pub(super) max_work: Option<usize>,
+}
+
+impl ValidationLimits {
+    /// Returns a new set of limits with all limits disabled.
+    pub const fn new() -> Self {
+        Self {
+            max_depth: None,
+            max_bytes: None,
+            max_collection_len: None,
+            max_shared_pointers: None,
+            max_work: None,
+        }
+    }
+
+    /// Sets the maximum depth of nested subtrees.
+    pub const fn max_depth(mut self, max_depth: NonZeroUsize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the maximum total number of bytes that may be claimed by nonlocal
+    /// data like the elements of vectors and strings.
+    pub const fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sets the maximum number of elements in any single collection.
+    pub const fn max_collection_len(mut self, max_len: usize) -> Self {
+        self.max_collection_len = Some(max_len);
+        self
+    }
+
+    /// Sets the maximum number of distinct shared pointers that may be
+    /// registered.
+    pub const fn max_shared_pointers(mut self, max_shared: usize) -> Self {
+        self.max_shared_pointers = Some(max_shared);
+        self
+    }
+
+    /// Sets the maximum amount of work that validation may do.
+    ///
+    /// One unit of work is done for each nonlocal subtree checked and for each
+    /// element of each collection.
+    pub const fn max_work(mut self, max_work: usize) -> Self {
+        self.max_work = Some(max_work);
+        self
+    }
+}
+
+/// A resource limit for validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    /// The total number of bytes claimed by nonlocal data.
+    Bytes,
+    /// The number of elements in a single collection.
+    CollectionLen,
+    /// The number of distinct shared pointers.
+    SharedPointers,
+    /// The total amount of work done.
+    Work,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bytes => write!(f, "bytes"),
+            Self::CollectionLen => write!(f, "collection length"),
+            Self::SharedPointers => write!(f, "shared pointers"),
+            Self::Work => write!(f, "work"),
+        }
+    }
+}
+
+/// An error indicating that validation exceeded one of its
+/// [`ValidationLimits`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LimitExceeded {
+    /// The limit that was exceeded.
+    pub limit: Limit,
+    /// The maximum allowed by the limit.
+    pub maximum: usize,
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "exceeded validation limit for {} (maximum: {})",
+            self.limit, self.maximum,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LimitExceeded {}
+
+/// A remaining budget for a limit.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Budget {
+    maximum: usize,
+    remaining: usize,
+}
+
+impl Budget {
+    pub(super) fn new(maximum: Option<usize>) -> Option<Self> {
+        maximum.map(|maximum| Self {
+            maximum,
+            remaining: maximum,
+        })
+    }
+
+    /// Spends `amount` from the budget, returning an error if the budget does
+    /// not have enough remaining.
+    pub(super) fn spend(
+        budget: &mut Option<Self>,
+        limit: Limit,
+        amount: usize,
+    ) -> Result<(), LimitExceeded> {
+        if let Some(budget) = budget {
+            budget.remaining =
+                budget.remaining.checked_sub(amount).ok_or(LimitExceeded {
+                    limit,
+                    maximum: budget.maximum,
+                })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use core::{mem::size_of, num::NonZeroUsize};
+    use std::rc::Rc;
+
+    use bytecheck::CheckBytes;
+    use rancor::Strategy;
+
+    use super::{Limit, LimitExceeded, ValidationLimits};
+    use crate::{
+        to_bytes,
+        validation::{
+            path::PathError, util::access_with_limits,
+            validators::DefaultValidator,
+        },
+        Archive, Archived,
+    };
+
+    fn exceeded<T>(bytes: &[u8], limits: ValidationLimits) -> Option<Limit>
+    where
+        T: Archive,
+        T::Archived:
+            for<'a> CheckBytes<Strategy<DefaultValidator<'a>, PathError>>,
+    {
+        let error = access_with_limits::<T::Archived, PathError>(bytes, limits)
+            .err()?;
+        let exceeded = error.error().downcast_ref::<LimitExceeded>().unwrap();
+        Some(exceeded.limit)
+    }
+
+    #[test]
+    fn validation_limits() {
+        let value = vec![vec![1u32, 2, 3], vec![4, 5]];
+        let bytes = to_bytes::<PathError>(&value).unwrap();
+        type T = Vec<Vec<u32>>;
+
+        assert_eq!(exceeded::<T>(&bytes, ValidationLimits::new()), None);
+        assert_eq!(
+            exceeded::<T>(
+                &bytes,
+                ValidationLimits::new().max_collection_len(3)
+            ),
+            None,
+        );
+        assert_eq!(
+            exceeded::<T>(
+                &bytes,
+                ValidationLimits::new().max_collection_len(2)
+            ),
+            Some(Limit::CollectionLen),
+        );
+        // The root, two inner vecs, and five elements in total.
+        let inner_bytes = 3 * size_of::<Archived<Vec<u32>>>() + 5 * 4;
+        assert_eq!(
+            exceeded::<T>(
+                &bytes,
+                ValidationLimits::new().max_bytes(inner_bytes)
+            ),
+            None,
+        );
+        assert_eq!(
+            exceeded::<T>(
+                &bytes,
+                ValidationLimits::new().max_bytes(inner_bytes - 1)
+            ),
+            Some(Limit::Bytes),
+        );
+        // Four subtrees (including the root) and seven elements.
+        assert_eq!(
+            exceeded::<T>(&bytes, ValidationLimits::new().max_work(11)),
+            None,
+        );
+        assert_eq!(
+            exceeded::<T>(&bytes, ValidationLimits::new().max_work(10)),
+            Some(Limit::Work),
+        );
+        let max_depth = |depth| {
+            ValidationLimits::new().max_depth(NonZeroUsize::new(depth).unwrap())
+        };
+        assert!(access_with_limits::<Archived<T>, PathError>(
+            &bytes,
+            max_depth(4)
+        )
+        .is_ok());
+        assert!(access_with_limits::<Archived<T>, PathError>(
+            &bytes,
+            max_depth(3)
+        )
+        .is_err());
+
+        let shared = Rc::new(1u32);
+        let value = vec![Rc::new(0u32), shared.clone(), shared];
+        let bytes = to_bytes::<PathError>(&value).unwrap();
+        type R = Vec<Rc<u32>>;
+        assert_eq!(
+            exceeded::<R>(
+                &bytes,
+                ValidationLimits::new().max_shared_pointers(2)
+            ),
+            None,
+        );
+        assert_eq!(
+            exceeded::<R>(
+                &bytes,
+                ValidationLimits::new().max_shared_pointers(1)
+            ),
+            Some(Limit::SharedPointers),
+        );
+    }
+}
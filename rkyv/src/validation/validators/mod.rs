@@ -1,11 +1,13 @@
 //! Validators that can check archived types.
 
 mod archive;
+mod limits;
 mod shared;
 
 use core::{any::TypeId, ops::Range};
 
 pub use archive::*;
+pub use limits::*;
 pub use shared::*;
 
 use crate::validation::{ArchiveContext, SharedContext};
@@ -35,6 +37,16 @@ impl<'a> DefaultValidator<'a> {
             shared: SharedValidator::with_capacity(capacity),
         }
     }
+
+    /// Creates a new validator from a byte range which fails if validation
+    /// exceeds the given limits.
+    #[inline]
+    pub fn with_limits(bytes: &'a [u8], limits: ValidationLimits) -> Self {
+        Self {
+            archive: ArchiveValidator::with_limits(bytes, limits),
+            shared: SharedValidator::with_limits(limits),
+        }
+    }
 }
 
 unsafe impl<'a, E> ArchiveContext<E> for DefaultValidator<'a>
@@ -67,6 +79,10 @@ where
         // `ArchiveValidator`, which has the same safety requirements.
         unsafe { self.archive.pop_subtree_range(range) }
     }
+
+    fn check_collection_len(&mut self, len: usize) -> Result<(), E> {
+        self.archive.check_collection_len(len)
+    }
 }
 
 impl<E> SharedContext<E> for DefaultValidator<'_>
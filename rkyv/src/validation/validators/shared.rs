@@ -9,6 +9,7 @@ use std::collections::HashMap;
 use hashbrown::HashMap;
 use rancor::{fail, Source};
 
+use super::limits::{Limit, LimitExceeded, ValidationLimits};
 use crate::validation::SharedContext;
 
 /// Errors that can occur when checking shared memory.
@@ -49,6 +50,7 @@ impl std::error::Error for SharedError {
 #[derive(Debug, Default)]
 pub struct SharedValidator {
     shared: HashMap<usize, TypeId>,
+    max_shared_pointers: Option<usize>,
 }
 
 impl SharedValidator {
@@ -57,6 +59,7 @@ impl SharedValidator {
     pub fn new() -> Self {
         Self {
             shared: HashMap::new(),
+            max_shared_pointers: None,
         }
     }
 
@@ -65,6 +68,17 @@ impl SharedValidator {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             shared: HashMap::with_capacity(capacity),
+            max_shared_pointers: None,
+        }
+    }
+
+    /// Shared memory validator which fails if more shared pointers are
+    /// registered than the given limits allow.
+    #[inline]
+    pub fn with_limits(limits: ValidationLimits) -> Self {
+        Self {
+            shared: HashMap::new(),
+            max_shared_pointers: limits.max_shared_pointers,
         }
     }
 }
@@ -81,6 +95,7 @@ impl<E: Source> SharedContext<E> for SharedValidator {
         #[cfg(not(feature = "std"))]
         use hashbrown::hash_map::Entry;
 
+        let len = self.shared.len();
         match self.shared.entry(address) {
             Entry::Occupied(previous_type_entry) => {
                 let previous_type_id = previous_type_entry.get();
@@ -94,6 +109,14 @@ impl<E: Source> SharedContext<E> for SharedValidator {
                 }
             }
             Entry::Vacant(ent) => {
+                if let Some(maximum) = self.max_shared_pointers {
+                    if len >= maximum {
+                        fail!(LimitExceeded {
+                            limit: Limit::SharedPointers,
+                            maximum,
+                        });
+                    }
+                }
                 ent.insert(type_id);
                 Ok(true)
             }
@@ -1,5 +1,7 @@
 //! An archived version of `Vec`.
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 use core::{
     borrow::Borrow,
     cmp, fmt, hash,
@@ -14,7 +16,8 @@ use rancor::Fallible;
 use crate::{
     primitive::ArchivedUsize,
     ser::{Allocator, Writer, WriterExt as _},
-    Archive, Place, Portable, RelPtr, Serialize, SerializeUnsized,
+    Archive, Extent, Place, Portable, RelPtr, Relocate, Serialize,
+    SerializeUnsized,
 };
 
 /// An archived [`Vec`].
@@ -185,6 +188,239 @@ impl<T> ArchivedVec<T> {
     }
 }
 
+// SAFETY: An archived vec owns its elements and their out-of-line data, and
+// only its relative pointer needs to be relocated.
+unsafe impl<T: Relocate> Relocate for ArchivedVec<T>
+where
+    Self: Portable,
+{
+    fn extent(&self) -> Option<Extent> {
+        let slice = self.as_slice();
+        Extent::merge(Some(Extent::of(slice)), slice.extent())
+    }
+
+    fn can_relocate(&self, delta: isize) -> bool {
+        self.ptr.can_relocate(delta)
+    }
+
+    unsafe fn relocate(self: Pin<&mut Self>, delta: isize) {
+        // SAFETY: The caller has upheld the safety requirements of `relocate`
+        // for the vec, and so also for its relative pointer.
+        unsafe {
+            self.map_unchecked_mut(|s| &mut s.ptr).relocate(delta);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Relocate> ArchivedVec<T> {
+    /// Swaps two elements of the archived vec.
+    ///
+    /// Validation requires the out-of-line data of the elements to be laid out
+    /// in the same order as the elements, so the out-of-line data of each
+    /// element is moved along with it and the elements are relocated to point
+    /// to it. Out-of-line data is only ever moved by a multiple of its
+    /// [alignment](Extent::align), and reordering it allocates a copy of the
+    /// elements and their out-of-line data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `a` or `b` are out of bounds.
+    /// - The out-of-line data of the elements isn't laid out in order.
+    /// - The reordered out-of-line data doesn't fit in the space it used to
+    ///   occupy. This can only happen when the out-of-line data of the elements
+    ///   has mixed alignments.
+    /// - An element can't be relocated to its new position.
+    ///
+    /// In all of these cases, the archived vec is left unmodified.
+    pub fn swap(self: Pin<&mut Self>, a: usize, b: usize) {
+        let mut order = (0..self.len()).collect::<Vec<_>>();
+        order.swap(a, b);
+        self.permute(&order);
+    }
+
+    /// Reverses the order of the elements of the archived vec in place.
+    ///
+    /// The out-of-line data of the elements is reordered with them. See
+    /// [`swap`](ArchivedVec::swap) for more details and the conditions under
+    /// which this panics.
+    pub fn reverse(self: Pin<&mut Self>) {
+        let order = (0..self.len()).rev().collect::<Vec<_>>();
+        self.permute(&order);
+    }
+
+    /// Sorts the archived vec in place with a comparator function.
+    ///
+    /// This sort is stable. The out-of-line data of the elements is reordered
+    /// with them. See [`swap`](ArchivedVec::swap) for more details and the
+    /// conditions under which this panics.
+    pub fn sort_by<F>(self: Pin<&mut Self>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> cmp::Ordering,
+    {
+        let slice = self.as_slice();
+        let mut order = (0..slice.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| compare(&slice[a], &slice[b]));
+        self.permute(&order);
+    }
+
+    /// Sorts the archived vec in place with a key extraction function.
+    ///
+    /// This sort is stable. See [`sort_by`](ArchivedVec::sort_by) for more
+    /// details.
+    pub fn sort_by_key<K, F>(self: Pin<&mut Self>, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let slice = self.as_slice();
+        let mut order = (0..slice.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| f(&slice[i]));
+        self.permute(&order);
+    }
+
+    /// Sorts the archived vec in place with a comparator function, but might
+    /// not preserve the order of equal elements.
+    ///
+    /// See [`sort_by`](ArchivedVec::sort_by) for more details.
+    pub fn sort_unstable_by<F>(self: Pin<&mut Self>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> cmp::Ordering,
+    {
+        let slice = self.as_slice();
+        let mut order = (0..slice.len()).collect::<Vec<_>>();
+        order.sort_unstable_by(|&a, &b| compare(&slice[a], &slice[b]));
+        self.permute(&order);
+    }
+
+    /// Sorts the archived vec in place with a key extraction function, but
+    /// might not preserve the order of equal elements.
+    ///
+    /// See [`sort_by`](ArchivedVec::sort_by) for more details.
+    pub fn sort_unstable_by_key<K, F>(self: Pin<&mut Self>, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let slice = self.as_slice();
+        let mut order = (0..slice.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&i| f(&slice[i]));
+        self.permute(&order);
+    }
+
+    /// Moves the element at `order[i]` to index `i` for every `i`, along with
+    /// its out-of-line data. `order` must be a permutation of the indices of
+    /// the archived vec.
+    fn permute(self: Pin<&mut Self>, order: &[usize]) {
+        let size = core::mem::size_of::<T>();
+        let slice = self.as_slice();
+        let elements_start = slice.as_ptr() as usize;
+        let extents = slice.iter().map(T::extent).collect::<Vec<_>>();
+
+        // The out-of-line data of the elements is serialized in order, with
+        // the elements written after all of it.
+        let mut region: Option<Extent> = None;
+        for extent in extents.iter().flatten() {
+            if let Some(region) = region {
+                assert!(
+                    region.end <= extent.start,
+                    "the out-of-line data of the archived elements is not in \
+                     order",
+                );
+            }
+            region = Extent::merge(region, Some(*extent));
+        }
+        let region = region.unwrap_or(Extent {
+            start: elements_start,
+            end: elements_start,
+            align: 1,
+        });
+        assert!(
+            region.end <= elements_start,
+            "the out-of-line data of the archived elements overlaps them",
+        );
+
+        // Pack the out-of-line data back into the same region in the new
+        // order, keeping each extent at the same offset from its alignment.
+        let mut new_starts = extents
+            .iter()
+            .map(|extent| extent.map_or(0, |e| e.start))
+            .collect::<Vec<_>>();
+        let mut cursor = region.start;
+        for &i in order {
+            if let Some(extent) = extents[i] {
+                let padding =
+                    extent.start.wrapping_sub(cursor) & (extent.align - 1);
+                new_starts[i] = cursor + padding;
+                cursor = new_starts[i] + extent.len();
+            }
+        }
+        assert!(
+            cursor <= region.end,
+            "the reordered out-of-line data of the archived elements does not \
+             fit in its original space",
+        );
+
+        // Each element moves relative to its out-of-line data by the distance
+        // it moved minus the distance its out-of-line data moved.
+        let deltas = order
+            .iter()
+            .enumerate()
+            .map(|(j, &i)| {
+                let moved = (j as isize - i as isize) * size as isize;
+                let data_moved = extents[i].map_or(0, |extent| {
+                    new_starts[i] as isize - extent.start as isize
+                });
+                moved - data_moved
+            })
+            .collect::<Vec<_>>();
+        for (j, &i) in order.iter().enumerate() {
+            assert!(
+                slice[i].can_relocate(deltas[j]),
+                "failed to relocate archived element {i} to index {j}",
+            );
+        }
+
+        // SAFETY: The out-of-line data of the elements is located in the same
+        // buffer as the elements, between `region.start` and the start of the
+        // elements. It's only owned by the elements, so it can be overwritten
+        // as long as the elements are relocated to point to its new position.
+        // The elements are only moved within their pinned slice, and we checked
+        // that they can all be relocated before modifying anything.
+        unsafe {
+            let elements =
+                self.pin_mut_slice().get_unchecked_mut().as_mut_ptr();
+            let bytes = elements.cast::<u8>();
+
+            let data = bytes.sub(elements_start - region.start);
+            let old_data =
+                core::slice::from_raw_parts(data, region.len()).to_vec();
+            core::ptr::write_bytes(data, 0, region.len());
+            for (i, extent) in extents.iter().enumerate() {
+                if let Some(extent) = extent {
+                    core::ptr::copy_nonoverlapping(
+                        old_data.as_ptr().add(extent.start - region.start),
+                        data.add(new_starts[i] - region.start),
+                        extent.len(),
+                    );
+                }
+            }
+
+            let old_elements =
+                core::slice::from_raw_parts(bytes, order.len() * size).to_vec();
+            for (j, &i) in order.iter().enumerate() {
+                core::ptr::copy_nonoverlapping(
+                    old_elements.as_ptr().add(i * size),
+                    bytes.add(j * size),
+                    size,
+                );
+                Pin::new_unchecked(&mut *elements.add(j)).relocate(deltas[j]);
+            }
+        }
+    }
+}
+
 impl<T> AsRef<[T]> for ArchivedVec<T> {
     fn as_ref(&self) -> &[T] {
         self.as_slice()
@@ -297,12 +533,12 @@ impl VecResolver {
 #[cfg(feature = "bytecheck")]
 mod verify {
     use bytecheck::{
-        rancor::{Fallible, Source},
+        rancor::{Fallible, ResultExt as _, Source},
         CheckBytes, Verify,
     };
 
     use crate::{
-        validation::{ArchiveContext, ArchiveContextExt},
+        validation::{path::PathSegment, ArchiveContext, ArchiveContextExt},
         vec::ArchivedVec,
     };
 
@@ -313,13 +549,25 @@ mod verify {
         C::Error: Source,
     {
         fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            context.check_collection_len(self.len())?;
+
             let ptr = core::ptr::slice_from_raw_parts(
                 self.ptr.as_ptr_wrapping(),
                 self.len.to_native() as usize,
             );
 
-            context.in_subtree(ptr, |context| unsafe {
-                <[T]>::check_bytes(ptr, context)
+            context.in_subtree(ptr, |context| {
+                let base = ptr.cast::<T>();
+                for index in 0..self.len() {
+                    // SAFETY: `in_subtree` has guaranteed that `ptr` is
+                    // properly aligned and points to enough bytes for the
+                    // slice, so each element pointer is as well.
+                    unsafe {
+                        T::check_bytes(base.add(index), context)
+                            .with_trace(|| PathSegment::Index(index))?;
+                    }
+                }
+                Ok(())
             })
         }
     }
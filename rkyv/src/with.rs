@@ -321,6 +321,9 @@ pub struct AsString;
 
 /// A wrapper that locks a lock and serializes the value immutably.
 ///
+/// This works with `Mutex` and `RwLock`, and with `RefCell`, which is borrowed
+/// immutably instead. Serializing a `RefCell` that is mutably borrowed fails.
+///
 /// This wrapper can panic under very specific circumstances when:
 ///
 /// 1. `serialize_with` is called and succeeds in locking the value to serialize
@@ -353,6 +356,30 @@ pub struct Lock<T> {
     _phantom: PhantomData<T>,
 }
 
+/// A wrapper that reads the value out of a cell with `get` and serializes it.
+///
+/// This works with `Cell<T>` where `T: Copy`, which archives as `T`, and with
+/// `OnceCell<T>`, which archives as an `Option<T>`. Unlike [`Unsafe`], this
+/// wrapper never needs to access the contents of a cell through a raw pointer.
+///
+/// # Example
+///
+/// ```
+/// use core::cell::{Cell, OnceCell};
+///
+/// use rkyv::{with::Get, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(Get)]
+///     cell: Cell<u32>,
+///     #[with(Get)]
+///     once_cell: OnceCell<String>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Get;
+
 /// A wrapper that serializes a `Cow` as if it were owned.
 ///
 /// # Example
@@ -394,6 +421,134 @@ pub struct AsOwned;
 #[derive(Debug)]
 pub struct AsVec;
 
+/// A wrapper that archives maps and sets as a `Vec` of entries sorted by key.
+///
+/// This supports `HashMap`, `HashSet`, `BTreeMap`, and `BTreeSet`. Unlike
+/// [`AsVec`], the archived maps and sets still support keyed lookups and range
+/// queries using a binary search. See
+/// [`sorted_vec_map`](crate::collections::sorted_vec_map) and
+/// [`sorted_vec_set`](crate::collections::sorted_vec_set) for details.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rkyv::{with::AsSortedVec, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsSortedVec)]
+///     values: HashMap<String, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsSortedVec;
+
+/// A wrapper that archives string-keyed maps as compressed tries.
+///
+/// This supports `BTreeMap<String, V>`. The archived
+/// [`ArchivedTrieMap`](crate::collections::trie::ArchivedTrieMap) stores shared
+/// key prefixes only once, and supports prefix and range queries.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use rkyv::{with::AsTrie, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsTrie)]
+///     words: BTreeMap<String, u64>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsTrie;
+
+/// A wrapper that archives sets of `u32` as compressed bitmaps.
+///
+/// This supports `BTreeSet<u32>`. The archived
+/// [`ArchivedBitmap`](crate::collections::bitmap::ArchivedBitmap) is much
+/// smaller than a hash set for large sets of values, and supports rank queries
+/// and set operations.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeSet;
+///
+/// use rkyv::{with::AsBitmap, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsBitmap)]
+///     ids: BTreeSet<u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsBitmap;
+
+/// A wrapper that archives maps and sets as perfect hash maps and sets.
+///
+/// This supports `HashMap`, `HashSet`, `BTreeMap`, and `BTreeSet`. The archived
+/// [`ArchivedPhfMap`](crate::collections::phf::ArchivedPhfMap) and
+/// [`ArchivedPhfSet`](crate::collections::phf::ArchivedPhfSet) guarantee that
+/// every lookup compares against at most one entry, at the cost of a slower
+/// serialization.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rkyv::{with::AsPerfectHash, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsPerfectHash)]
+///     values: HashMap<String, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsPerfectHash;
+
+/// A wrapper that archives hash maps and sets with the hasher `H` keyed by the
+/// serializer.
+///
+/// Archived hash maps and sets are built with an unseeded
+/// [`FxHasher64`](crate::hash::FxHasher64) by default, so anyone who chooses
+/// their keys can make those keys collide. This wrapper instead keys `H` with
+/// the key from the serializer's [`SeedProvider`](crate::ser::SeedProvider),
+/// and records the seed in the archive so that lookups hash keys the same way.
+///
+/// Serializers use the public
+/// [`DEFAULT_HASHER_KEY`](crate::ser::seed::DEFAULT_HASHER_KEY) unless they
+/// are wrapped in a [`WithSeed`](crate::ser::seed::WithSeed), which provides a
+/// chosen or random key. Serializing with the same key always produces the
+/// same bytes.
+///
+/// This wrapper supports `HashMap`, `HashSet`, `IndexMap`, and `IndexSet`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rkyv::{hash::SipHasher13, with::Seeded, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(Seeded<SipHasher13>)]
+///     values: HashMap<String, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Seeded<H> {
+    _phantom: PhantomData<H>,
+}
+
 /// A wrapper that niches some type combinations.
 ///
 /// A common type combination is `Option<Box<T>>`. By using a null pointer, the
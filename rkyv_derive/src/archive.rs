@@ -1,4 +1,6 @@
 mod r#enum;
+mod extensible;
+mod open_enum;
 mod printing;
 mod r#struct;
 
@@ -8,7 +10,10 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Data, DeriveInput, Error, Field, Ident, Meta};
 
-use crate::attributes::Attributes;
+use crate::{
+    attributes::{Attributes, FieldAttributes},
+    util::is_open_enum,
+};
 
 pub fn derive(input: &mut DeriveInput) -> Result<TokenStream, Error> {
     let attributes = Attributes::parse(input)?;
@@ -35,6 +40,14 @@ fn archived_doc(name: &Ident) -> String {
     format!("An archived [`{}`]", name)
 }
 
+fn extensible_fields_doc(name: &Ident) -> String {
+    format!("The archived fields of [`{}`]", name)
+}
+
+fn open_enum_variant_doc(name: &Ident) -> String {
+    format!("The active variant of an archived [`{}`]", name)
+}
+
 fn resolver_doc(name: &Ident) -> String {
     format!("The resolver for an archived [`{}`]", name)
 }
@@ -86,8 +99,36 @@ fn derive_archive_impl(
 
     let printing = printing::Printing::new(input, attributes)?;
 
+    if attributes.extensible.is_none() {
+        let fields: Vec<&Field> = match &input.data {
+            Data::Struct(data) => data.fields.iter().collect(),
+            Data::Enum(data) => {
+                data.variants.iter().flat_map(|v| v.fields.iter()).collect()
+            }
+            Data::Union(_) => Vec::new(),
+        };
+        for field in fields {
+            if let Some(since) = FieldAttributes::parse(field)?.since {
+                return Err(Error::new_spanned(
+                    since,
+                    "since may only be used on the fields of extensible \
+                     structs",
+                ));
+            }
+        }
+    }
+
     let (archive_types, archive_impls) = match input.data {
+        _ if attributes.extensible.is_some() => {
+            extensible::impl_extensible(input, attributes, &printing)?
+        }
         Data::Struct(_) => r#struct::impl_struct(input, attributes, &printing)?,
+        _ if attributes.open.is_some() => {
+            open_enum::impl_open_enum(input, attributes, &printing)?
+        }
+        Data::Enum(ref data) if is_open_enum(attributes, data)? => {
+            open_enum::impl_open_enum(input, attributes, &printing)?
+        }
         Data::Enum(_) => r#enum::impl_enum(input, attributes, &printing)?,
         Data::Union(_) => {
             return Err(Error::new_spanned(
@@ -0,0 +1,386 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, FieldsNamed};
+
+use crate::{
+    archive::{
+        archived_doc, extensible_fields_doc, field_archive_attrs,
+        printing::Printing, struct_field_doc,
+    },
+    attributes::Attributes,
+    util::{
+        archive_bound, archived, extensible_fields_name, field_versions,
+        is_not_omitted, strip_raw,
+    },
+};
+
+pub fn impl_extensible(
+    input: &mut DeriveInput,
+    attributes: &Attributes,
+    printing: &Printing,
+) -> Result<(TokenStream, TokenStream), Error> {
+    let fields =
+        match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => fields.clone(),
+                _ => return Err(Error::new_spanned(
+                    &input.ident,
+                    "extensible may only be used on structs with named fields",
+                )),
+            },
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "extensible may only be used on structs",
+                ))
+            }
+        };
+
+    if let Some(ref relocate) = attributes.relocate {
+        return Err(Error::new_spanned(
+            relocate,
+            "relocate may not be used with extensible",
+        ));
+    }
+    if let Some(ref compares) = attributes.compares {
+        return Err(Error::new_spanned(
+            compares,
+            "compare(...) may not be used with extensible",
+        ));
+    }
+    if let Some(ref resolver) = attributes.resolver {
+        return Err(Error::new_spanned(
+            resolver,
+            "resolver = \"...\" may not be used with extensible because the \
+             resolver is always `ExtensibleResolver`",
+        ));
+    }
+
+    let rkyv_path = &printing.rkyv_path;
+    let versions = field_versions(&Fields::Named(fields.clone()))?;
+
+    let where_clause = input.generics.make_where_clause();
+    for field in fields.named.iter().filter(is_not_omitted) {
+        where_clause
+            .predicates
+            .push(archive_bound(rkyv_path, field)?);
+    }
+
+    let fields_name = extensible_fields_name(&printing.archived_name);
+    let archived_defs = generate_archived_defs(input, printing, &fields)?;
+    let extensible_fields_impl =
+        generate_extensible_fields_impl(input, printing, &fields, &versions)?;
+    let check_fields_impl = if cfg!(feature = "bytecheck") {
+        Some(generate_check_fields_impl(
+            input, printing, &fields, &versions,
+        )?)
+    } else {
+        None
+    };
+
+    let name = &input.ident;
+    let archived_name = &printing.archived_name;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    let accessors = fields
+        .named
+        .iter()
+        .zip(versions.iter())
+        .map(|(field, &version)| {
+            let field_name = field.ident.as_ref().unwrap();
+            let field_doc = struct_field_doc(name, field_name);
+            let vis = &field.vis;
+            let archived_ty = archived(rkyv_path, field)?;
+            let offset = quote! {
+                ::core::mem::offset_of!(
+                    #fields_name #ty_generics,
+                    #field_name
+                )
+            };
+
+            if version == 0 {
+                Ok(quote! {
+                    #[doc = #field_doc]
+                    #vis fn #field_name(&self) -> &#archived_ty {
+                        // SAFETY: The field has the given type and offset,
+                        // and fields without a version are present in every
+                        // version.
+                        unsafe {
+                            self.inner.field_unchecked::<#archived_ty>(#offset)
+                        }
+                    }
+                })
+            } else {
+                Ok(quote! {
+                    #[doc = #field_doc]
+                    ///
+                    /// This returns `None` if the field is missing from the
+                    /// archived version.
+                    #vis fn #field_name(
+                        &self,
+                    ) -> ::core::option::Option<&#archived_ty> {
+                        // SAFETY: The field has the given type and offset, and
+                        // was added in the given version.
+                        unsafe {
+                            self.inner.field::<#archived_ty>(#version, #offset)
+                        }
+                    }
+                })
+            }
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok((
+        quote! {
+            #archived_defs
+            #extensible_fields_impl
+            #check_fields_impl
+        },
+        quote! {
+            impl #impl_generics #archived_name #ty_generics #where_clause {
+                #(#accessors)*
+            }
+
+            impl #impl_generics ::core::ops::Deref
+                for #archived_name #ty_generics
+            #where_clause
+            {
+                type Target = #rkyv_path::extensible::ArchivedExtensible<
+                    #fields_name #ty_generics,
+                >;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.inner
+                }
+            }
+
+            impl #impl_generics #rkyv_path::Archive for #name #ty_generics
+            #where_clause
+            {
+                type Archived = #archived_name #ty_generics;
+                type Resolver = #rkyv_path::extensible::ExtensibleResolver;
+
+                fn resolve(
+                    &self,
+                    resolver: Self::Resolver,
+                    out: #rkyv_path::Place<Self::Archived>,
+                ) {
+                    let field_ptr = unsafe {
+                        ::core::ptr::addr_of_mut!((*out.ptr()).inner)
+                    };
+                    let out_field = unsafe {
+                        #rkyv_path::Place::from_field_unchecked(out, field_ptr)
+                    };
+                    #rkyv_path::extensible::ArchivedExtensible::<
+                        #fields_name #ty_generics,
+                    >::resolve_from_resolver(resolver, out_field);
+                }
+            }
+        },
+    ))
+}
+
+fn generate_archived_defs(
+    input: &DeriveInput,
+    printing: &Printing,
+    fields: &FieldsNamed,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+
+    let archived_fields = fields
+        .named
+        .iter()
+        .map(|field| {
+            let field_ty = archived(rkyv_path, field)?;
+            let vis = &field.vis;
+            let archive_attrs = field_archive_attrs(field);
+
+            let field_name = field.ident.as_ref().unwrap();
+            let field_doc = struct_field_doc(&input.ident, field_name);
+            Ok(quote! {
+                #[doc = #field_doc]
+                #(#[#archive_attrs])*
+                #vis #field_name: #field_ty
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let archived_doc = archived_doc(&input.ident);
+    let fields_doc = extensible_fields_doc(&input.ident);
+    let archive_attrs = &printing.archive_attrs;
+    let vis = &input.vis;
+    let archived_name = &printing.archived_name;
+    let fields_name = extensible_fields_name(archived_name);
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[doc = #fields_doc]
+        #(#archive_attrs)*
+        #[repr(C)]
+        #vis struct #fields_name #generics #where_clause {
+            #(#archived_fields,)*
+        }
+
+        #[automatically_derived]
+        #[doc = #archived_doc]
+        #(#archive_attrs)*
+        #[repr(C)]
+        #vis struct #archived_name #generics #where_clause {
+            inner: #rkyv_path::extensible::ArchivedExtensible<
+                #fields_name #ty_generics,
+            >,
+        }
+
+        // SAFETY: As long as the `Archive` impl holds, the archived fields are
+        // guaranteed to be `Portable`.
+        unsafe impl #impl_generics #rkyv_path::Portable
+            for #fields_name #ty_generics
+        #where_clause
+        {}
+
+        // SAFETY: As long as the `Archive` impl holds, the archived type is
+        // guaranteed to be `Portable`.
+        unsafe impl #impl_generics #rkyv_path::Portable
+            for #archived_name #ty_generics
+        #where_clause
+        {}
+    })
+}
+
+fn generate_extensible_fields_impl(
+    input: &DeriveInput,
+    printing: &Printing,
+    fields: &FieldsNamed,
+    versions: &[u32],
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+
+    // Each time the version increases, every field before it is present in
+    // the earlier versions.
+    let mut checks = Vec::new();
+    let mut end = quote! { 0 };
+    let mut latest = 0;
+    for (field, &version) in fields.named.iter().zip(versions.iter()) {
+        if version > latest {
+            checks.push(quote! {
+                if version < #version {
+                    return #end;
+                }
+            });
+            latest = version;
+        }
+
+        let field_name = field.ident.as_ref().unwrap();
+        let archived_ty = archived(rkyv_path, field)?;
+        end = quote! {
+            ::core::mem::offset_of!(Self, #field_name)
+                + ::core::mem::size_of::<#archived_ty>()
+        };
+    }
+
+    let fields_name = extensible_fields_name(&printing.archived_name);
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    Ok(quote! {
+        // SAFETY: The fields are `repr(C)` and ordered by version, `VERSION` is
+        // the version of the last field, and `len_in` returns the end of the
+        // last field present in each version.
+        unsafe impl #impl_generics #rkyv_path::extensible::ExtensibleFields
+            for #fields_name #ty_generics
+        #where_clause
+        {
+            const VERSION: u32 = #latest;
+
+            #[allow(unused_variables)]
+            fn len_in(version: u32) -> usize {
+                #(#checks)*
+                ::core::mem::size_of::<Self>()
+            }
+        }
+    })
+}
+
+fn generate_check_fields_impl(
+    input: &DeriveInput,
+    printing: &Printing,
+    fields: &FieldsNamed,
+    versions: &[u32],
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+
+    let mut check_generics = input.generics.clone();
+    check_generics.params.push(parse_quote! {
+        __C: #rkyv_path::rancor::Fallible + ?Sized
+    });
+    let check_where = check_generics.make_where_clause();
+    check_where.predicates.push(parse_quote! {
+        <__C as #rkyv_path::rancor::Fallible>::Error:
+            #rkyv_path::rancor::Source
+    });
+    for field in fields.named.iter().filter(is_not_omitted) {
+        let archived_ty = archived(rkyv_path, field)?;
+        check_where.predicates.push(parse_quote! {
+            #archived_ty: #rkyv_path::bytecheck::CheckBytes<__C>
+        });
+    }
+
+    let mut checks = Vec::new();
+    let mut latest = 0;
+    for (field, &version) in fields.named.iter().zip(versions.iter()) {
+        if version > latest {
+            checks.push(quote! {
+                if version < #version {
+                    return Ok(());
+                }
+            });
+            latest = version;
+        }
+
+        let field_name = field.ident.as_ref().unwrap();
+        let field_name_str = strip_raw(field_name);
+        let archived_ty = archived(rkyv_path, field)?;
+        checks.push(quote! {
+            // SAFETY: The caller has guaranteed that `value` points to enough
+            // bytes for every field present in `version`.
+            unsafe {
+                #rkyv_path::extensible::check_field::<#archived_ty, __C>(
+                    base,
+                    ::core::mem::offset_of!(Self, #field_name),
+                    #field_name_str,
+                    context,
+                )?;
+            }
+        });
+    }
+
+    let fields_name = extensible_fields_name(&printing.archived_name);
+    let (impl_generics, _, where_clause) = check_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        // SAFETY: `check_fields` checks every field present in `version`.
+        unsafe impl #impl_generics #rkyv_path::extensible::CheckFields<__C>
+            for #fields_name #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            unsafe fn check_fields(
+                value: *const Self,
+                version: u32,
+                context: &mut __C,
+            ) -> ::core::result::Result<
+                (),
+                <__C as #rkyv_path::rancor::Fallible>::Error,
+            > {
+                let base = value.cast::<u8>();
+                #(#checks)*
+                Ok(())
+            }
+        }
+    })
+}
@@ -0,0 +1,569 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, Data, DataEnum, DeriveInput, Error, Fields, GenericParam,
+    Ident, LitStr,
+};
+
+use crate::{
+    archive::{
+        archived_doc, enum_field_doc, field_archive_attrs,
+        open_enum_variant_doc, printing::Printing, variant_doc,
+    },
+    attributes::Attributes,
+    util::{
+        archive_bound, archived, is_not_omitted, members,
+        open_enum_fields_name, open_enum_variant_name, strip_raw,
+        unknown_variant,
+    },
+};
+
+pub fn impl_open_enum(
+    input: &mut DeriveInput,
+    attributes: &Attributes,
+    printing: &Printing,
+) -> Result<(TokenStream, TokenStream), Error> {
+    let data = match &input.data {
+        Data::Enum(data) => data.clone(),
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "open may only be used on enums",
+            ))
+        }
+    };
+
+    if data.variants.len() > 256 {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "enums with more than 256 variants cannot derive Archive",
+        ));
+    }
+    if let Some(ref relocate) = attributes.relocate {
+        return Err(Error::new_spanned(
+            relocate,
+            "relocate may not be used with open enums",
+        ));
+    }
+    if let Some(ref compares) = attributes.compares {
+        return Err(Error::new_spanned(
+            compares,
+            "compare(...) may not be used with open enums",
+        ));
+    }
+    if let Some(ref schema) = attributes.schema {
+        return Err(Error::new_spanned(
+            schema,
+            "schema may not be used with open enums",
+        ));
+    }
+
+    let unknown = unknown_variant(&data)?.map(|v| v.ident.clone());
+    for variant in data.variants.iter() {
+        if variant.ident == "Variant" {
+            return Err(Error::new_spanned(
+                &variant.ident,
+                "open enums may not have a variant named `Variant` because it \
+                 would conflict with the generated variant type",
+            ));
+        }
+        if unknown.is_none() && variant.ident == "Unknown" {
+            return Err(Error::new_spanned(
+                &variant.ident,
+                "open enums may not have a variant named `Unknown` unless it \
+                 is marked with #[archive(unknown)]",
+            ));
+        }
+    }
+    let unknown =
+        unknown.unwrap_or_else(|| Ident::new("Unknown", input.ident.span()));
+
+    let rkyv_path = &printing.rkyv_path;
+
+    let where_clause = input.generics.make_where_clause();
+    for field in data
+        .variants
+        .iter()
+        .flat_map(|v| v.fields.iter())
+        .filter(is_not_omitted)
+    {
+        where_clause
+            .predicates
+            .push(archive_bound(rkyv_path, field)?);
+    }
+
+    let archived_defs = generate_archived_defs(input, printing, &data)?;
+    let variant_def = generate_variant_def(input, printing, &data, &unknown)?;
+    let variant_fn = generate_variant_fn(input, printing, &data, &unknown);
+    let check_variants_impl = if cfg!(feature = "bytecheck") {
+        Some(generate_check_variants_impl(input, printing, &data)?)
+    } else {
+        None
+    };
+
+    let archived_variant_tags = data.variants.iter().map(|v| {
+        let variant = &v.ident;
+        let discriminant = v
+            .discriminant
+            .as_ref()
+            .map(|(eq, expr)| quote! { #eq #expr });
+        quote! { #variant #discriminant }
+    });
+
+    let tag_arms = data.variants.iter().map(|v| {
+        let variant = &v.ident;
+        match v.fields {
+            Fields::Named(_) => quote! {
+                Self::#variant { .. } => ArchivedTag::#variant
+            },
+            Fields::Unnamed(_) => quote! {
+                Self::#variant(..) => ArchivedTag::#variant
+            },
+            Fields::Unit => quote! { Self::#variant => ArchivedTag::#variant },
+        }
+    });
+
+    let name = &input.ident;
+    let archived_name = &printing.archived_name;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    Ok((
+        quote! {
+            #archived_defs
+            #variant_def
+        },
+        quote! {
+            #[derive(PartialEq, PartialOrd)]
+            #[repr(u8)]
+            enum ArchivedTag {
+                #(#archived_variant_tags,)*
+            }
+
+            #variant_fn
+            #check_variants_impl
+
+            impl #impl_generics ::core::ops::Deref
+                for #archived_name #ty_generics
+            #where_clause
+            {
+                type Target = #rkyv_path::extensible::ArchivedOpenEnum<Self>;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.inner
+                }
+            }
+
+            impl #impl_generics Archive for #name #ty_generics #where_clause {
+                type Archived = #archived_name #ty_generics;
+                type Resolver = #rkyv_path::extensible::OpenEnumResolver;
+
+                fn resolve(
+                    &self,
+                    resolver: Self::Resolver,
+                    out: #rkyv_path::Place<Self::Archived>,
+                ) {
+                    let tag = match self {
+                        #(#tag_arms,)*
+                    };
+                    let field_ptr = unsafe {
+                        ::core::ptr::addr_of_mut!((*out.ptr()).inner)
+                    };
+                    let out_field = unsafe {
+                        #rkyv_path::Place::from_field_unchecked(out, field_ptr)
+                    };
+                    #rkyv_path::extensible::ArchivedOpenEnum::<
+                        #archived_name #ty_generics,
+                    >::resolve_from_resolver(tag as u8, resolver, out_field);
+                }
+            }
+        },
+    ))
+}
+
+fn generate_archived_defs(
+    input: &DeriveInput,
+    printing: &Printing,
+    data: &DataEnum,
+) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let rkyv_path = &printing.rkyv_path;
+    let archive_attrs = &printing.archive_attrs;
+    let vis = &input.vis;
+    let archived_name = &printing.archived_name;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    // Every generic parameter is used by at least one variant, but not
+    // necessarily by each of their fields.
+    let phantom = (!generics.params.is_empty()).then(|| {
+        quote! { ::core::marker::PhantomData<#name #ty_generics> }
+    });
+
+    let mut fields_defs = Vec::new();
+    for v in data.variants.iter() {
+        let variant = &v.ident;
+        let fields_name = open_enum_fields_name(archived_name, variant);
+        let variant_doc = variant_doc(name, variant);
+
+        let fields_def = match v.fields {
+            Fields::Named(ref fields) => {
+                let fields = fields
+                    .named
+                    .iter()
+                    .map(|f| {
+                        let field_name = f.ident.as_ref().unwrap();
+                        let field_doc =
+                            enum_field_doc(name, variant, field_name);
+                        let archive_attrs = field_archive_attrs(f);
+                        let archived = archived(rkyv_path, f)?;
+                        Ok(quote! {
+                            #[doc = #field_doc]
+                            #(#[#archive_attrs])*
+                            #vis #field_name: #archived
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                let phantom = phantom.as_ref().map(|phantom| {
+                    quote! { __phantom: #phantom, }
+                });
+
+                quote! {
+                    #vis struct #fields_name #generics #where_clause {
+                        #(#fields,)*
+                        #phantom
+                    }
+                }
+            }
+            Fields::Unnamed(ref fields) => {
+                let fields = fields
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| {
+                        let field_doc = enum_field_doc(name, variant, &i);
+                        let archive_attrs = field_archive_attrs(f);
+                        let archived = archived(rkyv_path, f)?;
+                        Ok(quote! {
+                            #[doc = #field_doc]
+                            #(#[#archive_attrs])*
+                            #vis #archived
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                let phantom =
+                    phantom.as_ref().map(|phantom| quote! { #phantom, });
+
+                quote! {
+                    #vis struct #fields_name #generics (
+                        #(#fields,)*
+                        #phantom
+                    ) #where_clause;
+                }
+            }
+            Fields::Unit => continue,
+        };
+
+        fields_defs.push(quote! {
+            #[automatically_derived]
+            #[doc = #variant_doc]
+            #(#archive_attrs)*
+            #[repr(C)]
+            #fields_def
+
+            // SAFETY: As long as the `Archive` impl holds, the archived fields
+            // are guaranteed to be `Portable`.
+            unsafe impl #impl_generics #rkyv_path::Portable
+                for #fields_name #ty_generics
+            #where_clause
+            {}
+        });
+    }
+
+    let archived_doc = archived_doc(name);
+    // The header only refers to its generic parameters through itself.
+    let phantom = if input.generics.params.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            _phantom: ::core::marker::PhantomData<#name #ty_generics>,
+        })
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[doc = #archived_doc]
+        #(#archive_attrs)*
+        #[repr(C)]
+        #vis struct #archived_name #generics #where_clause {
+            inner: #rkyv_path::extensible::ArchivedOpenEnum<
+                #archived_name #ty_generics,
+            >,
+            #phantom
+        }
+
+        // SAFETY: As long as the `Archive` impl holds, the archived type is
+        // guaranteed to be `Portable`.
+        unsafe impl #impl_generics #rkyv_path::Portable
+            for #archived_name #ty_generics
+        #where_clause
+        {}
+
+        #(#fields_defs)*
+    })
+}
+
+fn has_fields(data: &DataEnum) -> bool {
+    data.variants
+        .iter()
+        .any(|v| !matches!(v.fields, Fields::Unit))
+}
+
+fn generate_variant_def(
+    input: &DeriveInput,
+    printing: &Printing,
+    data: &DataEnum,
+    unknown: &Ident,
+) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let archived_name = &printing.archived_name;
+    let variant_name = open_enum_variant_name(archived_name);
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let variants =
+        data.variants
+            .iter()
+            .filter(|v| v.ident != *unknown)
+            .map(|v| {
+                let variant = &v.ident;
+                let variant_doc = variant_doc(name, variant);
+                if matches!(v.fields, Fields::Unit) {
+                    quote! {
+                        #[doc = #variant_doc]
+                        #variant
+                    }
+                } else {
+                    let fields_name =
+                        open_enum_fields_name(archived_name, variant);
+                    quote! {
+                        #[doc = #variant_doc]
+                        #variant(&'__a #fields_name #ty_generics)
+                    }
+                }
+            });
+
+    // The variant type only borrows from the archived enum if some of its
+    // variants have fields.
+    let mut generics = input.generics.clone();
+    if has_fields(data) {
+        generics
+            .params
+            .insert(0, GenericParam::Lifetime(parse_quote!('__a)));
+    }
+    let where_clause = &generics.where_clause;
+
+    let doc = open_enum_variant_doc(name);
+    let vis = &input.vis;
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[doc = #doc]
+        #vis enum #variant_name #generics #where_clause {
+            #(#variants,)*
+            /// A variant which is unknown to the reader, along with its tag.
+            #unknown(u8),
+        }
+    })
+}
+
+fn generate_variant_fn(
+    input: &DeriveInput,
+    printing: &Printing,
+    data: &DataEnum,
+    unknown: &Ident,
+) -> TokenStream {
+    let archived_name = &printing.archived_name;
+    let variant_name = open_enum_variant_name(archived_name);
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    let checks =
+        data.variants
+            .iter()
+            .filter(|v| v.ident != *unknown)
+            .map(|v| {
+                let variant = &v.ident;
+                if matches!(v.fields, Fields::Unit) {
+                    quote! {
+                        if tag == ArchivedTag::#variant as u8 {
+                            return #variant_name::#variant;
+                        }
+                    }
+                } else {
+                    let fields_name =
+                        open_enum_fields_name(archived_name, variant);
+                    quote! {
+                        if tag == ArchivedTag::#variant as u8 {
+                            // SAFETY: The archived tag is the tag of this
+                            // variant, so its fields have this type.
+                            let fields = unsafe {
+                                self.inner.fields::<#fields_name #ty_generics>()
+                            };
+                            return #variant_name::#variant(fields);
+                        }
+                    }
+                }
+            });
+
+    let (lifetime, self_lifetime, return_generics) = if has_fields(data) {
+        let mut generics = input.generics.clone();
+        generics
+            .params
+            .insert(0, GenericParam::Lifetime(parse_quote!('__a)));
+        let (_, return_generics, _) = generics.split_for_impl();
+        (
+            quote! { <'__a> },
+            quote! { '__a },
+            quote! { #return_generics },
+        )
+    } else {
+        (
+            TokenStream::new(),
+            TokenStream::new(),
+            quote! { #ty_generics },
+        )
+    };
+
+    let vis = &input.vis;
+
+    quote! {
+        impl #impl_generics #archived_name #ty_generics #where_clause {
+            /// Returns the active variant of the archived enum.
+            #vis fn variant #lifetime (
+                &#self_lifetime self,
+            ) -> #variant_name #return_generics {
+                let tag = self.inner.tag();
+                #(#checks)*
+                #variant_name::#unknown(tag)
+            }
+        }
+    }
+}
+
+fn generate_check_variants_impl(
+    input: &DeriveInput,
+    printing: &Printing,
+    data: &DataEnum,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+    let archived_name = &printing.archived_name;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let mut check_generics = input.generics.clone();
+    check_generics.params.push(parse_quote! {
+        __C: #rkyv_path::rancor::Fallible + ?Sized
+    });
+    let check_where = check_generics.make_where_clause();
+    check_where.predicates.push(parse_quote! {
+        <__C as #rkyv_path::rancor::Fallible>::Error:
+            #rkyv_path::rancor::Source
+    });
+    for field in data
+        .variants
+        .iter()
+        .flat_map(|v| v.fields.iter())
+        .filter(is_not_omitted)
+    {
+        let archived_ty = archived(rkyv_path, field)?;
+        check_where.predicates.push(parse_quote! {
+            #archived_ty: #rkyv_path::bytecheck::CheckBytes<__C>
+        });
+    }
+
+    let mut len_checks = Vec::new();
+    let mut variant_checks = Vec::new();
+    for v in data.variants.iter() {
+        let variant = &v.ident;
+        if matches!(v.fields, Fields::Unit) {
+            len_checks.push(quote! {
+                if tag == ArchivedTag::#variant as u8 {
+                    return Some(0);
+                }
+            });
+            continue;
+        }
+
+        let fields_name = open_enum_fields_name(archived_name, variant);
+        len_checks.push(quote! {
+            if tag == ArchivedTag::#variant as u8 {
+                return Some(::core::mem::size_of::<
+                    #fields_name #ty_generics
+                >());
+            }
+        });
+
+        let field_checks = members(&v.fields)
+            .map(|(member, field)| {
+                let archived_ty = archived(rkyv_path, field)?;
+                let field_name = match field.ident {
+                    Some(ref ident) => strip_raw(ident),
+                    None => quote!(#member).to_string(),
+                };
+                let field_name = LitStr::new(&field_name, variant.span());
+                Ok(quote! {
+                    // SAFETY: The caller has guaranteed that `fields` points
+                    // to enough bytes for the fields of this variant.
+                    unsafe {
+                        #rkyv_path::extensible::check_field::<
+                            #archived_ty,
+                            __C,
+                        >(
+                            fields,
+                            ::core::mem::offset_of!(
+                                #fields_name #ty_generics,
+                                #member
+                            ),
+                            #field_name,
+                            context,
+                        )?;
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        variant_checks.push(quote! {
+            if tag == ArchivedTag::#variant as u8 {
+                #(#field_checks)*
+            }
+        });
+    }
+
+    let (impl_generics, _, where_clause) = check_generics.split_for_impl();
+
+    Ok(quote! {
+        // SAFETY: `fields_len` returns the size of the fields of each known
+        // variant, and `check_variant` checks each of their fields.
+        unsafe impl #impl_generics #rkyv_path::extensible::CheckVariants<__C>
+            for #archived_name #ty_generics
+        #where_clause
+        {
+            fn fields_len(tag: u8) -> ::core::option::Option<usize> {
+                #(#len_checks)*
+                None
+            }
+
+            #[allow(unused_variables)]
+            unsafe fn check_variant(
+                tag: u8,
+                fields: *const u8,
+                context: &mut __C,
+            ) -> ::core::result::Result<
+                (),
+                <__C as #rkyv_path::rancor::Fallible>::Error,
+            > {
+                #(#variant_checks)*
+                Ok(())
+            }
+        }
+    })
+}
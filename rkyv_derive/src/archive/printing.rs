@@ -34,6 +34,34 @@ impl Printing {
                      because no type is generated",
                 ));
             }
+            if let Some(ref relocate) = attributes.relocate {
+                return Err(Error::new_spanned(
+                    relocate,
+                    "relocate may not be used with as = \"...\" because no \
+                     type is generated",
+                ));
+            }
+            if let Some(ref schema) = attributes.schema {
+                return Err(Error::new_spanned(
+                    schema,
+                    "schema may not be used with as = \"...\" because no \
+                     type is generated",
+                ));
+            }
+            if let Some(ref extensible) = attributes.extensible {
+                return Err(Error::new_spanned(
+                    extensible,
+                    "extensible may not be used with as = \"...\" because \
+                     no type is generated",
+                ));
+            }
+            if let Some(ref open) = attributes.open {
+                return Err(Error::new_spanned(
+                    open,
+                    "open may not be used with as = \"...\" because no type \
+                     is generated",
+                ));
+            }
             if let Some(first) = attributes.attrs.first() {
                 return Err(Error::new_spanned(
                     first,
@@ -79,8 +107,25 @@ impl Printing {
             Vec::new()
         };
 
+        let mut derive_rkyv = Vec::<Attribute>::new();
+        if attributes.relocate.is_some() {
+            derive_rkyv.push(parse_quote! { #[derive(#rkyv_path::Relocate)] });
+            // SAFETY: The archived type only contains the archived types of
+            // the fields, which relocate their own offsets.
+            derive_rkyv.push(parse_quote! { #[relocate(unsafe_no_offsets)] });
+        }
+        if attributes.schema.is_some() {
+            derive_rkyv.push(parse_quote! {
+                #[derive(#rkyv_path::schema::ArchiveSchema)]
+            });
+        }
+        if !derive_rkyv.is_empty() {
+            derive_rkyv.push(parse_quote! { #[archive(crate = #rkyv_path)] });
+        }
+
         let archive_attrs = derive_check_bytes
             .into_iter()
+            .chain(derive_rkyv)
             .chain(
                 attributes
                     .attrs
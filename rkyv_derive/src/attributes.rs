@@ -1,8 +1,8 @@
 use quote::ToTokens;
 use syn::{
     meta::ParseNestedMeta, parenthesized, parse::Parse, parse_quote,
-    punctuated::Punctuated, AttrStyle, DeriveInput, Error, Ident, LitStr, Meta,
-    Path, Token, WherePredicate,
+    punctuated::Punctuated, AttrStyle, DeriveInput, Error, Field, Ident,
+    LitInt, LitStr, Meta, Path, Token, Variant, WherePredicate,
 };
 
 fn try_set_attribute<T: ToTokens>(
@@ -32,6 +32,10 @@ pub struct Attributes {
     pub serialize_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
     pub deserialize_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
     pub check_bytes: Option<Path>,
+    pub relocate: Option<Path>,
+    pub schema: Option<Path>,
+    pub extensible: Option<Path>,
+    pub open: Option<Path>,
     pub crate_path: Option<Path>,
 }
 
@@ -43,6 +47,30 @@ impl Attributes {
             }
 
             try_set_attribute(&mut self.check_bytes, meta.path, "check_bytes")
+        } else if meta.path.is_ident("relocate") {
+            if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                return Err(meta.error("relocate argument must be a path"));
+            }
+
+            try_set_attribute(&mut self.relocate, meta.path, "relocate")
+        } else if meta.path.is_ident("schema") {
+            if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                return Err(meta.error("schema argument must be a path"));
+            }
+
+            try_set_attribute(&mut self.schema, meta.path, "schema")
+        } else if meta.path.is_ident("extensible") {
+            if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                return Err(meta.error("extensible argument must be a path"));
+            }
+
+            try_set_attribute(&mut self.extensible, meta.path, "extensible")
+        } else if meta.path.is_ident("open") {
+            if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                return Err(meta.error("open argument must be a path"));
+            }
+
+            try_set_attribute(&mut self.open, meta.path, "open")
         } else if meta.path.is_ident("compare") {
             let traits;
             parenthesized!(traits in meta.input);
@@ -142,3 +170,65 @@ impl Attributes {
             .unwrap_or_else(|| parse_quote! { ::rkyv })
     }
 }
+
+#[derive(Default)]
+pub struct FieldAttributes {
+    pub since: Option<LitInt>,
+}
+
+impl FieldAttributes {
+    fn parse_meta(&mut self, meta: ParseNestedMeta<'_>) -> Result<(), Error> {
+        if meta.path.is_ident("since") {
+            try_set_attribute(&mut self.since, meta.value()?.parse()?, "since")
+        } else {
+            Err(meta.error("unrecognized archive field argument"))
+        }
+    }
+
+    pub fn parse(field: &Field) -> Result<FieldAttributes, Error> {
+        let mut result = FieldAttributes::default();
+        for attr in field.attrs.iter() {
+            if attr.path().is_ident("archive") {
+                attr.parse_nested_meta(|meta| result.parse_meta(meta))?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn since(&self) -> Result<u32, Error> {
+        self.since
+            .as_ref()
+            .map_or(Ok(0), |since| since.base10_parse())
+    }
+}
+
+#[derive(Default)]
+pub struct VariantAttributes {
+    pub unknown: Option<Path>,
+}
+
+impl VariantAttributes {
+    fn parse_meta(&mut self, meta: ParseNestedMeta<'_>) -> Result<(), Error> {
+        if meta.path.is_ident("unknown") {
+            if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                return Err(meta.error("unknown argument must be a path"));
+            }
+
+            try_set_attribute(&mut self.unknown, meta.path, "unknown")
+        } else {
+            Err(meta.error("unrecognized archive variant argument"))
+        }
+    }
+
+    pub fn parse(variant: &Variant) -> Result<VariantAttributes, Error> {
+        let mut result = VariantAttributes::default();
+        for attr in variant.attrs.iter() {
+            if attr.path().is_ident("archive") {
+                attr.parse_nested_meta(|meta| result.parse_meta(meta))?;
+            }
+        }
+
+        Ok(result)
+    }
+}
@@ -1,13 +1,17 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
-    parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
-    Error, Fields, Generics, Ident, Index,
+    parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DataEnum,
+    DeriveInput, Error, Fields, Generics, Ident, Index,
 };
 
 use crate::{
-    attributes::Attributes,
-    util::{archive_bound, deserialize, deserialize_bound, is_not_omitted},
+    attributes::{Attributes, FieldAttributes},
+    util::{
+        archive_bound, deserialize, deserialize_bound, deserialize_into,
+        is_not_omitted, is_open_enum, members, open_enum_variant_name,
+        strip_raw, unknown_variant,
+    },
 };
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
@@ -52,6 +56,9 @@ fn derive_deserialize_impl(
     let where_clause = where_clause.unwrap();
 
     let deserialize_impl = match input.data {
+        Data::Struct(ref data) if attributes.extensible.is_some() => {
+            deserialize_extensible(&input, attributes, &data.fields)?
+        }
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => {
                 let mut deserialize_where = where_clause.clone();
@@ -76,6 +83,20 @@ fn derive_deserialize_impl(
                     })
                     .collect::<Result<Vec<_>, Error>>()?;
 
+                let deserialize_into_fields = fields
+                    .named
+                    .iter()
+                    .map(|field| {
+                        let name = &field.ident;
+                        deserialize_into(
+                            &rkyv_path,
+                            field,
+                            quote! { &self.#name },
+                            quote! { &mut target.#name },
+                        )
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
                 quote! {
                     impl #impl_generics
                         #rkyv_path::Deserialize<#name #ty_generics, __D>
@@ -93,6 +114,18 @@ fn derive_deserialize_impl(
                                 #(#deserialize_fields,)*
                             })
                         }
+
+                        fn deserialize_into(
+                            &self,
+                            target: &mut #name #ty_generics,
+                            deserializer: &mut __D,
+                        ) -> ::core::result::Result<
+                            (),
+                            <__D as #rkyv_path::rancor::Fallible>::Error,
+                        > {
+                            #(#deserialize_into_fields)*
+                            Ok(())
+                        }
                     }
                 }
             }
@@ -123,6 +156,21 @@ fn derive_deserialize_impl(
                     })
                     .collect::<Result<Vec<_>, Error>>()?;
 
+                let deserialize_into_fields = fields
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        let index = Index::from(i);
+                        deserialize_into(
+                            &rkyv_path,
+                            field,
+                            quote! { &self.#index },
+                            quote! { &mut target.#index },
+                        )
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
                 quote! {
                     impl #impl_generics
                         #rkyv_path::Deserialize<#name #ty_generics, __D>
@@ -140,6 +188,18 @@ fn derive_deserialize_impl(
                                 #(#deserialize_fields,)*
                             ))
                         }
+
+                        fn deserialize_into(
+                            &self,
+                            target: &mut #name #ty_generics,
+                            deserializer: &mut __D,
+                        ) -> ::core::result::Result<
+                            (),
+                            <__D as #rkyv_path::rancor::Fallible>::Error,
+                        > {
+                            #(#deserialize_into_fields)*
+                            Ok(())
+                        }
                     }
                 }
             }
@@ -161,6 +221,9 @@ fn derive_deserialize_impl(
                 }
             },
         },
+        Data::Enum(ref data) if is_open_enum(attributes, data)? => {
+            deserialize_open_enum(&input, attributes, data)?
+        }
         Data::Enum(ref data) => {
             let mut deserialize_where = where_clause.clone();
             for variant in data.variants.iter() {
@@ -263,6 +326,108 @@ fn derive_deserialize_impl(
                 })
                 .collect::<Result<Vec<_>, Error>>()?;
 
+            let deserialize_into_variants = data
+                .variants
+                .iter()
+                .map(|v| {
+                    let variant = &v.ident;
+                    match v.fields {
+                        Fields::Named(ref fields) => {
+                            let bindings = fields.named.iter().map(|field| {
+                                let name = &field.ident;
+                                quote! { #name }
+                            });
+                            let target_bindings =
+                                fields.named.iter().enumerate().map(
+                                    |(i, field)| {
+                                        let name = &field.ident;
+                                        let binding = Ident::new(
+                                            &format!("__target_{}", i),
+                                            field.span(),
+                                        );
+                                        quote! { #name: #binding }
+                                    },
+                                );
+                            let fields = fields
+                                .named
+                                .iter()
+                                .enumerate()
+                                .map(|(i, field)| {
+                                    let name = &field.ident;
+                                    let binding = Ident::new(
+                                        &format!("__target_{}", i),
+                                        field.span(),
+                                    );
+                                    deserialize_into(
+                                        &rkyv_path,
+                                        field,
+                                        quote! { #name },
+                                        quote! { #binding },
+                                    )
+                                })
+                                .collect::<Result<Vec<_>, Error>>()?;
+                            Ok(quote! {
+                                (
+                                    Self::#variant { #(#bindings,)* },
+                                    #name::#variant { #(#target_bindings,)* },
+                                ) => {
+                                    #(#fields)*
+                                }
+                            })
+                        }
+                        Fields::Unnamed(ref fields) => {
+                            let bindings =
+                                fields.unnamed.iter().enumerate().map(
+                                    |(i, f)| {
+                                        Ident::new(&format!("_{}", i), f.span())
+                                    },
+                                );
+                            let target_bindings =
+                                fields.unnamed.iter().enumerate().map(
+                                    |(i, f)| {
+                                        Ident::new(
+                                            &format!("__target_{}", i),
+                                            f.span(),
+                                        )
+                                    },
+                                );
+                            let fields = fields
+                                .unnamed
+                                .iter()
+                                .enumerate()
+                                .map(|(i, field)| {
+                                    let binding = Ident::new(
+                                        &format!("_{}", i),
+                                        field.span(),
+                                    );
+                                    let target_binding = Ident::new(
+                                        &format!("__target_{}", i),
+                                        field.span(),
+                                    );
+                                    deserialize_into(
+                                        &rkyv_path,
+                                        field,
+                                        quote! { #binding },
+                                        quote! { #target_binding },
+                                    )
+                                })
+                                .collect::<Result<Vec<_>, Error>>()?;
+                            Ok(quote! {
+                                (
+                                    Self::#variant(#(#bindings,)*),
+                                    #name::#variant(#(#target_bindings,)*),
+                                ) => {
+                                    #(#fields)*
+                                }
+                            })
+                        }
+                        Fields::Unit => Ok(quote! {
+                            (Self::#variant, #name::#variant) => ()
+                        }),
+                    }
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
             quote! {
                 impl #impl_generics
                     #rkyv_path::Deserialize<#name #ty_generics, __D>
@@ -280,6 +445,29 @@ fn derive_deserialize_impl(
                             #(#deserialize_variants,)*
                         })
                     }
+
+                    fn deserialize_into(
+                        &self,
+                        target: &mut #name #ty_generics,
+                        deserializer: &mut __D,
+                    ) -> ::core::result::Result<
+                        (),
+                        <__D as #rkyv_path::rancor::Fallible>::Error,
+                    > {
+                        match (self, target) {
+                            #(#deserialize_into_variants,)*
+                            #[allow(unreachable_patterns)]
+                            (_, target) => {
+                                *target = <
+                                    Self as #rkyv_path::Deserialize<
+                                        #name #ty_generics,
+                                        __D,
+                                    >
+                                >::deserialize(self, deserializer)?;
+                            }
+                        }
+                        Ok(())
+                    }
                 }
             }
         }
@@ -296,3 +484,243 @@ fn derive_deserialize_impl(
         #deserialize_impl
     })
 }
+
+fn deserialize_extensible(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = attributes.crate_path();
+
+    let fields = match fields {
+        Fields::Named(fields) => fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "extensible may only be used on structs with named fields",
+            ))
+        }
+    };
+
+    let mut impl_input_params = Punctuated::default();
+    impl_input_params
+        .push(parse_quote! { __D: #rkyv_path::rancor::Fallible + ?Sized });
+    for param in input.generics.params.iter() {
+        impl_input_params.push(param.clone());
+    }
+    let impl_input_generics = Generics {
+        lt_token: Some(Default::default()),
+        params: impl_input_params,
+        gt_token: Some(Default::default()),
+        where_clause: input.generics.where_clause.clone(),
+    };
+
+    let mut deserialize_where = input.generics.where_clause.clone().unwrap();
+    for field in fields.named.iter().filter(is_not_omitted) {
+        deserialize_where
+            .predicates
+            .push(archive_bound(&rkyv_path, field)?);
+        deserialize_where
+            .predicates
+            .push(deserialize_bound(&rkyv_path, field)?);
+    }
+
+    let mut deserialize_fields = Vec::new();
+    let mut deserialize_into_fields = Vec::new();
+    for field in fields.named.iter() {
+        let name = &field.ident;
+        let deserialize = deserialize(&rkyv_path, field)?;
+
+        // Fields added in later versions may be missing from the archive, in
+        // which case they're deserialized with their default values.
+        if FieldAttributes::parse(field)?.since.is_none() {
+            deserialize_fields.push(quote! {
+                #name: #deserialize(self.#name(), deserializer)?
+            });
+            deserialize_into_fields.push(deserialize_into(
+                &rkyv_path,
+                field,
+                quote! { self.#name() },
+                quote! { &mut target.#name },
+            )?);
+        } else {
+            let ty = &field.ty;
+            deserialize_where
+                .predicates
+                .push(parse_quote! { #ty: ::core::default::Default });
+
+            let deserialize_into = deserialize_into(
+                &rkyv_path,
+                field,
+                quote! { field },
+                quote! { &mut target.#name },
+            )?;
+            deserialize_fields.push(quote! {
+                #name: match self.#name() {
+                    Some(field) => #deserialize(field, deserializer)?,
+                    None => ::core::default::Default::default(),
+                }
+            });
+            deserialize_into_fields.push(quote! {
+                match self.#name() {
+                    Some(field) => { #deserialize_into }
+                    None => target.#name = ::core::default::Default::default(),
+                }
+            });
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ..) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics
+            #rkyv_path::Deserialize<#name #ty_generics, __D>
+            for #rkyv_path::Archived<#name #ty_generics>
+        #deserialize_where
+        {
+            fn deserialize(
+                &self,
+                deserializer: &mut __D,
+            ) -> ::core::result::Result<
+                #name #ty_generics,
+                <__D as #rkyv_path::rancor::Fallible>::Error,
+            > {
+                Ok(#name {
+                    #(#deserialize_fields,)*
+                })
+            }
+
+            fn deserialize_into(
+                &self,
+                target: &mut #name #ty_generics,
+                deserializer: &mut __D,
+            ) -> ::core::result::Result<
+                (),
+                <__D as #rkyv_path::rancor::Fallible>::Error,
+            > {
+                #(#deserialize_into_fields)*
+                Ok(())
+            }
+        }
+    })
+}
+
+fn deserialize_open_enum(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    data: &DataEnum,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = attributes.crate_path();
+
+    let mut impl_input_params = Punctuated::default();
+    impl_input_params
+        .push(parse_quote! { __D: #rkyv_path::rancor::Fallible + ?Sized });
+    for param in input.generics.params.iter() {
+        impl_input_params.push(param.clone());
+    }
+    let impl_input_generics = Generics {
+        lt_token: Some(Default::default()),
+        params: impl_input_params,
+        gt_token: Some(Default::default()),
+        where_clause: input.generics.where_clause.clone(),
+    };
+
+    let mut deserialize_where = input.generics.where_clause.clone().unwrap();
+    for field in data
+        .variants
+        .iter()
+        .flat_map(|v| v.fields.iter())
+        .filter(is_not_omitted)
+    {
+        deserialize_where
+            .predicates
+            .push(archive_bound(&rkyv_path, field)?);
+        deserialize_where
+            .predicates
+            .push(deserialize_bound(&rkyv_path, field)?);
+    }
+
+    let name = &input.ident;
+    let archived_name = attributes.archived.as_ref().map_or_else(
+        || Ident::new(&format!("Archived{}", strip_raw(name)), name.span()),
+        |value| value.clone(),
+    );
+    let variant_name = open_enum_variant_name(&archived_name);
+
+    let unknown = unknown_variant(data)?;
+    let mut deserialize_arms = data
+        .variants
+        .iter()
+        .filter(|v| Some(&v.ident) != unknown.map(|u| &u.ident))
+        .map(|v| {
+            let variant = &v.ident;
+            let values = members(&v.fields)
+                .map(|(member, field)| {
+                    let deserialize = deserialize(&rkyv_path, field)?;
+                    Ok(quote! {
+                        #member: #deserialize(&fields.#member, deserializer)?
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            if matches!(v.fields, Fields::Unit) {
+                Ok(quote! { #variant_name::#variant => #name::#variant })
+            } else {
+                Ok(quote! {
+                    #variant_name::#variant(fields) => #name::#variant {
+                        #(#values,)*
+                    }
+                })
+            }
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    // Unknown variants are deserialized as the unknown variant if there is
+    // one, and are otherwise an error.
+    match unknown {
+        Some(unknown) => {
+            let unknown = &unknown.ident;
+            deserialize_arms.push(quote! {
+                #variant_name::#unknown(_) => #name::#unknown
+            });
+        }
+        None => {
+            deserialize_where.predicates.push(parse_quote! {
+                <__D as #rkyv_path::rancor::Fallible>::Error:
+                    #rkyv_path::rancor::Source
+            });
+            deserialize_arms.push(quote! {
+                #variant_name::Unknown(tag) => {
+                    return Err(#rkyv_path::rancor::Source::new(
+                        #rkyv_path::extensible::UnknownVariant::new(tag),
+                    ));
+                }
+            });
+        }
+    }
+
+    let (impl_generics, ..) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics
+            #rkyv_path::Deserialize<#name #ty_generics, __D>
+            for #rkyv_path::Archived<#name #ty_generics>
+        #deserialize_where
+        {
+            fn deserialize(
+                &self,
+                deserializer: &mut __D,
+            ) -> ::core::result::Result<
+                #name #ty_generics,
+                <__D as #rkyv_path::rancor::Fallible>::Error,
+            > {
+                Ok(match self.variant() {
+                    #(#deserialize_arms,)*
+                })
+            }
+        }
+    })
+}
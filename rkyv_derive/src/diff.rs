@@ -0,0 +1,227 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, punctuated::Punctuated, Data, DeriveInput, Error, Fields,
+    Generics, Ident,
+};
+
+use crate::{
+    attributes::Attributes,
+    util::{is_not_omitted, is_open_enum, strip_raw},
+};
+
+pub fn derive(mut input: DeriveInput) -> Result<TokenStream, Error> {
+    let attributes = Attributes::parse(&input)?;
+    let rkyv_path = attributes.crate_path();
+
+    if let Some(ref archive_as) = attributes.archive_as {
+        return Err(Error::new_spanned(
+            archive_as,
+            "Diff cannot be derived with as = \"...\"",
+        ));
+    }
+    if let Some(ref extensible) = attributes.extensible {
+        return Err(Error::new_spanned(
+            extensible,
+            "Diff cannot be derived for extensible structs",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect::<Vec<_>>(),
+        Data::Enum(data) => {
+            if is_open_enum(&attributes, data)? {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "Diff cannot be derived for open enums",
+                ));
+            }
+            data.variants.iter().flat_map(|v| v.fields.iter()).collect()
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Diff cannot be derived for unions",
+            ))
+        }
+    };
+    if let Some(attr) = fields
+        .iter()
+        .flat_map(|f| f.attrs.iter())
+        .find(|attr| attr.meta.path().is_ident("with"))
+    {
+        return Err(Error::new_spanned(
+            attr,
+            "Diff cannot be derived for fields with wrappers",
+        ));
+    }
+
+    let where_clause = input.generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bounds {
+        for bound in bounds {
+            where_clause.predicates.push(bound.clone());
+        }
+    }
+    for field in fields.into_iter().filter(is_not_omitted) {
+        let ty = &field.ty;
+        where_clause.predicates.push(parse_quote! {
+            #ty: #rkyv_path::diff::Diff<__E>
+        });
+    }
+    // These are the supertraits of `Diff`, which have to be proven for every
+    // set of generic parameters.
+    where_clause.predicates.push(parse_quote! {
+        <Self as #rkyv_path::Archive>::Archived:
+            #rkyv_path::Deserialize<
+                Self,
+                #rkyv_path::rancor::Strategy<#rkyv_path::de::Pool, __E>,
+            >
+            + for<'__a> #rkyv_path::bytecheck::CheckBytes<
+                #rkyv_path::rancor::Strategy<
+                    #rkyv_path::validation::validators::DefaultValidator<'__a>,
+                    __E,
+                >,
+            >
+    });
+    where_clause.predicates.push(parse_quote! {
+        Self: for<'__a> #rkyv_path::Serialize<
+            #rkyv_path::ser::DefaultSerializer<
+                '__a,
+                #rkyv_path::util::AlignedVec,
+                __E,
+            >,
+        >
+    });
+
+    let name = &input.ident;
+    let archived_name = attributes.archived.clone().unwrap_or_else(|| {
+        Ident::new(&format!("Archived{}", strip_raw(name)), name.span())
+    });
+
+    let variants = match &input.data {
+        Data::Struct(data) => {
+            vec![(quote!(#archived_name), quote!(#name), &data.fields)]
+        }
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|v| {
+                let variant = &v.ident;
+                (
+                    quote!(#archived_name::#variant),
+                    quote!(#name::#variant),
+                    &v.fields,
+                )
+            })
+            .collect(),
+        Data::Union(_) => unreachable!(),
+    };
+
+    let mut diff_arms = Vec::new();
+    let mut patch_arms = Vec::new();
+    for (archived_path, path, fields) in variants {
+        let old = pattern(&archived_path, fields, "__old");
+        let new = pattern(&archived_path, fields, "__new");
+        let pushes = fields.iter().enumerate().map(|(i, field)| {
+            let ty = &field.ty;
+            let old = format_ident!("__old_{}", i);
+            let new = format_ident!("__new_{}", i);
+            quote! {
+                changes.push::<#ty, __E>(#i, #old, #new)?;
+            }
+        });
+        diff_arms.push(quote! {
+            (#old, #new) => {
+                #[allow(unused_mut)]
+                let mut changes = #rkyv_path::diff::FieldChanges::new();
+                #(#pushes)*
+                Ok(changes.finish())
+            }
+        });
+
+        let patched = fields.iter().enumerate().map(|(i, field)| {
+            let ty = &field.ty;
+            let old = format_ident!("__old_{}", i);
+            quote! { patches.patch::<#ty, __E>(#i, #old)? }
+        });
+        let value = match fields {
+            Fields::Named(_) => {
+                let names = fields.iter().map(|f| &f.ident);
+                quote! { #path { #(#names: #patched,)* } }
+            }
+            Fields::Unnamed(_) => quote! { #path(#(#patched,)*) },
+            Fields::Unit => quote! { #path },
+        };
+        patch_arms.push(quote! {
+            (#old, #rkyv_path::diff::ArchivedChange::Fields(changes)) => {
+                #[allow(unused_mut)]
+                let mut patches =
+                    #rkyv_path::diff::FieldPatches::new(changes);
+                let value = #value;
+                patches.finish()?;
+                Ok(value)
+            }
+        });
+    }
+
+    let mut impl_params = Punctuated::default();
+    impl_params.push(parse_quote! { __E: #rkyv_path::rancor::Source });
+    for param in input.generics.params.iter() {
+        impl_params.push(param.clone());
+    }
+    let impl_generics = Generics {
+        lt_token: Some(Default::default()),
+        params: impl_params,
+        gt_token: Some(Default::default()),
+        where_clause: input.generics.where_clause.clone(),
+    };
+    let (impl_generics, ..) = impl_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv_path::diff::Diff<__E> for #name #ty_generics
+        #where_clause
+        {
+            #[allow(unreachable_patterns)]
+            fn diff(
+                old: &<Self as #rkyv_path::Archive>::Archived,
+                new: &<Self as #rkyv_path::Archive>::Archived,
+            ) -> ::core::result::Result<
+                ::core::option::Option<#rkyv_path::diff::Change>,
+                __E,
+            > {
+                match (old, new) {
+                    #(#diff_arms)*
+                    _ => #rkyv_path::diff::replace::<Self, __E>(new)
+                        .map(::core::option::Option::Some),
+                }
+            }
+
+            fn patch(
+                old: &<Self as #rkyv_path::Archive>::Archived,
+                change: &#rkyv_path::diff::ArchivedChange,
+            ) -> ::core::result::Result<Self, __E> {
+                match (old, change) {
+                    (_, #rkyv_path::diff::ArchivedChange::Replace(bytes)) => {
+                        #rkyv_path::diff::replaced::<Self, __E>(bytes)
+                    }
+                    #(#patch_arms)*
+                    _ => #rkyv_path::diff::invalid_change(),
+                }
+            }
+        }
+    })
+}
+
+fn pattern(path: &TokenStream, fields: &Fields, prefix: &str) -> TokenStream {
+    let bindings = (0..fields.len()).map(|i| format_ident!("{}_{}", prefix, i));
+    match fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|f| &f.ident);
+            quote! { #path { #(#names: #bindings,)* } }
+        }
+        Fields::Unnamed(_) => quote! { #path(#(#bindings,)*) },
+        Fields::Unit => quote! { #path },
+    }
+}
@@ -9,8 +9,11 @@
 mod archive;
 mod attributes;
 mod deserialize;
+mod diff;
 mod portable;
+mod relocate;
 mod repr;
+mod schema;
 mod serde;
 mod serialize;
 mod util;
@@ -68,6 +71,33 @@ pub fn derive_portable(
 ///   enable safe deserialization. Requires `validation` feature. Not compatible
 ///   with `as = "..."`. In that case, use `#[derive(CheckBytes)]` on the
 ///   archived type, and include a `use rkyv::bytecheck` statement.
+/// - `relocate`: Derive `Relocate` on the archived type, which allows archived
+///   values to be moved around in place (e.g. when sorting an archived vec).
+///   Not compatible with `as = "..."`. In that case, use `#[derive(Relocate)]`
+///   and `#[relocate(unsafe_no_offsets)]` on the archived type instead.
+/// - `schema`: Derive `ArchiveSchema` on the archived type, which describes the
+///   layout of the archived type at runtime. Requires the `alloc` feature. Not
+///   compatible with `as = "..."`. In that case, use `#[derive(ArchiveSchema)]`
+///   on the archived type instead. Recursive types should use
+///   `#[archive_attr(omit_bounds)]` on the same fields as `#[omit_bounds]`.
+/// - `extensible`: Stores the fields of the struct out-of-line behind a header
+///   with their length and version, so that fields can be added in later
+///   versions without breaking older archives or readers. Fields added in later
+///   versions are marked with `#[archive(since = N)]`, must come after the
+///   fields of earlier versions, and must implement `Default`. The archived
+///   type provides a method to access each field instead of public fields.
+///   Only supported on structs with named fields. Not compatible with `as =
+///   "..."`, `relocate`, or `compare(...)`.
+/// - `open`: Stores the fields of each enum variant out-of-line behind a
+///   header with their tag and length, so that variants can be added in later
+///   versions without breaking older readers. Readers see variants they don't
+///   know about as `Unknown(tag)` instead of failing validation, and
+///   deserializing them fails. A unit variant may instead be marked with
+///   `#[archive(unknown)]` to make it the catch-all, in which case unknown
+///   variants are deserialized as that variant and `open` may be omitted. The
+///   archived type provides a `variant` method which returns the active
+///   variant. Only supported on enums. Not compatible with `as = "..."`,
+///   `relocate`, `compare(...)`, or `schema`.
 /// - `as = "..."`: Instead of generating a separate archived type, this type
 ///   will archive as the named type. This is useful for types which are generic
 ///   over their parameters.
@@ -113,6 +143,70 @@ pub fn derive_archive(
     }
 }
 
+/// Derives `Relocate` for the labeled type.
+///
+/// The type must also implement `Portable`. All of its fields must implement
+/// `Relocate`.
+///
+/// The derived implementation only merges the extents of the fields and
+/// relocates each of them, so any relative offset stored in a field which
+/// doesn't fix it up (for example, a plain integer) would be left dangling. Because this can't be checked, the
+/// type must be marked with `#[relocate(unsafe_no_offsets)]` to assert that it
+/// doesn't contain any such offsets. Types generated with
+/// `#[archive(relocate)]` are marked automatically.
+#[proc_macro_derive(Relocate, attributes(archive, relocate))]
+pub fn derive_relocate(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut derive_input = parse_macro_input!(input as DeriveInput);
+    serde::receiver::replace_receiver(&mut derive_input);
+
+    match relocate::derive(derive_input) {
+        Ok(result) => result.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derives `ArchiveSchema` for the labeled type.
+///
+/// The type must also implement `Portable`. All of its fields must implement
+/// `ArchiveSchema`, and enums must be `#[repr(u8)]` or `#[repr(i8)]`.
+///
+/// This macro also supports the `#[omit_bounds]` attribute. See [`Archive`] for
+/// more information.
+#[proc_macro_derive(ArchiveSchema, attributes(archive, omit_bounds))]
+pub fn derive_archive_schema(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut derive_input = parse_macro_input!(input as DeriveInput);
+    serde::receiver::replace_receiver(&mut derive_input);
+
+    match schema::derive(derive_input) {
+        Ok(result) => result.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derives `Diff` for the labeled type.
+///
+/// The type must also implement `Archive`, `Serialize`, and `Deserialize`, and
+/// its archived type must implement `CheckBytes`. All of its fields must
+/// implement `Diff`. Fields with wrappers, extensible structs, and open enums
+/// are not supported.
+///
+/// This macro also supports the `#[omit_bounds]` attribute. See [`Archive`] for
+/// more information.
+#[proc_macro_derive(Diff, attributes(archive, omit_bounds))]
+pub fn derive_diff(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut derive_input = parse_macro_input!(input as DeriveInput);
+    serde::receiver::replace_receiver(&mut derive_input);
+
+    match diff::derive(derive_input) {
+        Ok(result) => result.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 /// Derives `Serialize` for the labeled type.
 ///
 /// This macro also supports the `#[archive]`, `#[omit_bounds]`, and `#[with]`
@@ -0,0 +1,229 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, spanned::Spanned as _, Data, DeriveInput, Error, Fields,
+    Ident, Path,
+};
+
+use crate::{attributes::Attributes, util::members};
+
+pub fn derive(mut input: DeriveInput) -> Result<TokenStream, Error> {
+    let attributes = Attributes::parse(&input)?;
+    let rkyv_path = attributes.crate_path();
+
+    if !has_unsafe_no_offsets(&input)? {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "deriving Relocate requires #[relocate(unsafe_no_offsets)] to \
+             assert that every relative offset in the type is stored in a \
+             field which implements Relocate",
+        ));
+    }
+
+    let where_clause = input.generics.make_where_clause();
+
+    let (extent, can_relocate, relocate) = match &input.data {
+        Data::Struct(data) => {
+            for field in data.fields.iter() {
+                let ty = &field.ty;
+                where_clause.predicates.push(parse_quote! {
+                    #ty: #rkyv_path::Relocate
+                });
+            }
+
+            generate_struct_bodies(&rkyv_path, &data.fields)
+        }
+        Data::Enum(data) => {
+            for field in data.variants.iter().flat_map(|v| v.fields.iter()) {
+                let ty = &field.ty;
+                where_clause.predicates.push(parse_quote! {
+                    #ty: #rkyv_path::Relocate
+                });
+            }
+
+            let mut extent_arms = Vec::new();
+            let mut can_relocate_arms = Vec::new();
+            let mut relocate_arms = Vec::new();
+            for variant in data.variants.iter() {
+                let (extent, can_relocate, relocate) = generate_variant_arms(
+                    &rkyv_path,
+                    &variant.ident,
+                    &variant.fields,
+                );
+                extent_arms.push(extent);
+                can_relocate_arms.push(can_relocate);
+                relocate_arms.push(relocate);
+            }
+
+            (
+                quote! {
+                    match self {
+                        #(#extent_arms,)*
+                    }
+                },
+                quote! {
+                    match self {
+                        #(#can_relocate_arms,)*
+                    }
+                },
+                quote! {
+                    // SAFETY: The fields of the enum are never moved out of
+                    // their pinned locations.
+                    match unsafe { ::core::pin::Pin::get_unchecked_mut(self) } {
+                        #(#relocate_arms,)*
+                    }
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Relocate cannot be derived for unions",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    Ok(quote! {
+        // SAFETY: The extent of the type covers the extents of all of its
+        // fields, and every field is relocated by the same `delta`.
+        unsafe impl #impl_generics #rkyv_path::Relocate for #name #ty_generics
+        #where_clause
+        {
+            fn extent(&self) -> ::core::option::Option<#rkyv_path::Extent> {
+                #extent
+            }
+
+            fn can_relocate(&self, delta: isize) -> bool {
+                #can_relocate
+            }
+
+            unsafe fn relocate(
+                self: ::core::pin::Pin<&mut Self>,
+                delta: isize,
+            ) {
+                #relocate
+            }
+        }
+    })
+}
+
+fn has_unsafe_no_offsets(input: &DeriveInput) -> Result<bool, Error> {
+    let mut result = false;
+    for attr in input.attrs.iter() {
+        if attr.path().is_ident("relocate") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("unsafe_no_offsets") {
+                    result = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unrecognized relocate argument"))
+                }
+            })?;
+        }
+    }
+    Ok(result)
+}
+
+fn generate_struct_bodies(
+    rkyv_path: &Path,
+    fields: &Fields,
+) -> (TokenStream, TokenStream, TokenStream) {
+    let members = members(fields)
+        .map(|(member, _)| member)
+        .collect::<Vec<_>>();
+    let extent = quote! {
+        let extent = ::core::option::Option::None;
+        #(
+            let extent = #rkyv_path::Extent::merge(
+                extent,
+                #rkyv_path::Relocate::extent(&self.#members),
+            );
+        )*
+        extent
+    };
+    let can_relocate = quote! {
+        true #(&& #rkyv_path::Relocate::can_relocate(&self.#members, delta))*
+    };
+
+    if fields.is_empty() {
+        return (extent, can_relocate, TokenStream::new());
+    }
+
+    let relocate = quote! {
+        // SAFETY: The fields of the struct are never moved out of their pinned
+        // locations.
+        let this = unsafe { ::core::pin::Pin::get_unchecked_mut(self) };
+        #(
+            // SAFETY: The caller has upheld the safety requirements of
+            // `relocate` for the struct, and so also for each of its fields.
+            unsafe {
+                #rkyv_path::Relocate::relocate(
+                    ::core::pin::Pin::new_unchecked(&mut this.#members),
+                    delta,
+                );
+            }
+        )*
+    };
+
+    (extent, can_relocate, relocate)
+}
+
+fn generate_variant_arms(
+    rkyv_path: &Path,
+    variant: &Ident,
+    fields: &Fields,
+) -> (TokenStream, TokenStream, TokenStream) {
+    let bindings = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| Ident::new(&format!("field_{}", i), f.span()))
+        .collect::<Vec<_>>();
+
+    let pattern = match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|f| &f.ident);
+            quote! { Self::#variant { #(#names: #bindings,)* } }
+        }
+        Fields::Unnamed(_) => quote! { Self::#variant(#(#bindings,)*) },
+        Fields::Unit => quote! { Self::#variant },
+    };
+
+    (
+        quote! {
+            #pattern => {
+                let extent = ::core::option::Option::None;
+                #(
+                    let extent = #rkyv_path::Extent::merge(
+                        extent,
+                        #rkyv_path::Relocate::extent(#bindings),
+                    );
+                )*
+                extent
+            }
+        },
+        quote! {
+            #pattern => {
+                true #(&& #rkyv_path::Relocate::can_relocate(#bindings, delta))*
+            }
+        },
+        quote! {
+            #pattern => {
+                #(
+                    // SAFETY: The caller has upheld the safety requirements of
+                    // `relocate` for the enum, and so also for each of the
+                    // fields of the active variant.
+                    unsafe {
+                        #rkyv_path::Relocate::relocate(
+                            ::core::pin::Pin::new_unchecked(#bindings),
+                            delta,
+                        );
+                    }
+                )*
+            }
+        },
+    )
+}
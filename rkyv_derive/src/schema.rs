@@ -0,0 +1,209 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Generics, Ident, Index, Member,
+    Path, Type,
+};
+
+use crate::{
+    attributes::Attributes,
+    repr::{Primitive, Repr},
+    util::{is_not_omitted, members, strip_raw},
+};
+
+pub fn derive(mut input: DeriveInput) -> Result<TokenStream, Error> {
+    let attributes = Attributes::parse(&input)?;
+    let rkyv_path = attributes.crate_path();
+
+    // The original generics are used to declare the variant structs of enums,
+    // which don't need the schema bounds.
+    let generics = input.generics.clone();
+
+    let where_clause = input.generics.make_where_clause();
+    let fields = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect::<Vec<_>>(),
+        Data::Enum(data) => {
+            data.variants.iter().flat_map(|v| v.fields.iter()).collect()
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "ArchiveSchema cannot be derived for unions",
+            ))
+        }
+    };
+    for field in fields.into_iter().filter(is_not_omitted) {
+        let ty = &field.ty;
+        where_clause.predicates.push(parse_quote! {
+            #ty: #rkyv_path::schema::ArchiveSchema
+        });
+    }
+    // Types are identified by their `TypeId` in the registry.
+    where_clause.predicates.push(parse_quote! { Self: 'static });
+
+    let (variant_structs, kind) = match &input.data {
+        Data::Struct(data) => {
+            let fields = generate_fields(
+                &rkyv_path,
+                members(&data.fields).map(|(member, f)| {
+                    (
+                        field_name(&member),
+                        &f.ty,
+                        quote! { ::core::mem::offset_of!(Self, #member) },
+                    )
+                }),
+            );
+            (
+                TokenStream::new(),
+                quote! { #rkyv_path::schema::Kind::Struct(#fields) },
+            )
+        }
+        Data::Enum(_) => generate_enum(&rkyv_path, &input, &generics)?,
+        Data::Union(_) => unreachable!(),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        const _: () = {
+            #variant_structs
+
+            impl #impl_generics #rkyv_path::schema::ArchiveSchema
+                for #name #ty_generics
+            #where_clause
+            {
+                fn describe(
+                    registry: &mut #rkyv_path::schema::Registry,
+                ) -> #rkyv_path::schema::TypeSchema {
+                    #rkyv_path::schema::TypeSchema::new::<Self>(#kind)
+                }
+            }
+        };
+    })
+}
+
+fn generate_fields<'a>(
+    rkyv_path: &Path,
+    fields: impl Iterator<Item = (String, &'a Type, TokenStream)>,
+) -> TokenStream {
+    let fields = fields.map(|(name, ty, offset)| {
+        quote! {
+            #rkyv_path::schema::Field::new::<#ty>(registry, #name, #offset)
+        }
+    });
+
+    quote! {
+        ::core::convert::Into::into([#(#fields,)*])
+    }
+}
+
+fn field_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => strip_raw(ident),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+fn generate_enum(
+    rkyv_path: &Path,
+    input: &DeriveInput,
+    generics: &Generics,
+) -> Result<(TokenStream, TokenStream), Error> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => unreachable!(),
+    };
+
+    let repr = match Repr::from_attrs(&input.attrs)? {
+        Repr::Primitive(Primitive::U8) => quote!(u8),
+        Repr::Primitive(Primitive::I8) => quote!(i8),
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "ArchiveSchema can only be derived for enums with \
+                 #[repr(u8)] or #[repr(i8)]",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let where_clause = &generics.where_clause;
+
+    let mut variant_structs = Vec::new();
+    let mut variants = Vec::new();
+    let mut base = None;
+    let mut offset = 0u8;
+    for variant in data.variants.iter() {
+        if let Some((_, discriminant)) = &variant.discriminant {
+            base = Some(discriminant);
+            offset = 0;
+        }
+        let tag = match base {
+            Some(base) => quote! {{
+                const BASE: #repr = #base;
+                (BASE as u8).wrapping_add(#offset)
+            }},
+            None => quote!(#offset),
+        };
+        offset = offset.wrapping_add(1);
+
+        // Each variant of an enum with a primitive repr is laid out like a
+        // `repr(C)` struct with the tag as its first field.
+        let struct_name = Ident::new(
+            &format!("__Variant{}", strip_raw(&variant.ident)),
+            variant.ident.span(),
+        );
+        let field_tys = variant.fields.iter().map(|f| &f.ty);
+        variant_structs.push(quote! {
+            #[allow(dead_code)]
+            #[repr(C)]
+            struct #struct_name #generics (
+                u8,
+                #(#field_tys,)*
+                ::core::marker::PhantomData<#name #ty_generics>,
+            )
+            #where_clause;
+        });
+
+        let fields = generate_fields(
+            rkyv_path,
+            members(&variant.fields)
+                .enumerate()
+                .map(|(i, (member, f))| {
+                    let index = Index::from(i + 1);
+                    (
+                        field_name(&member),
+                        &f.ty,
+                        quote! {
+                            ::core::mem::offset_of!(
+                                #struct_name #ty_generics,
+                                #index
+                            )
+                        },
+                    )
+                }),
+        );
+
+        let variant_name = strip_raw(&variant.ident);
+        variants.push(quote! {
+            #rkyv_path::schema::Variant {
+                name: ::core::convert::Into::into(#variant_name),
+                tag: #tag,
+                fields: #fields,
+            }
+        });
+    }
+
+    Ok((
+        quote! { #(#variant_structs)* },
+        quote! {
+            #rkyv_path::schema::Kind::Enum(::core::convert::Into::into([
+                #(#variants,)*
+            ]))
+        },
+    ))
+}
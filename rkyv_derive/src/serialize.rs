@@ -1,13 +1,16 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
-    parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
-    Error, Fields, Generics, Ident, Index,
+    parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DataEnum,
+    DeriveInput, Error, Fields, Generics, Ident, Index,
 };
 
 use crate::{
     attributes::Attributes,
-    util::{is_not_omitted, serialize, serialize_bound, strip_raw},
+    util::{
+        extensible_fields_name, is_not_omitted, is_open_enum, members,
+        open_enum_fields_name, resolve, serialize, serialize_bound, strip_raw,
+    },
 };
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
@@ -58,6 +61,9 @@ fn derive_serialize_impl(
 
     let serialize_impl =
         match input.data {
+            Data::Struct(ref data) if attributes.extensible.is_some() => {
+                serialize_extensible(&input, attributes, &data.fields)?
+            }
             Data::Struct(ref data) => match data.fields {
                 Fields::Named(ref fields) => {
                     let mut serialize_where = where_clause.clone();
@@ -149,6 +155,9 @@ fn derive_serialize_impl(
                     }
                 }
             },
+            Data::Enum(ref data) if is_open_enum(attributes, data)? => {
+                serialize_open_enum(&input, attributes, data)?
+            }
             Data::Enum(ref data) => {
                 let mut serialize_where = where_clause.clone();
                 for variant in data.variants.iter() {
@@ -261,3 +270,237 @@ fn derive_serialize_impl(
         #serialize_impl
     })
 }
+
+fn serialize_extensible(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = attributes.crate_path();
+
+    let mut impl_input_params = Punctuated::default();
+    impl_input_params.push(parse_quote! {
+        __S: #rkyv_path::rancor::Fallible + #rkyv_path::ser::Writer + ?Sized
+    });
+    for param in input.generics.params.iter() {
+        impl_input_params.push(param.clone());
+    }
+    let impl_input_generics = Generics {
+        lt_token: Some(Default::default()),
+        params: impl_input_params,
+        gt_token: Some(Default::default()),
+        where_clause: input.generics.where_clause.clone(),
+    };
+
+    let mut serialize_where = input.generics.where_clause.clone().unwrap();
+    for field in fields.iter().filter(is_not_omitted) {
+        serialize_where
+            .predicates
+            .push(serialize_bound(&rkyv_path, field)?);
+    }
+
+    let resolver_names = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| Ident::new(&format!("resolver_{}", i), field.span()))
+        .collect::<Vec<_>>();
+    let serialize_fields = members(fields)
+        .zip(resolver_names.iter())
+        .map(|((member, field), resolver_name)| {
+            let serialize = serialize(&rkyv_path, field)?;
+            Ok(quote! {
+                let #resolver_name = #serialize(&self.#member, serializer)?;
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let resolve_fields = members(fields)
+        .zip(resolver_names.iter())
+        .map(|((member, field), resolver_name)| {
+            let resolves = resolve(&rkyv_path, field)?;
+            Ok(quote! {
+                let field_ptr = unsafe {
+                    ::core::ptr::addr_of_mut!((*out.ptr()).#member)
+                };
+                let out_field = unsafe {
+                    #rkyv_path::Place::from_field_unchecked(out, field_ptr)
+                };
+                #resolves(&self.#member, #resolver_name, out_field);
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let name = &input.ident;
+    let archived_name = attributes.archived.as_ref().map_or_else(
+        || Ident::new(&format!("Archived{}", strip_raw(name)), name.span()),
+        |value| value.clone(),
+    );
+    let fields_name = extensible_fields_name(&archived_name);
+    let (impl_generics, ..) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #rkyv_path::Serialize<__S> for #name #ty_generics
+        #serialize_where
+        {
+            // Some resolvers will be (), this allow is to prevent clippy
+            // from complaining.
+            #[allow(clippy::unit_arg)]
+            fn serialize(
+                &self,
+                serializer: &mut __S,
+            ) -> ::core::result::Result<
+                Self::Resolver,
+                <__S as #rkyv_path::rancor::Fallible>::Error,
+            > {
+                #(#serialize_fields)*
+                #rkyv_path::extensible::ArchivedExtensible::<
+                    #fields_name #ty_generics,
+                >::serialize_fields(serializer, |out| {
+                    #(#resolve_fields)*
+                })
+            }
+        }
+    })
+}
+
+fn serialize_open_enum(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    data: &DataEnum,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = attributes.crate_path();
+
+    let mut impl_input_params = Punctuated::default();
+    impl_input_params.push(parse_quote! {
+        __S: #rkyv_path::rancor::Fallible + #rkyv_path::ser::Writer + ?Sized
+    });
+    for param in input.generics.params.iter() {
+        impl_input_params.push(param.clone());
+    }
+    let impl_input_generics = Generics {
+        lt_token: Some(Default::default()),
+        params: impl_input_params,
+        gt_token: Some(Default::default()),
+        where_clause: input.generics.where_clause.clone(),
+    };
+
+    let mut serialize_where = input.generics.where_clause.clone().unwrap();
+    for field in data
+        .variants
+        .iter()
+        .flat_map(|v| v.fields.iter())
+        .filter(is_not_omitted)
+    {
+        serialize_where
+            .predicates
+            .push(serialize_bound(&rkyv_path, field)?);
+    }
+
+    let name = &input.ident;
+    let archived_name = attributes.archived.as_ref().map_or_else(
+        || Ident::new(&format!("Archived{}", strip_raw(name)), name.span()),
+        |value| value.clone(),
+    );
+    let (impl_generics, ..) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let serialize_arms = data
+        .variants
+        .iter()
+        .map(|v| {
+            let variant = &v.ident;
+            let (members, fields) =
+                members(&v.fields).unzip::<_, _, Vec<_>, Vec<_>>();
+            let (self_bindings, resolver_bindings) = fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    (
+                        Ident::new(&format!("self_{}", i), field.span()),
+                        Ident::new(&format!("resolver_{}", i), field.span()),
+                    )
+                })
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            let serializes = fields
+                .iter()
+                .map(|field| serialize(&rkyv_path, field))
+                .collect::<Result<Vec<_>, Error>>()?;
+            let resolves = fields
+                .iter()
+                .map(|field| resolve(&rkyv_path, field))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let pattern = match v.fields {
+                Fields::Named(_) => quote! {
+                    Self::#variant { #(#members: #self_bindings,)* }
+                },
+                Fields::Unnamed(_) => {
+                    quote! { Self::#variant(#(#self_bindings,)*) }
+                }
+                Fields::Unit => quote! { Self::#variant },
+            };
+            let (out, fields_ty) = if matches!(v.fields, Fields::Unit) {
+                (quote! { _ }, quote! { () })
+            } else {
+                let fields_name =
+                    open_enum_fields_name(&archived_name, variant);
+                (quote! { out }, quote! { #fields_name #ty_generics })
+            };
+
+            Ok(quote! {
+                #pattern => {
+                    #(
+                        let #resolver_bindings =
+                            #serializes(#self_bindings, serializer)?;
+                    )*
+                    #rkyv_path::extensible::ArchivedOpenEnum::<
+                        #archived_name #ty_generics,
+                    >::serialize_variant(
+                        serializer,
+                        |#out: #rkyv_path::Place<#fields_ty>| {
+                            #(
+                                let field_ptr = unsafe {
+                                    ::core::ptr::addr_of_mut!(
+                                        (*out.ptr()).#members
+                                    )
+                                };
+                                let out_field = unsafe {
+                                    #rkyv_path::Place::from_field_unchecked(
+                                        out,
+                                        field_ptr,
+                                    )
+                                };
+                                #resolves(
+                                    #self_bindings,
+                                    #resolver_bindings,
+                                    out_field,
+                                );
+                            )*
+                        },
+                    )
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(quote! {
+        impl #impl_generics #rkyv_path::Serialize<__S> for #name #ty_generics
+        #serialize_where
+        {
+            // Some resolvers will be (), this allow is to prevent clippy
+            // from complaining.
+            #[allow(clippy::unit_arg)]
+            fn serialize(
+                &self,
+                serializer: &mut __S,
+            ) -> ::core::result::Result<
+                Self::Resolver,
+                <__S as #rkyv_path::rancor::Fallible>::Error,
+            > {
+                match self {
+                    #(#serialize_arms,)*
+                }
+            }
+        }
+    })
+}
@@ -1,10 +1,12 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{
-    parse_quote, Error, Field, Fields, Index, Member, Meta, Path, Type,
-    WherePredicate,
+    parse_quote, DataEnum, Error, Field, Fields, Index, Member, Meta, Path,
+    Type, Variant, WherePredicate,
 };
 
+use crate::attributes::{Attributes, FieldAttributes, VariantAttributes};
+
 pub fn strip_raw(ident: &Ident) -> String {
     let as_string = ident.to_string();
     as_string
@@ -23,6 +25,79 @@ pub fn is_not_omitted(f: &&Field) -> bool {
     })
 }
 
+pub fn extensible_fields_name(archived_name: &Ident) -> Ident {
+    Ident::new(
+        &format!("{}Fields", strip_raw(archived_name)),
+        archived_name.span(),
+    )
+}
+
+pub fn field_versions(fields: &Fields) -> Result<Vec<u32>, Error> {
+    let mut versions = Vec::with_capacity(fields.len());
+    let mut latest = 0;
+    for field in fields.iter() {
+        let attributes = FieldAttributes::parse(field)?;
+        let version = attributes.since()?;
+        if version < latest {
+            return Err(Error::new_spanned(
+                attributes.since,
+                format!(
+                    "fields must be ordered by the version they were added \
+                     in, but this field follows a field added in version {}",
+                    latest,
+                ),
+            ));
+        }
+        latest = version;
+        versions.push(version);
+    }
+    Ok(versions)
+}
+
+pub fn open_enum_fields_name(archived_name: &Ident, variant: &Ident) -> Ident {
+    Ident::new(
+        &format!("{}{}", strip_raw(archived_name), strip_raw(variant)),
+        variant.span(),
+    )
+}
+
+pub fn open_enum_variant_name(archived_name: &Ident) -> Ident {
+    Ident::new(
+        &format!("{}Variant", strip_raw(archived_name)),
+        archived_name.span(),
+    )
+}
+
+pub fn unknown_variant(data: &DataEnum) -> Result<Option<&Variant>, Error> {
+    let mut result = None;
+    for variant in data.variants.iter() {
+        let attributes = VariantAttributes::parse(variant)?;
+        if let Some(unknown) = attributes.unknown {
+            if result.is_some() {
+                return Err(Error::new_spanned(
+                    unknown,
+                    "only one variant may be marked as unknown",
+                ));
+            }
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::new_spanned(
+                    unknown,
+                    "the unknown variant must be a unit variant",
+                ));
+            }
+            result = Some(variant);
+        }
+    }
+    Ok(result)
+}
+
+pub fn is_open_enum(
+    attributes: &Attributes,
+    data: &DataEnum,
+) -> Result<bool, Error> {
+    Ok(attributes.open.is_some() || unknown_variant(data)?.is_some())
+}
+
 pub fn members_starting_at(
     fields: &Fields,
     start: usize,
@@ -210,3 +285,36 @@ pub fn deserialize(
         },
     )
 }
+
+pub fn deserialize_into(
+    rkyv_path: &Path,
+    field: &Field,
+    value: TokenStream,
+    target: TokenStream,
+) -> Result<TokenStream, Error> {
+    let ty = &field.ty;
+
+    let archived = archived(rkyv_path, field)?;
+
+    map_with_or_else(
+        field,
+        |with_ty| {
+            quote! {
+                *#target = <
+                    #with_ty as #rkyv_path::with::DeserializeWith<
+                        #archived,
+                        #ty,
+                        __D,
+                    >
+                >::deserialize_with(#value, deserializer)?;
+            }
+        },
+        || {
+            quote! {
+                <
+                    #archived as #rkyv_path::Deserialize<#ty, __D>
+                >::deserialize_into(#value, #target, deserializer)?;
+            }
+        },
+    )
+}